@@ -1,5 +1,5 @@
 use crate::config::FailOn;
-use crate::report::ReportFormat;
+use crate::report::{GroupBy, ReportFormat};
 use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -10,6 +10,10 @@ use std::path::PathBuf;
     about = "Repository footgun scanner for modern stacks"
 )]
 pub struct Cli {
+    #[arg(long, global = true)]
+    pub offline: bool,
+    #[arg(long, global = true)]
+    pub sandbox: bool,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -19,6 +23,12 @@ pub enum Commands {
     Check {
         #[command(flatten)]
         args: RunArgs,
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    Audit {
+        #[command(flatten)]
+        args: RunArgs,
     },
     Init {
         #[command(flatten)]
@@ -40,26 +50,222 @@ pub enum Commands {
         #[command(subcommand)]
         command: SupabaseSubcommand,
     },
+    Vercel {
+        #[command(subcommand)]
+        command: VercelSubcommand,
+    },
+    Stripe {
+        #[command(subcommand)]
+        command: StripeSubcommand,
+    },
+    Rules {
+        #[command(subcommand)]
+        command: RulesSubcommand,
+    },
+    Explain {
+        #[command(flatten)]
+        args: ExplainArgs,
+    },
+    Doctor {
+        #[command(flatten)]
+        args: RunArgs,
+    },
+    Github {
+        #[command(subcommand)]
+        command: GithubSubcommand,
+    },
+    Export {
+        #[command(flatten)]
+        args: ExportArgs,
+    },
+    Remediate {
+        #[command(flatten)]
+        args: RemediateArgs,
+    },
+    VerifyAttestation {
+        #[command(flatten)]
+        args: VerifyAttestationArgs,
+    },
+    Score {
+        #[command(flatten)]
+        args: ScoreArgs,
+    },
+    Policy {
+        #[command(subcommand)]
+        command: PolicySubcommand,
+    },
+    Config {
+        #[command(subcommand)]
+        command: ConfigSubcommand,
+    },
+    Ignore {
+        #[command(subcommand)]
+        command: IgnoreSubcommand,
+    },
+    Daemon {
+        #[command(flatten)]
+        args: DaemonArgs,
+    },
+    Summarize {
+        #[command(flatten)]
+        args: SummarizeArgs,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct SummarizeArgs {
+    #[arg(required = true, value_name = "REPORT")]
+    pub reports: Vec<PathBuf>,
+    #[arg(long, value_name = "SUMMARY")]
+    pub previous: Vec<PathBuf>,
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub top: usize,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct DaemonArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PolicySubcommand {
+    Update {
+        #[command(flatten)]
+        args: PolicyUpdateArgs,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct PolicyUpdateArgs {
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigSubcommand {
+    Migrate {
+        #[command(flatten)]
+        args: ConfigMigrateArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum IgnoreSubcommand {
+    Add {
+        #[command(flatten)]
+        args: IgnoreAddArgs,
+    },
+    List {
+        #[command(flatten)]
+        args: IgnoreListArgs,
+    },
+    Remove {
+        #[command(flatten)]
+        args: IgnoreRemoveArgs,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct IgnoreAddArgs {
+    // without one, the current findings are listed with their fingerprints
+    pub fingerprint: Option<String>,
+    #[arg(long)]
+    pub reason: Option<String>,
+    // `30d`, `6w`, or YYYY-MM-DD
+    #[arg(long)]
+    pub expires: Option<String>,
+    #[arg(long)]
+    pub owner: Option<String>,
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct IgnoreListArgs {
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct IgnoreRemoveArgs {
+    // a position from `devguard ignore list` or a fingerprint prefix
+    pub id: String,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigMigrateArgs {
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    // without it the diff is only printed
+    #[arg(long)]
+    pub write: bool,
 }
 
 #[derive(Debug, Args, Clone)]
 pub struct RunArgs {
     #[arg(long, default_value = ".")]
     pub path: PathBuf,
+    #[arg(long, value_name = "URL", conflicts_with = "path")]
+    pub remote: Option<String>,
     #[arg(long)]
     pub config: Option<PathBuf>,
     #[arg(long, value_enum)]
     pub format: Option<ReportFormat>,
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["format", "json"])]
+    pub template: Option<PathBuf>,
     #[arg(long)]
     pub output: Option<PathBuf>,
     #[arg(long)]
     pub summary_only: bool,
+    #[arg(long, conflicts_with = "summary_only")]
+    pub summary: bool,
+    #[arg(long, value_name = "N")]
+    pub max_issues: Option<usize>,
+    #[arg(long, value_enum, default_value = "severity")]
+    pub group_by: GroupBy,
+    #[arg(long)]
+    pub show_passes: bool,
+    #[arg(long, conflicts_with = "show_passes")]
+    pub no_passes: bool,
     #[arg(long)]
     pub min_score: Option<u8>,
     #[arg(long, value_enum)]
     pub fail_on: Option<FailOn>,
     #[arg(long)]
     pub github_step_summary: bool,
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+    #[arg(long)]
+    pub show_context: bool,
+    #[arg(long)]
+    pub deep: bool,
+    #[arg(long)]
+    pub no_process_env: bool,
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    pub providers: Vec<String>,
+    #[arg(long, value_delimiter = ',', value_name = "GROUPS")]
+    pub checks: Vec<String>,
+    #[arg(long, value_delimiter = ',', value_name = "GROUPS")]
+    pub skip: Vec<String>,
+    #[arg(long, requires = "output")]
+    pub attest: bool,
+    #[arg(long)]
+    pub deterministic: bool,
+    #[arg(long, value_name = "LANG")]
+    pub lang: Option<String>,
+    #[arg(long, value_name = "OWNER")]
+    pub owner: Option<String>,
+    #[arg(long)]
+    pub no_ci: bool,
     #[arg(long, hide = true, conflicts_with = "format")]
     pub json: bool,
 }
@@ -76,6 +282,18 @@ pub enum ScanSubcommand {
         #[command(flatten)]
         args: RunArgs,
     },
+    Push {
+        #[command(flatten)]
+        args: ScanPushArgs,
+    },
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ScanPushArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+    #[arg(long, value_name = "REVS")]
+    pub range: Option<String>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -94,18 +312,149 @@ pub enum GitSubcommand {
     },
 }
 
+#[derive(Debug, Subcommand)]
+pub enum GithubSubcommand {
+    Audit {
+        #[command(flatten)]
+        args: GithubAuditArgs,
+    },
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct GithubAuditArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+    #[arg(long, value_name = "OWNER/NAME")]
+    pub repo: Option<String>,
+    #[arg(long)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+    #[arg(long)]
+    pub github_issues: bool,
+    #[arg(long, value_name = "WEBHOOK_URL")]
+    pub jira: Option<String>,
+    #[arg(long, value_name = "OWNER/NAME")]
+    pub repo: Option<String>,
+    #[arg(long)]
+    pub token: Option<String>,
+    #[arg(long, value_enum, default_value = "warning")]
+    pub min_severity: FailOn,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct RemediateArgs {
+    pub fingerprint: Option<String>,
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct VerifyAttestationArgs {
+    pub report: PathBuf,
+    #[arg(long)]
+    pub public_key: Option<PathBuf>,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long, value_name = "SHA")]
+    pub commit: Option<String>,
+}
+
+// scan-only flags such as --path or --deep have no effect on a saved report
+#[derive(Debug, Args, Clone)]
+pub struct ScoreArgs {
+    #[arg(long, value_name = "REPORT")]
+    pub input: PathBuf,
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum SupabaseSubcommand {
     Verify {
         #[command(flatten)]
-        args: SupabaseVerifyArgs,
+        args: ProviderVerifyArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VercelSubcommand {
+    Verify {
+        #[command(flatten)]
+        args: ProviderVerifyArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StripeSubcommand {
+    Verify {
+        #[command(flatten)]
+        args: ProviderVerifyArgs,
     },
 }
 
 #[derive(Debug, Args, Clone)]
-pub struct SupabaseVerifyArgs {
+pub struct ProviderVerifyArgs {
     #[command(flatten)]
     pub run: RunArgs,
     #[arg(long)]
     pub force: bool,
 }
+
+#[derive(Debug, Subcommand)]
+pub enum RulesSubcommand {
+    List {
+        #[command(flatten)]
+        args: RulesListArgs,
+    },
+    New {
+        #[command(flatten)]
+        args: RulesNewArgs,
+    },
+    Test {
+        #[command(flatten)]
+        args: RulesTestArgs,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct RulesTestArgs {
+    #[arg(required = true)]
+    pub fixtures: Vec<PathBuf>,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RulesNewArgs {
+    pub name: String,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct RulesListArgs {
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    pub rule_id: String,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
@@ -1,22 +1,311 @@
+#[cfg(not(feature = "wasm"))]
+use crate::core::bundle;
+use crate::core::{Category, catalog};
+use crate::policy;
+use crate::utils::date::Date;
+use crate::utils::sandbox;
 use anyhow::{Context, Result, bail};
 use clap::ValueEnum;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+pub const BUILTIN_RELEASE_PROFILE: &str = "release";
+pub const BUILTIN_AUDIT_PROFILE: &str = "audit";
+// scan and provider time budgets stretch by this much under the audit profile
+const AUDIT_BUDGET_FACTOR: u64 = 10;
 
 #[derive(Debug, Clone)]
 pub struct LoadedConfig {
     pub config: Config,
+    pub source: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    pub policy_bundle: PolicyBundleConfig,
     pub general: GeneralConfig,
+    pub policy: PolicyConfig,
     pub scan: ScanConfig,
     pub env: EnvConfig,
+    pub git: GitConfig,
+    pub release: ReleaseConfig,
     pub providers: ProvidersConfig,
+    pub net: NetConfig,
+    pub telemetry: TelemetryConfig,
+    pub attestation: AttestationConfig,
+    pub staleness: StalenessConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules_dir: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages_dir: Option<PathBuf>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, RuleSetting>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub custom_patterns: Vec<CustomPattern>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub required_files: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<Suppression>,
+    // path glob -> owning team or email address
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub owners: BTreeMap<String, String>,
+}
+
+impl Config {
+    // `--providers a,b`: enable these and run them even when detection finds nothing
+    pub fn force_providers(&mut self, names: &[String]) -> Result<()> {
+        for name in names {
+            let providers = &mut self.providers;
+            let (enabled, markers) = match name.trim().to_ascii_lowercase().as_str() {
+                "supabase" => (
+                    &mut providers.supabase.enabled,
+                    &mut providers.supabase.markers,
+                ),
+                "vercel" => (&mut providers.vercel.enabled, &mut providers.vercel.markers),
+                "stripe" => (&mut providers.stripe.enabled, &mut providers.stripe.markers),
+                other => bail!(
+                    "unknown provider `{}` in --providers (expected supabase, vercel, or stripe)",
+                    other
+                ),
+            };
+            *enabled = true;
+            markers.force = true;
+        }
+        Ok(())
+    }
+
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        // unlike release, a user-defined audit profile refines the built-in one
+        if name == BUILTIN_AUDIT_PROFILE {
+            self.apply_audit();
+        }
+        let Some(profile) = self.profiles.get(name).cloned() else {
+            if name == BUILTIN_RELEASE_PROFILE {
+                self.release.enabled = true;
+                return Ok(());
+            }
+            if name == BUILTIN_AUDIT_PROFILE {
+                return Ok(());
+            }
+
+            let mut available = self.profiles.keys().cloned().collect::<Vec<_>>();
+            for builtin in [BUILTIN_RELEASE_PROFILE, BUILTIN_AUDIT_PROFILE] {
+                if !available.iter().any(|key| key == builtin) {
+                    available.push(builtin.to_string());
+                }
+            }
+            available.sort();
+            bail!(
+                "unknown profile: {} (available: {})",
+                name,
+                available.join(", ")
+            );
+        };
+
+        if let Some(fail_on) = profile.fail_on {
+            self.general.fail_on = fail_on;
+        }
+        if let Some(min_score) = profile.min_score {
+            self.general.min_score = min_score;
+        }
+        if let Some(categories) = profile.categories {
+            self.general.categories = categories;
+        }
+        if let Some(release) = profile.release {
+            self.release.enabled = release;
+        }
+        self.rules.extend(profile.rules);
+        Ok(())
+    }
+
+    // every opt-in check on, every category reported, passes and context shown, and a
+    // longer time budget
+    fn apply_audit(&mut self) {
+        self.general.categories.clear();
+        self.general.show_passes = Some(true);
+        self.general.max_findings_per_rule = 0;
+        self.scan.deep = true;
+        self.scan.structured = true;
+        self.scan.blame = true;
+        self.scan.show_context = true;
+        self.scan.documents.enabled = true;
+        self.scan.local_history.enabled = true;
+        self.scan.disclosure.enabled = true;
+        self.scan.timeout_secs = self
+            .scan
+            .timeout_secs
+            .map(|secs| secs * AUDIT_BUDGET_FACTOR);
+        self.scan.max_files = self
+            .scan
+            .max_files
+            .map(|files| files * AUDIT_BUDGET_FACTOR as usize);
+        self.scan.max_total_mb = self.scan.max_total_mb.map(|mb| mb * AUDIT_BUDGET_FACTOR);
+        self.providers.timeout_secs = self
+            .providers
+            .timeout_secs
+            .map(|secs| secs * AUDIT_BUDGET_FACTOR);
+        self.env.auto_discover = true;
+        self.env.required_non_empty = true;
+        self.env.detect_by_content = true;
+        self.env.direnv.enabled = true;
+        self.env.direnv.warn_forbidden_sources = true;
+        self.env.mise.enabled = true;
+        self.staleness.enabled = true;
+        self.release.enabled = true;
+    }
+
+    // packs load in file-name order; a later pack's rule override beats an earlier one's,
+    // and the config file's own `[rules]` beat every pack
+    #[cfg(not(feature = "wasm"))]
+    fn load_rule_packs(&mut self, base_dir: &Path) -> Result<()> {
+        let Some(rules_dir) = &self.rules_dir else {
+            return Ok(());
+        };
+        let dir = base_dir.join(rules_dir);
+        let entries = fs::read_dir(&dir)
+            .with_context(|| {
+                format!(
+                    "failed reading rules_dir {} (create a pack with `devguard rules new <name> --dir {}`)",
+                    dir.display(),
+                    rules_dir.display()
+                )
+            })?;
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut pack_rules = BTreeMap::new();
+        for path in paths {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("failed reading rule pack {}", path.display()))?;
+            let pack = toml::from_str::<RulePack>(&content)
+                .with_context(|| format!("failed parsing rule pack {}", path.display()))?;
+            pack_rules.extend(pack.rules);
+            self.custom_patterns
+                .extend(pack.custom_patterns.into_iter().map(|mut pattern| {
+                    pattern.pack = Some(name.clone());
+                    if pattern.category.is_none() {
+                        pattern.category = pack.category.clone();
+                    }
+                    pattern
+                }));
+            for file in pack.required_files {
+                if !self.required_files.contains(&file) {
+                    self.required_files.push(file);
+                }
+            }
+        }
+        for (code, setting) in pack_rules {
+            self.rules.entry(code).or_insert(setting);
+        }
+        Ok(())
+    }
+
+    pub fn category_enabled(&self, category: Category) -> bool {
+        self.general.categories.is_empty() || self.general.categories.contains(&category)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let rule_maps = std::iter::once(&self.rules)
+            .chain(self.profiles.values().map(|profile| &profile.rules));
+        for rules in rule_maps {
+            if let Some(code) = rules.keys().find(|code| catalog::find(code).is_none()) {
+                bail!("unknown rule id in rule overrides: {}", code);
+            }
+        }
+
+        let mut pattern_ids = std::collections::HashSet::new();
+        for pattern in &self.custom_patterns {
+            let origin = pattern
+                .pack
+                .as_ref()
+                .map(|pack| format!(" in rule pack {}", pack))
+                .unwrap_or_default();
+            if pattern.id.trim().is_empty() {
+                bail!("custom pattern{} has an empty id", origin);
+            }
+            if !pattern_ids.insert(pattern.id.as_str()) {
+                bail!("duplicate custom pattern id {}{}", pattern.id, origin);
+            }
+            if pattern.custom_category() == Some("") {
+                bail!(
+                    "custom pattern {}{} has an empty category",
+                    pattern.id,
+                    origin
+                );
+            }
+            Regex::new(&pattern.regex).with_context(|| {
+                format!("invalid regex for custom pattern {}{}", pattern.id, origin)
+            })?;
+        }
+        if let Some(file) = self
+            .required_files
+            .iter()
+            .find(|file| file.trim().is_empty())
+        {
+            bail!("required_files entry {:?} is empty", file);
+        }
+
+        for (name, expression) in &self.policy.rules {
+            policy::validate(expression)
+                .with_context(|| format!("invalid policy rule {}: {}", name, expression))?;
+        }
+
+        let allowlist = &self.scan.allowlist;
+        if let Some(hash) = allowlist.hashes.iter().find(|hash| {
+            let hash = hash.trim();
+            hash.len() != 64 || !hash.chars().all(|ch| ch.is_ascii_hexdigit())
+        }) {
+            bail!(
+                "scan.allowlist hash {:?} is not a sha-256 hex digest (64 hex characters)",
+                hash
+            );
+        }
+        for pattern in &allowlist.patterns {
+            Regex::new(pattern)
+                .with_context(|| format!("invalid scan.allowlist pattern {:?}", pattern))?;
+        }
+
+        let telemetry = &self.telemetry;
+        if telemetry.enabled
+            && telemetry.prometheus_textfile.is_none()
+            && telemetry.statsd.is_none()
+            && telemetry.otlp_endpoint.is_none()
+        {
+            bail!(
+                "telemetry is enabled but no prometheus_textfile, statsd, or otlp_endpoint is set"
+            );
+        }
+
+        for suppression in &self.suppressions {
+            if let Some(expires) = &suppression.expires
+                && Date::parse(expires).is_none()
+            {
+                bail!(
+                    "suppression for {} has invalid expires date {:?} (expected YYYY-MM-DD)",
+                    suppression.rule,
+                    expires
+                );
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +314,14 @@ pub struct GeneralConfig {
     pub fail_on: FailOn,
     pub min_score: u8,
     pub json: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_passes: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<Category>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    // findings of one rule past this many are folded into a single summary; 0 lists them all
+    pub max_findings_per_rule: usize,
 }
 
 impl Default for GeneralConfig {
@@ -33,6 +330,10 @@ impl Default for GeneralConfig {
             fail_on: FailOn::Warning,
             min_score: 80,
             json: false,
+            show_passes: None,
+            categories: Vec::new(),
+            lang: None,
+            max_findings_per_rule: 50,
         }
     }
 }
@@ -56,11 +357,99 @@ impl fmt::Display for FailOn {
     }
 }
 
+// what happens to findings in minified, vendored, or generated files
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratedFiles {
+    #[default]
+    Downgrade,
+    Skip,
+    Scan,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSetting {
+    Off,
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<FailOn>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<Category>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release: Option<bool>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, RuleSetting>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    #[serde(alias = "max_critical", skip_serializing_if = "Option::is_none")]
+    pub max_errors: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_warnings: Option<usize>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_per_category: BTreeMap<Category, usize>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, String>,
+    // points taken off the score per suppressed finding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppressed_penalty: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suppression {
+    pub rule: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    // narrows the entry to one finding, as written by `devguard ignore add`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ScanConfig {
     pub exclude: Vec<String>,
     pub max_file_size_kb: u64,
+    pub max_buffered_kb: u64,
+    pub max_buffered_total_mb: u64,
+    pub binary_extensions: Vec<String>,
+    pub follow_symlinks: bool,
+    pub show_context: bool,
+    pub ignore_comments: bool,
+    pub blame: bool,
+    pub structured: bool,
+    pub tool_configs: bool,
+    pub generated_files: GeneratedFiles,
+    pub deep: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_mb: Option<u64>,
+    pub workspace_max_mb: u64,
+    pub allowlist: AllowlistConfig,
+    pub documents: DocumentsConfig,
+    pub local_history: LocalHistoryConfig,
+    pub disclosure: DisclosureConfig,
 }
 
 impl Default for ScanConfig {
@@ -75,6 +464,144 @@ impl Default for ScanConfig {
                 ".next".to_string(),
             ],
             max_file_size_kb: 512,
+            max_buffered_kb: 256,
+            max_buffered_total_mb: 64,
+            binary_extensions: [
+                "png", "jpg", "jpeg", "gif", "bmp", "ico", "webp", "avif", "tiff", "psd", "woff",
+                "woff2", "ttf", "otf", "eot", "wasm", "zip", "gz", "tgz", "bz2", "xz", "7z", "rar",
+                "jar", "pdf", "mp3", "mp4", "mov", "webm", "ogg", "wav", "exe", "dll", "so",
+                "dylib", "class", "pyc", "sqlite",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            follow_symlinks: false,
+            show_context: false,
+            ignore_comments: false,
+            blame: true,
+            structured: true,
+            tool_configs: true,
+            generated_files: GeneratedFiles::Downgrade,
+            deep: false,
+            timeout_secs: None,
+            max_files: None,
+            max_total_mb: None,
+            workspace_max_mb: 1024,
+            allowlist: AllowlistConfig::default(),
+            documents: DocumentsConfig::default(),
+            local_history: LocalHistoryConfig::default(),
+            disclosure: DisclosureConfig::default(),
+        }
+    }
+}
+
+// opt-in: text is pulled out of .docx, .xlsx, and .pptx files and checked for credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DocumentsConfig {
+    pub enabled: bool,
+    pub max_size_kb: u64,
+}
+
+impl Default for DocumentsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size_kb: 10240,
+        }
+    }
+}
+
+// opt-in: stashes and commits only the HEAD reflog references are scanned for secrets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalHistoryConfig {
+    pub enabled: bool,
+    pub reflog_days: u64,
+}
+
+impl Default for LocalHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reflog_days: 30,
+        }
+    }
+}
+
+// opt-in: private addresses, internal hostnames, and cloud metadata endpoints in any file.
+// `allow` takes exact hosts or addresses, or `.example.internal` for a whole domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisclosureConfig {
+    pub enabled: bool,
+    pub host_suffixes: Vec<String>,
+    pub allow: Vec<String>,
+}
+
+impl Default for DisclosureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host_suffixes: [".internal", ".corp", ".intranet", ".lan"]
+                .map(String::from)
+                .to_vec(),
+            allow: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetConfig {
+    pub timeout_secs: u64,
+    pub retries: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 20,
+            retries: 2,
+            proxy: None,
+        }
+    }
+}
+
+// minisign keys for `check --attest` and `verify-attestation`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttestationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_key: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PathBuf>,
+}
+
+// run metrics for fleet dashboards; nothing is written or sent unless `enabled` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    pub prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prometheus_textfile: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statsd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            prefix: "devguard".to_string(),
+            prometheus_textfile: None,
+            statsd: None,
+            otlp_endpoint: None,
         }
     }
 }
@@ -83,15 +610,29 @@ impl Default for ScanConfig {
 #[serde(default)]
 pub struct EnvConfig {
     pub required: Vec<String>,
+    // a required key must also hold a value that is not blank or a placeholder
+    pub required_non_empty: bool,
+    // off, only dotenv files can satisfy a required key
+    pub check_process_env: bool,
     pub forbid_commit: Vec<String>,
     pub dotenv_files: Vec<String>,
     pub example_files: Vec<String>,
+    pub auto_discover: bool,
+    pub detect_by_content: bool,
+    pub format: DotenvFormatConfig,
+    pub direnv: DirenvConfig,
+    pub mise: MiseConfig,
+    // keys a secret manager injects at run time instead of a dotenv file
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub managed: BTreeMap<String, SecretManager>,
 }
 
 impl Default for EnvConfig {
     fn default() -> Self {
         Self {
             required: vec!["DATABASE_URL".to_string()],
+            required_non_empty: false,
+            check_process_env: true,
             forbid_commit: vec![
                 ".env".to_string(),
                 ".env.local".to_string(),
@@ -105,6 +646,220 @@ impl Default for EnvConfig {
                 ".env.production".to_string(),
             ],
             example_files: vec![".env.example".to_string(), ".env.template".to_string()],
+            auto_discover: false,
+            detect_by_content: false,
+            format: DotenvFormatConfig::default(),
+            direnv: DirenvConfig::default(),
+            mise: MiseConfig::default(),
+            managed: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretManager {
+    Doppler,
+    Vault,
+    #[serde(rename = "1password")]
+    OnePassword,
+    TerraformCloud,
+}
+
+impl fmt::Display for SecretManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Doppler => write!(f, "Doppler"),
+            Self::Vault => write!(f, "Vault"),
+            Self::OnePassword => write!(f, "1Password"),
+            Self::TerraformCloud => write!(f, "Terraform Cloud"),
+        }
+    }
+}
+
+// encoding problems in dotenv and example files that loaders trip over
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DotenvFormatConfig {
+    pub enabled: bool,
+    // for loaders that strip the \r themselves
+    pub allow_crlf: bool,
+}
+
+impl Default for DotenvFormatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allow_crlf: false,
+        }
+    }
+}
+
+// `.envrc` and shell rc files set env vars too, but fall outside the dotenv parser
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirenvConfig {
+    pub enabled: bool,
+    pub files: Vec<String>,
+    pub warn_forbidden_sources: bool,
+}
+
+impl Default for DirenvConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            files: [
+                ".envrc",
+                ".bashrc",
+                ".bash_profile",
+                ".zshrc",
+                ".zshenv",
+                ".profile",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            warn_forbidden_sources: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiseConfig {
+    pub enabled: bool,
+}
+
+impl Default for MiseConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+// how a remote `extends` bundle is cached and, with `public_key`, which minisign key its
+// `<url>.minisig` signature must verify against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyBundleConfig {
+    pub ttl_hours: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PathBuf>,
+}
+
+impl Default for PolicyBundleConfig {
+    fn default() -> Self {
+        Self {
+            ttl_hours: 24,
+            public_key: None,
+        }
+    }
+}
+
+// drift over time: example files and migrations that stopped keeping up with the code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StalenessConfig {
+    pub enabled: bool,
+    pub example_max_age_days: u64,
+    pub migrations_dirs: Vec<String>,
+    pub schema_paths: Vec<String>,
+    pub migrations_grace_days: u64,
+}
+
+impl Default for StalenessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            example_max_age_days: 180,
+            migrations_dirs: [
+                "supabase/migrations",
+                "prisma/migrations",
+                "migrations",
+                "db/migrate",
+                "drizzle",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            schema_paths: [
+                "prisma/schema.prisma",
+                "src/db/schema.ts",
+                "db/schema.ts",
+                "supabase/schemas",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            migrations_grace_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitConfig {
+    pub codeowners_sensitive_dirs: Vec<String>,
+    // the rest use .gitattributes pattern syntax and only apply when that file exists
+    pub encrypted_paths: Vec<String>,
+    pub export_ignore_dirs: Vec<String>,
+    pub merge_protected_paths: Vec<String>,
+    pub portable_paths: bool,
+    pub gitignore_coverage: bool,
+    pub merge_leftovers: bool,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            codeowners_sensitive_dirs: vec![
+                "auth".to_string(),
+                "payments".to_string(),
+                "billing".to_string(),
+                "secrets".to_string(),
+                "security".to_string(),
+            ],
+            encrypted_paths: [".env", ".env.*", "*.pem", "*.key", "secrets/**"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            export_ignore_dirs: [".github", ".husky", ".devcontainer", ".vscode"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            merge_protected_paths: [
+                "CODEOWNERS",
+                ".gitattributes",
+                ".gitignore",
+                "SECURITY.md",
+                "devguard.toml",
+                ".github/workflows/*",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            portable_paths: true,
+            gitignore_coverage: true,
+            merge_leftovers: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReleaseConfig {
+    pub enabled: bool,
+    pub production_env_files: Vec<String>,
+}
+
+impl Default for ReleaseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            production_env_files: vec![
+                ".env.production".to_string(),
+                ".env.prod".to_string(),
+                ".env.production.local".to_string(),
+            ],
         }
     }
 }
@@ -112,6 +867,8 @@ impl Default for EnvConfig {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ProvidersConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
     pub supabase: SupabaseConfig,
     pub vercel: VercelConfig,
     pub stripe: StripeConfig,
@@ -124,6 +881,10 @@ pub struct SupabaseConfig {
     pub require_migrations: bool,
     pub migrations_dir: String,
     pub forbid_service_role_in_client: bool,
+    pub check_server_anon_clients: bool,
+    pub review_storage_policies: bool,
+    #[serde(flatten)]
+    pub markers: DetectMarkers,
 }
 
 impl Default for SupabaseConfig {
@@ -133,6 +894,9 @@ impl Default for SupabaseConfig {
             require_migrations: true,
             migrations_dir: "supabase/migrations".to_string(),
             forbid_service_role_in_client: true,
+            check_server_anon_clients: true,
+            review_storage_policies: true,
+            markers: DetectMarkers::default(),
         }
     }
 }
@@ -141,11 +905,18 @@ impl Default for SupabaseConfig {
 #[serde(default)]
 pub struct VercelConfig {
     pub enabled: bool,
+    pub check_edge_runtime: bool,
+    #[serde(flatten)]
+    pub markers: DetectMarkers,
 }
 
 impl Default for VercelConfig {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            check_edge_runtime: true,
+            markers: DetectMarkers::default(),
+        }
     }
 }
 
@@ -154,6 +925,11 @@ impl Default for VercelConfig {
 pub struct StripeConfig {
     pub enabled: bool,
     pub warn_live_keys: bool,
+    pub check_code: bool,
+    // `YYYY-MM-DD`; pinned api versions older than this are reported
+    pub min_api_version: String,
+    #[serde(flatten)]
+    pub markers: DetectMarkers,
 }
 
 impl Default for StripeConfig {
@@ -161,10 +937,112 @@ impl Default for StripeConfig {
         Self {
             enabled: true,
             warn_live_keys: true,
+            check_code: true,
+            min_api_version: "2020-08-27".to_string(),
+            markers: DetectMarkers::default(),
         }
     }
 }
 
+// secret values that are known to be fake; `hashes` are sha-256 hex digests of the exact
+// matched value and `patterns` are regexes tested against it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AllowlistConfig {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hashes: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+}
+
+// a regex that reports a finding wherever it matches, limited to `paths` globs when set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomPattern {
+    pub id: String,
+    pub regex: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default = "default_pattern_severity")]
+    pub severity: RuleSetting,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<PatternCategory>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    #[serde(skip)]
+    pub pack: Option<String>,
+}
+
+fn default_pattern_severity() -> RuleSetting {
+    RuleSetting::Warning
+}
+
+// a built-in category slug, or any other label, which groups the pattern's findings under a
+// heading of their own; those are scored and filtered as `secrets`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PatternCategory {
+    Builtin(Category),
+    Custom(String),
+}
+
+impl PatternCategory {
+    // `Git` or `Supply chain` name the built-in categories just like their slugs
+    fn builtin(&self) -> Option<Category> {
+        match self {
+            Self::Builtin(category) => Some(*category),
+            Self::Custom(label) => Category::ALL.into_iter().find(|category| {
+                category.slug().eq_ignore_ascii_case(label.trim())
+                    || category.label().eq_ignore_ascii_case(label.trim())
+            }),
+        }
+    }
+}
+
+impl CustomPattern {
+    pub fn scored_category(&self) -> Category {
+        self.category
+            .as_ref()
+            .and_then(PatternCategory::builtin)
+            .unwrap_or(Category::Secrets)
+    }
+
+    pub fn custom_category(&self) -> Option<&str> {
+        match &self.category {
+            Some(category @ PatternCategory::Custom(label)) if category.builtin().is_none() => {
+                Some(label.trim())
+            }
+            _ => None,
+        }
+    }
+}
+
+// one `*.toml` file in `rules_dir`
+#[cfg(not(feature = "wasm"))]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RulePack {
+    // applies to the pack's patterns that do not set their own
+    category: Option<PatternCategory>,
+    rules: BTreeMap<String, RuleSetting>,
+    custom_patterns: Vec<CustomPattern>,
+    required_files: Vec<String>,
+}
+
+// extra detection heuristics a user can attach to any provider; `force` skips detection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DetectMarkers {
+    pub force: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub detect_paths: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub detect_packages: Vec<String>,
+}
+
+#[cfg(not(feature = "wasm"))]
 pub fn load_config(cli_config_path: Option<&Path>, cwd: &Path) -> Result<LoadedConfig> {
     if let Some(path) = cli_config_path {
         if !path.exists() {
@@ -176,6 +1054,7 @@ pub fn load_config(cli_config_path: Option<&Path>, cwd: &Path) -> Result<LoadedC
 
         return Ok(LoadedConfig {
             config: read_config(path)?,
+            source: Some(path.to_path_buf()),
         });
     }
 
@@ -183,15 +1062,18 @@ pub fn load_config(cli_config_path: Option<&Path>, cwd: &Path) -> Result<LoadedC
     if local_path.exists() {
         return Ok(LoadedConfig {
             config: read_config(&local_path)?,
+            source: Some(local_path),
         });
     }
 
     Ok(LoadedConfig {
         config: Config::default(),
+        source: None,
     })
 }
 
 pub fn write_default_config(path: &Path) -> Result<()> {
+    sandbox::ensure_allowed(&format!("writing {}", path.display()))?;
     if path.exists() {
         bail!(
             "refusing to overwrite existing config file: {}",
@@ -208,10 +1090,266 @@ pub fn default_config_toml() -> Result<String> {
     toml::to_string_pretty(&Config::default()).context("failed to serialize default config")
 }
 
-fn read_config(path: &Path) -> Result<Config> {
+// `devguard policy update`: refetch the config's remote `extends` bundle, bypassing the cache
+#[cfg(not(feature = "wasm"))]
+pub fn update_extends(path: &Path) -> Result<String> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("failed reading config file {}", path.display()))?;
     let config = toml::from_str::<Config>(&content)
         .with_context(|| format!("failed parsing config file {}", path.display()))?;
+    let Some(source) = config.extends else {
+        bail!("{} has no `extends` to update", path.display());
+    };
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    bundle::update(&source, base_dir, &config.policy_bundle, &config.net)?;
+    Ok(source)
+}
+
+// a config that arrives as text with no file behind it, as in the wasm build; `extends` and
+// `rules_dir` need a filesystem to resolve, so they are rejected instead of ignored
+pub fn parse_config(content: &str) -> Result<Config> {
+    let config = toml::from_str::<Config>(content).context("failed parsing config")?;
+    if config.extends.is_some() {
+        bail!("`extends` is not supported without a filesystem");
+    }
+    if config.rules_dir.is_some() {
+        bail!("`rules_dir` is not supported without a filesystem; use [[custom_patterns]]");
+    }
+    config.validate().context("invalid config")?;
+    Ok(config)
+}
+
+#[cfg(not(feature = "wasm"))]
+fn read_config(path: &Path) -> Result<Config> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed reading config file {}", path.display()))?;
+    let mut config = toml::from_str::<Config>(&content)
+        .with_context(|| format!("failed parsing config file {}", path.display()))?;
+    // rules_dir and messages_dir are relative to the config file, not to wherever devguard
+    // was started
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    if let Some(source) = config.extends.clone() {
+        let base = bundle::load(&source, base_dir, &config.policy_bundle, &config.net)?;
+        config = extend(&base, &content).with_context(|| {
+            format!(
+                "failed applying extends = {:?} in {}",
+                source,
+                path.display()
+            )
+        })?;
+    }
+    config.load_rule_packs(base_dir)?;
+    if let Some(messages_dir) = &mut config.messages_dir {
+        *messages_dir = base_dir.join(&messages_dir);
+    }
+    config
+        .validate()
+        .with_context(|| format!("invalid config file {}", path.display()))?;
     Ok(config)
 }
+
+// tables merge key by key with the extending file winning; arrays and plain values replace
+#[cfg(not(feature = "wasm"))]
+fn extend(base: &str, content: &str) -> Result<Config> {
+    let mut merged =
+        toml::from_str::<toml::Table>(base).context("failed parsing extended config")?;
+    if merged.contains_key("extends") {
+        bail!("the extended config has its own `extends`, which is not supported");
+    }
+    merge_tables(&mut merged, toml::from_str::<toml::Table>(content)?);
+    Ok(toml::Value::Table(merged).try_into::<Config>()?)
+}
+
+#[cfg(not(feature = "wasm"))]
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => {
+                merge_tables(existing, table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn extended_configs_merge_tables_with_the_local_file_winning() {
+        let dir = std::env::temp_dir().join(format!("devguard-extends-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("dir created");
+        fs::write(
+            dir.join("org.toml"),
+            "[general]\nfail_on = \"error\"\nmin_score = 90\n\n[env]\nrequired = [\"DATABASE_URL\", \"SENTRY_DSN\"]\n\n[rules]\nDG_GIT_002 = \"off\"\n",
+        )
+        .expect("file written");
+        fs::write(
+            dir.join("devguard.toml"),
+            "extends = \"org.toml\"\n\n[general]\nmin_score = 70\n\n[env]\nrequired = [\"API_URL\"]\n",
+        )
+        .expect("file written");
+
+        let cfg = read_config(&dir.join("devguard.toml")).expect("config loads");
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(cfg.general.fail_on, FailOn::Error);
+        assert_eq!(cfg.general.min_score, 70);
+        assert_eq!(cfg.env.required, ["API_URL"]);
+        assert_eq!(cfg.rules.get("DG_GIT_002"), Some(&RuleSetting::Off));
+
+        let nested = extend("extends = \"other.toml\"\n", "").expect_err("nested extends");
+        assert!(nested.to_string().contains("not supported"));
+    }
+
+    #[test]
+    fn profiles_override_general_settings_and_rules() {
+        let mut cfg = toml::from_str::<Config>(
+            r#"
+[profiles.ci]
+fail_on = "error"
+min_score = 90
+categories = ["secrets", "env"]
+rules = { DG_GIT_002 = "off", DG_SEC_002 = "error" }
+"#,
+        )
+        .expect("config parses");
+        cfg.validate().expect("config is valid");
+
+        cfg.apply_profile("ci").expect("profile exists");
+        assert_eq!(cfg.general.fail_on, FailOn::Error);
+        assert_eq!(cfg.general.min_score, 90);
+        assert!(cfg.category_enabled(Category::Secrets));
+        assert!(!cfg.category_enabled(Category::Git));
+        assert_eq!(cfg.rules.get("DG_SEC_002"), Some(&RuleSetting::Error));
+    }
+
+    #[test]
+    fn provider_detection_markers_sit_beside_provider_settings() {
+        let cfg = toml::from_str::<Config>(
+            r#"
+[providers.supabase]
+require_migrations = false
+detect_paths = ["db/supabase.ts"]
+detect_packages = ["@acme/supabase-client"]
+"#,
+        )
+        .expect("config parses");
+        let supabase = &cfg.providers.supabase;
+        assert!(!supabase.require_migrations);
+        assert_eq!(supabase.markers.detect_paths, ["db/supabase.ts"]);
+        assert_eq!(supabase.markers.detect_packages, ["@acme/supabase-client"]);
+        assert!(cfg.providers.stripe.markers.detect_packages.is_empty());
+    }
+
+    #[test]
+    fn forced_providers_are_enabled_and_unknown_names_rejected() {
+        let mut cfg = Config::default();
+        cfg.providers.stripe.enabled = false;
+        cfg.force_providers(&["Stripe".to_string(), "supabase".to_string()])
+            .expect("known providers");
+        assert!(cfg.providers.stripe.enabled);
+        assert!(cfg.providers.stripe.markers.force);
+        assert!(cfg.providers.supabase.markers.force);
+        assert!(!cfg.providers.vercel.markers.force);
+        assert!(cfg.force_providers(&["firebase".to_string()]).is_err());
+    }
+
+    #[test]
+    fn builtin_release_profile_and_unknown_profiles() {
+        let mut cfg = Config::default();
+        cfg.apply_profile("release").expect("builtin profile");
+        assert!(cfg.release.enabled);
+
+        let err = cfg.apply_profile("nightly").expect_err("unknown profile");
+        assert!(err.to_string().contains("available: audit, release"));
+    }
+
+    #[test]
+    fn audit_profile_turns_on_opt_in_checks_under_user_overrides() {
+        let mut cfg = toml::from_str::<Config>(
+            "[general]
+categories = [\"secrets\"]
+
+[scan]
+timeout_secs = 30
+
+[profiles.audit]
+min_score = 90
+",
+        )
+        .expect("parses");
+        cfg.apply_profile(BUILTIN_AUDIT_PROFILE)
+            .expect("builtin profile");
+        assert!(cfg.scan.deep && cfg.scan.documents.enabled && cfg.release.enabled);
+        assert!(cfg.env.direnv.warn_forbidden_sources);
+        assert!(cfg.general.categories.is_empty());
+        assert_eq!(cfg.scan.timeout_secs, Some(300));
+        assert_eq!(cfg.general.min_score, 90);
+    }
+
+    #[test]
+    fn unknown_rule_overrides_are_rejected() {
+        let cfg = toml::from_str::<Config>(
+            "[rules]
+DG_NOPE_001 = \"off\"\n",
+        )
+        .expect("parses");
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn rule_packs_merge_under_the_config_file() {
+        let root = std::env::temp_dir().join(format!("devguard-packs-{}", std::process::id()));
+        fs::create_dir_all(root.join("packs")).expect("dir created");
+        fs::write(
+            root.join("packs/a-base.toml"),
+            "required_files = [\"SECURITY.md\"]\n[rules]\nDG_GIT_002 = \"off\"\nDG_SEC_002 = \"info\"\n[[custom_patterns]]\nid = \"acme\"\nregex = \"acme_[0-9]+\"\n",
+        )
+        .expect("pack written");
+        fs::write(
+            root.join("packs/b-strict.toml"),
+            "[rules]\nDG_SEC_002 = \"error\"\n",
+        )
+        .expect("pack written");
+        fs::write(
+            root.join("devguard.toml"),
+            "rules_dir = \"packs\"\n[rules]\nDG_GIT_002 = \"warning\"\n",
+        )
+        .expect("config written");
+        let cfg = read_config(&root.join("devguard.toml"));
+        fs::write(
+            root.join("packs/c-dup.toml"),
+            "[[custom_patterns]]\nid = \"acme\"\nregex = \"x\"\n",
+        )
+        .expect("pack written");
+        let duplicate = read_config(&root.join("devguard.toml"));
+        fs::remove_dir_all(&root).expect("cleanup");
+
+        let cfg = cfg.expect("packs load");
+        assert_eq!(cfg.rules.get("DG_GIT_002"), Some(&RuleSetting::Warning));
+        assert_eq!(cfg.rules.get("DG_SEC_002"), Some(&RuleSetting::Error));
+        assert_eq!(cfg.required_files, ["SECURITY.md"]);
+        assert_eq!(cfg.custom_patterns[0].pack.as_deref(), Some("a-base"));
+        assert!(duplicate.is_err());
+    }
+
+    #[test]
+    fn allowlist_entries_are_validated() {
+        let mut cfg = Config::default();
+        cfg.scan.allowlist.hashes = vec!["a".repeat(64)];
+        cfg.scan.allowlist.patterns = vec!["^sk_test_demo".to_string()];
+        cfg.validate().expect("valid allowlist");
+
+        cfg.scan.allowlist.hashes = vec!["not-a-digest".to_string()];
+        assert!(cfg.validate().is_err());
+
+        cfg.scan.allowlist.hashes.clear();
+        cfg.scan.allowlist.patterns = vec!["(unclosed".to_string()];
+        assert!(cfg.validate().is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::config::AttestationConfig;
+use crate::report::FinalReport;
+use crate::utils::date::now_timestamp;
+use crate::utils::digest::sha256_hex;
+use crate::utils::{process, sandbox};
+use anyhow::{Context, Result, bail};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const STATEMENT_SCHEMA: &str = "devguard-attestation/1";
+const SIGN_TIMEOUT: Duration = Duration::from_secs(60);
+
+// what the signature vouches for: this exact report file came out of a devguard run on
+// this commit at this time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Statement {
+    pub schema: String,
+    pub tool_version: String,
+    pub report_file: String,
+    pub report_sha256: String,
+    pub repository: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    pub timestamp: String,
+    pub score: u8,
+    pub passed: bool,
+}
+
+fn statement_path(report_path: &Path) -> PathBuf {
+    let mut name = report_path.as_os_str().to_owned();
+    name.push(".attestation.json");
+    PathBuf::from(name)
+}
+
+fn signature_path(statement_path: &Path) -> PathBuf {
+    let mut name = statement_path.as_os_str().to_owned();
+    name.push(".minisig");
+    PathBuf::from(name)
+}
+
+// writes `<report>.attestation.json` and signs it with the minisign CLI, which keeps
+// secret key handling (and its password prompt) out of devguard
+pub fn attest(
+    report_path: &Path,
+    rendered: &str,
+    report: &FinalReport,
+    cfg: &AttestationConfig,
+) -> Result<PathBuf> {
+    sandbox::ensure_allowed("--attest")?;
+    let Some(secret_key) = &cfg.secret_key else {
+        bail!("--attest needs attestation.secret_key set to a minisign secret key file");
+    };
+    let run = report.run.as_ref();
+    let statement = Statement {
+        schema: STATEMENT_SCHEMA.to_string(),
+        tool_version: report.tool.version.to_string(),
+        report_file: report_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        report_sha256: sha256_hex(rendered.as_bytes()),
+        repository: report.repository_path.clone(),
+        git_commit: run.and_then(|run| run.git_commit.clone()),
+        git_branch: run.and_then(|run| run.git_branch.clone()),
+        timestamp: run
+            .and_then(|run| run.started_at.clone())
+            .unwrap_or_else(now_timestamp),
+        score: report.score,
+        passed: report.passed,
+    };
+
+    let path = statement_path(report_path);
+    fs::write(
+        &path,
+        format!("{}\n", serde_json::to_string_pretty(&statement)?),
+    )
+    .with_context(|| format!("failed writing {}", path.display()))?;
+
+    let trusted_comment = format!(
+        "devguard attestation commit:{} timestamp:{}",
+        statement.git_commit.as_deref().unwrap_or("none"),
+        statement.timestamp
+    );
+    let key = secret_key.to_string_lossy();
+    let message = path.to_string_lossy();
+    let signature = signature_path(&path);
+    let signature_arg = signature.to_string_lossy();
+    let output = process::run_with_timeout(
+        "minisign",
+        &[
+            "-S",
+            "-s",
+            &key,
+            "-m",
+            &message,
+            "-x",
+            &signature_arg,
+            "-t",
+            &trusted_comment,
+        ],
+        SIGN_TIMEOUT,
+    )
+    .context("minisign did not run (is it installed? password-protected keys cannot be used non-interactively)")?;
+    if !output.success {
+        bail!(
+            "minisign failed to sign {}: {}",
+            path.display(),
+            output.first_line()
+        );
+    }
+    Ok(path)
+}
+
+// checks the signature over the statement, then that the report still hashes to the
+// value the statement recorded
+pub fn verify(report_path: &Path, public_key: &Path) -> Result<Statement> {
+    let path = statement_path(report_path);
+    let statement_text =
+        fs::read(&path).with_context(|| format!("failed reading {}", path.display()))?;
+    let signature_file = signature_path(&path);
+    let signature_text = fs::read_to_string(&signature_file)
+        .with_context(|| format!("failed reading {}", signature_file.display()))?;
+    let public_key_text = fs::read_to_string(public_key)
+        .with_context(|| format!("failed reading {}", public_key.display()))?;
+    verify_signature(&statement_text, &signature_text, &public_key_text)
+        .with_context(|| format!("signature check failed for {}", path.display()))?;
+
+    let statement = serde_json::from_slice::<Statement>(&statement_text)
+        .with_context(|| format!("{} is not a devguard attestation", path.display()))?;
+    if statement.schema != STATEMENT_SCHEMA {
+        bail!("unsupported attestation schema {}", statement.schema);
+    }
+    let report = fs::read(report_path)
+        .with_context(|| format!("failed reading {}", report_path.display()))?;
+    let actual = sha256_hex(&report);
+    if actual != statement.report_sha256 {
+        bail!(
+            "{} was modified after it was attested (sha256 {} but the statement records {})",
+            report_path.display(),
+            actual,
+            statement.report_sha256
+        );
+    }
+    Ok(statement)
+}
+
+pub fn verify_signature(message: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    // accept both a `.pub` file and a bare base64 key
+    let key = PublicKey::decode(public_key)
+        .or_else(|_| PublicKey::from_base64(public_key.trim()))
+        .map_err(|err| anyhow::anyhow!("invalid minisign public key: {}", err))?;
+    let signature = Signature::decode(signature)
+        .map_err(|err| anyhow::anyhow!("invalid minisign signature: {}", err))?;
+    key.verify(message, &signature, false)
+        .map_err(|err| anyhow::anyhow!("{}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // vector from minisign's own test suite: a prehashed signature over b"test"
+    const PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==";
+
+    #[test]
+    fn signatures_are_checked_against_the_exact_statement() {
+        verify_signature(b"test", SIGNATURE, PUBLIC_KEY).expect("valid signature");
+        let pub_file = format!("untrusted comment: minisign public key\n{}\n", PUBLIC_KEY);
+        verify_signature(b"test", SIGNATURE, &pub_file).expect("public key file accepted");
+        assert!(verify_signature(b"Test", SIGNATURE, PUBLIC_KEY).is_err());
+    }
+
+    #[test]
+    fn attestation_files_sit_next_to_the_report() {
+        let statement = statement_path(Path::new("out/devguard.json"));
+        assert_eq!(
+            statement,
+            PathBuf::from("out/devguard.json.attestation.json")
+        );
+        assert_eq!(
+            signature_path(&statement),
+            PathBuf::from("out/devguard.json.attestation.json.minisig")
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+// how many unreached paths the partial-scan issue names; past this they are only counted
+const LISTED_UNSCANNED: usize = 50;
+
+#[derive(Debug, Default)]
+pub struct ScanBudget {
+    deadline: Option<Instant>,
+    stage_deadline: Cell<Option<Instant>>,
+    skipped: RefCell<Vec<String>>,
+    unscanned: RefCell<Vec<String>>,
+    max_files: Option<usize>,
+    max_bytes: Option<u64>,
+    files_read: AtomicUsize,
+    bytes_read: AtomicU64,
+}
+
+impl ScanBudget {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            ..Self::default()
+        }
+    }
+
+    // caps on distinct files read and their total size; reaching either ends the scan the
+    // same way the deadline does
+    pub fn with_limits(mut self, max_files: Option<usize>, max_bytes: Option<u64>) -> Self {
+        self.max_files = max_files;
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn charge(&self, bytes: u64) {
+        self.files_read.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    // which limit stopped the scan, if one did
+    pub fn limit_reached(&self) -> Option<String> {
+        let files = self.files_read.load(Ordering::Relaxed);
+        let bytes = self.bytes_read.load(Ordering::Relaxed);
+        if let Some(max) = self.max_files.filter(|max| files >= *max) {
+            return Some(format!("scan.max_files ({}) reached", max));
+        }
+        self.max_bytes
+            .filter(|max| bytes >= *max)
+            .map(|max| format!("scan.max_total_mb ({}) reached", max / (1024 * 1024)))
+    }
+
+    pub fn expired(&self) -> bool {
+        let now = Instant::now();
+        self.deadline.is_some_and(|deadline| now >= deadline)
+            || self.limit_reached().is_some()
+            || self
+                .stage_deadline
+                .get()
+                .is_some_and(|deadline| now >= deadline)
+    }
+
+    // cooperative per-stage limit: walkers inside `run` stop once it passes. returns
+    // whether the stage ran out of time.
+    pub fn with_stage_timeout<T>(
+        &self,
+        timeout: Option<Duration>,
+        run: impl FnOnce() -> T,
+    ) -> (T, bool) {
+        let Some(timeout) = timeout else {
+            return (run(), false);
+        };
+        let deadline = Instant::now() + timeout;
+        self.stage_deadline.set(Some(deadline));
+        let result = run();
+        self.stage_deadline.set(None);
+        (result, Instant::now() >= deadline)
+    }
+
+    // returns false (and records the stage as skipped) once the deadline has passed.
+    pub fn allows(&self, stage: &str) -> bool {
+        if self.expired() {
+            self.record(format!("{}: not started", stage));
+            return false;
+        }
+        true
+    }
+
+    pub fn record(&self, what: String) {
+        self.skipped.borrow_mut().push(what);
+    }
+
+    pub fn skipped(&self) -> Vec<String> {
+        self.skipped.borrow().clone()
+    }
+
+    // a stage that ran out of budget, with every path it did not get to; all of them are
+    // counted, and the first few across all stages are kept by name
+    pub fn record_stopped(&self, stage: &str, unscanned: impl IntoIterator<Item = String>) {
+        let mut count = 0;
+        let mut listed = self.unscanned.borrow_mut();
+        for path in unscanned {
+            count += 1;
+            if listed.len() < LISTED_UNSCANNED && !listed.contains(&path) {
+                listed.push(path);
+            }
+        }
+        drop(listed);
+        self.record(format!(
+            "{}: stopped with {} file(s) unscanned",
+            stage, count
+        ));
+    }
+
+    // the stages that stopped or never started, then the paths they did not reach
+    pub fn unscanned_summary(&self) -> String {
+        let mut summary = self.skipped.borrow().join("; ");
+        let listed = self.unscanned.borrow();
+        if listed.len() == LISTED_UNSCANNED {
+            summary.push_str(&format!(
+                "; first {} unscanned files: {}",
+                LISTED_UNSCANNED,
+                listed.join(", ")
+            ));
+        } else if !listed.is_empty() {
+            summary.push_str(&format!("; unscanned files: {}", listed.join(", ")));
+        }
+        summary
+    }
+}
+
+// distinct files whose content was read by any check, and those skipped as binary or oversized
+#[derive(Debug, Default)]
+pub struct ScanStats {
+    scanned: RefCell<HashSet<PathBuf>>,
+    skipped: RefCell<HashSet<PathBuf>>,
+}
+
+impl ScanStats {
+    // whether the path was new to its set
+    pub fn record(&self, path: &Path, scanned: bool) -> bool {
+        let set = if scanned {
+            &self.scanned
+        } else {
+            &self.skipped
+        };
+        set.borrow_mut().insert(path.to_path_buf())
+    }
+
+    pub fn files_scanned(&self) -> usize {
+        self.scanned.borrow().len()
+    }
+
+    pub fn files_skipped(&self) -> usize {
+        let scanned = self.scanned.borrow();
+        self.skipped
+            .borrow()
+            .iter()
+            .filter(|path| !scanned.contains(*path))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_budget_never_expires() {
+        let budget = ScanBudget::new(None);
+        assert!(budget.allows("secret scan"));
+        assert!(budget.skipped().is_empty());
+    }
+
+    #[test]
+    fn stage_timeout_only_applies_inside_the_stage() {
+        let budget = ScanBudget::new(None);
+        let (expired_inside, timed_out) =
+            budget.with_stage_timeout(Some(Duration::ZERO), || budget.expired());
+        assert!(expired_inside);
+        assert!(timed_out);
+        assert!(!budget.expired());
+    }
+
+    #[test]
+    fn file_and_size_limits_end_the_scan() {
+        let files = ScanBudget::new(None).with_limits(Some(2), None);
+        files.charge(10);
+        assert!(!files.expired());
+        files.charge(10);
+        assert!(files.expired());
+        assert_eq!(
+            files.limit_reached().as_deref(),
+            Some("scan.max_files (2) reached")
+        );
+
+        let size = ScanBudget::new(None).with_limits(None, Some(1024 * 1024));
+        size.charge(1024 * 1024);
+        assert!(!size.allows("git checks"));
+        assert_eq!(
+            size.limit_reached().as_deref(),
+            Some("scan.max_total_mb (1) reached")
+        );
+    }
+
+    #[test]
+    fn expired_budget_records_skipped_stages() {
+        let budget = ScanBudget::new(Some(Duration::ZERO));
+        assert!(!budget.allows("git checks"));
+        assert_eq!(budget.skipped(), vec!["git checks: not started"]);
+    }
+
+    #[test]
+    fn stopped_stages_count_every_unscanned_path_and_list_a_bounded_few() {
+        let budget = ScanBudget::new(None);
+        budget.record_stopped("secret scan", (0..120).map(|index| format!("f{index}")));
+        budget.record_stopped("env checks", ["f0".to_string(), "g".to_string()]);
+
+        assert_eq!(
+            budget.skipped(),
+            [
+                "secret scan: stopped with 120 file(s) unscanned",
+                "env checks: stopped with 2 file(s) unscanned"
+            ]
+        );
+        let summary = budget.unscanned_summary();
+        assert!(summary.contains("; first 50 unscanned files: f0, f1, "));
+        assert!(summary.ends_with(", f49"));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::config::{NetConfig, PolicyBundleConfig};
+use crate::core::attest;
+use crate::utils::digest::sha256_hex;
+use crate::utils::{net, sandbox};
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+// `extends` names a shared base config: a path relative to the extending config file, or
+// an http(s) URL whose body is cached and reused for `policy_bundle.ttl_hours`
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+pub fn load(
+    source: &str,
+    base_dir: &Path,
+    cfg: &PolicyBundleConfig,
+    net_cfg: &NetConfig,
+) -> Result<String> {
+    if !is_remote(source) {
+        let path = base_dir.join(source);
+        return fs::read_to_string(&path)
+            .with_context(|| format!("failed reading extended config {}", path.display()));
+    }
+
+    let cached = cache_path(source);
+    if is_fresh(&cached, cfg.ttl_hours) {
+        return read_cached(source, &cached, base_dir, cfg);
+    }
+    // an unreachable server (or --offline) should not break every run while a copy exists
+    match update(source, base_dir, cfg, net_cfg) {
+        Ok(content) => Ok(content),
+        Err(err) if cached.is_file() => {
+            eprintln!(
+                "warning: using the stale cached policy bundle for {}: {:#}",
+                source, err
+            );
+            read_cached(source, &cached, base_dir, cfg)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+// `devguard policy update`: fetch, verify, and cache regardless of the cached copy's age
+pub fn update(
+    source: &str,
+    base_dir: &Path,
+    cfg: &PolicyBundleConfig,
+    net_cfg: &NetConfig,
+) -> Result<String> {
+    if !is_remote(source) {
+        bail!(
+            "extends = {:?} is a local path; only URLs are fetched",
+            source
+        );
+    }
+    let client = net::Client::new(net_cfg)?;
+    let content = fetch(&client, source)?;
+    let signature = match &cfg.public_key {
+        Some(public_key) => {
+            let signature = fetch(&client, &signature_url(source))?;
+            verify(source, &content, &signature, &base_dir.join(public_key))?;
+            Some(signature)
+        }
+        None => None,
+    };
+
+    let cached = cache_path(source);
+    sandbox::ensure_allowed(&format!("writing {}", cached.display()))?;
+    if let Some(parent) = cached.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed creating {}", parent.display()))?;
+    }
+    fs::write(&cached, &content).with_context(|| format!("failed writing {}", cached.display()))?;
+    if let Some(signature) = signature {
+        let signature_file = signature_path(&cached);
+        fs::write(&signature_file, signature)
+            .with_context(|| format!("failed writing {}", signature_file.display()))?;
+    }
+    Ok(content)
+}
+
+fn fetch(client: &net::Client, url: &str) -> Result<String> {
+    let response = client.request("GET", url, &[], None)?;
+    if !response.is_success() {
+        bail!("GET {} returned HTTP {}", url, response.status);
+    }
+    Ok(response.text)
+}
+
+// the cached copy is checked again on every read, so editing the cache does not bypass
+// the signature
+fn read_cached(
+    source: &str,
+    cached: &Path,
+    base_dir: &Path,
+    cfg: &PolicyBundleConfig,
+) -> Result<String> {
+    let content = fs::read_to_string(cached)
+        .with_context(|| format!("failed reading cached policy bundle {}", cached.display()))?;
+    if let Some(public_key) = &cfg.public_key {
+        let signature_file = signature_path(cached);
+        let signature = fs::read_to_string(&signature_file).with_context(|| {
+            format!(
+                "no cached signature for {} (run `devguard policy update`)",
+                source
+            )
+        })?;
+        verify(source, &content, &signature, &base_dir.join(public_key))?;
+    }
+    Ok(content)
+}
+
+fn verify(source: &str, content: &str, signature: &str, public_key: &Path) -> Result<()> {
+    let public_key_text = fs::read_to_string(public_key)
+        .with_context(|| format!("failed reading {}", public_key.display()))?;
+    attest::verify_signature(content.as_bytes(), signature, &public_key_text)
+        .with_context(|| format!("signature check failed for policy bundle {}", source))
+}
+
+fn is_fresh(path: &Path, ttl_hours: u64) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < Duration::from_secs(ttl_hours * 3600))
+}
+
+fn signature_url(source: &str) -> String {
+    format!("{}.minisig", source)
+}
+
+fn signature_path(cached: &Path) -> PathBuf {
+    let mut name = cached.as_os_str().to_owned();
+    name.push(".minisig");
+    PathBuf::from(name)
+}
+
+fn cache_path(source: &str) -> PathBuf {
+    cache_dir()
+        .join("policies")
+        .join(format!("{}.toml", &sha256_hex(source.as_bytes())[..32]))
+}
+
+pub fn cache_dir() -> PathBuf {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    if let Some(dir) = var("DEVGUARD_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = var("XDG_CACHE_HOME") {
+        return PathBuf::from(dir).join("devguard");
+    }
+    if let Some(dir) = var("LOCALAPPDATA") {
+        return PathBuf::from(dir).join("devguard").join("cache");
+    }
+    match var("HOME") {
+        Some(home) => PathBuf::from(home).join(".cache").join("devguard"),
+        None => std::env::temp_dir().join("devguard-cache"),
+    }
+}
@@ -0,0 +1,183 @@
+use crate::providers;
+use anyhow::{Result, bail};
+use std::collections::BTreeSet;
+
+// the independently selectable parts of a run; category toggles in config still apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckGroup {
+    Secrets,
+    Env,
+    Git,
+    Staleness,
+    Release,
+    Supply,
+    Provider(&'static str),
+}
+
+const BUILTIN_GROUPS: [(&str, CheckGroup); 6] = [
+    ("secrets", CheckGroup::Secrets),
+    ("env", CheckGroup::Env),
+    ("git", CheckGroup::Git),
+    ("staleness", CheckGroup::Staleness),
+    ("release", CheckGroup::Release),
+    ("supply", CheckGroup::Supply),
+];
+
+impl CheckGroup {
+    // `providers` stands for every provider
+    fn parse(name: &str) -> Result<Vec<CheckGroup>> {
+        let name = name.trim().to_ascii_lowercase();
+        if let Some((_, group)) = BUILTIN_GROUPS.iter().find(|(slug, _)| *slug == name) {
+            return Ok(vec![*group]);
+        }
+        let providers = provider_groups();
+        if name == "providers" {
+            return Ok(providers);
+        }
+        match providers
+            .into_iter()
+            .find(|group| matches!(group, CheckGroup::Provider(provider) if *provider == name))
+        {
+            Some(group) => Ok(vec![group]),
+            None => bail!(
+                "unknown check group `{}` (expected one of: {})",
+                name,
+                group_names().join(", ")
+            ),
+        }
+    }
+}
+
+fn provider_groups() -> Vec<CheckGroup> {
+    providers::all_providers()
+        .iter()
+        .map(|provider| CheckGroup::Provider(provider.name()))
+        .collect()
+}
+
+fn group_names() -> Vec<&'static str> {
+    let mut names = BUILTIN_GROUPS
+        .iter()
+        .map(|(slug, _)| *slug)
+        .collect::<Vec<_>>();
+    names.push("providers");
+    names.extend(
+        providers::all_providers()
+            .iter()
+            .map(|provider| provider.name()),
+    );
+    names
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckSet {
+    groups: BTreeSet<CheckGroup>,
+    // the set was asked for by name, so selected providers that cannot run say why
+    explicit: bool,
+    force_providers: bool,
+}
+
+impl CheckSet {
+    pub fn all() -> Self {
+        let mut groups = BUILTIN_GROUPS
+            .iter()
+            .map(|(_, group)| *group)
+            .collect::<BTreeSet<_>>();
+        groups.extend(provider_groups());
+        Self {
+            groups,
+            explicit: false,
+            force_providers: false,
+        }
+    }
+
+    pub fn of(groups: impl IntoIterator<Item = CheckGroup>) -> Self {
+        Self {
+            groups: groups.into_iter().collect(),
+            explicit: true,
+            force_providers: false,
+        }
+    }
+
+    // selected providers run even when detection finds nothing
+    pub fn forcing_providers(mut self, force: bool) -> Self {
+        self.force_providers = force;
+        self
+    }
+
+    // `--checks` replaces the command's default groups and `--skip` removes from them
+    pub fn narrowed(self, checks: &[String], skip: &[String]) -> Result<Self> {
+        let mut set = if checks.is_empty() {
+            self
+        } else {
+            let mut groups = BTreeSet::new();
+            for name in checks {
+                groups.extend(CheckGroup::parse(name)?);
+            }
+            Self {
+                groups,
+                explicit: true,
+                force_providers: self.force_providers,
+            }
+        };
+        for name in skip {
+            for group in CheckGroup::parse(name)? {
+                set.groups.remove(&group);
+            }
+        }
+        Ok(set)
+    }
+
+    pub fn includes(&self, group: CheckGroup) -> bool {
+        self.groups.contains(&group)
+    }
+
+    pub fn is_explicit(&self) -> bool {
+        self.explicit
+    }
+
+    pub fn forces_providers(&self) -> bool {
+        self.force_providers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(set: &CheckSet) -> Vec<String> {
+        set.groups
+            .iter()
+            .map(|group| format!("{:?}", group))
+            .collect()
+    }
+
+    #[test]
+    fn checks_and_skips_compose() {
+        let strings = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let picked = CheckSet::all()
+            .narrowed(&strings(&["secrets", "Supabase"]), &[])
+            .expect("valid groups");
+        assert_eq!(names(&picked), ["Secrets", "Provider(\"supabase\")"]);
+        assert!(picked.is_explicit());
+
+        let skipped = CheckSet::all()
+            .narrowed(&[], &strings(&["git", "providers"]))
+            .expect("valid groups");
+        assert!(!skipped.is_explicit());
+        assert!(!skipped.includes(CheckGroup::Git));
+        assert!(!skipped.includes(CheckGroup::Provider("stripe")));
+        assert!(skipped.includes(CheckGroup::Secrets));
+
+        let error = CheckSet::all()
+            .narrowed(&strings(&["secret"]), &[])
+            .expect_err("unknown group");
+        assert!(error.to_string().contains("expected one of: secrets, env"));
+    }
+}
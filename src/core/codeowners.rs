@@ -0,0 +1,242 @@
+use crate::config::Config;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs as fs_utils;
+use crate::utils::git as git_utils;
+use crate::utils::glob;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeSet;
+
+const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+static OWNER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(@[A-Za-z0-9][A-Za-z0-9-]*(/[A-Za-z0-9._-]+)?|[^@\s]+@[^@\s]+\.[^@\s]+)$")
+        .expect("valid codeowners owner regex")
+});
+
+#[derive(Debug, Clone)]
+pub struct OwnerRule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+    pub line: usize,
+    matcher: Option<Regex>,
+}
+
+impl OwnerRule {
+    pub fn matches(&self, path: &str) -> bool {
+        self.matcher
+            .as_ref()
+            .is_some_and(|matcher| matcher.is_match(path))
+    }
+}
+
+pub fn parse_codeowners(content: &str) -> Vec<OwnerRule> {
+    let mut rules = Vec::new();
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let Some(pattern) = parts.next() else {
+            continue;
+        };
+        let owners = parts
+            .take_while(|part| !part.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+
+        rules.push(OwnerRule {
+            pattern: pattern.to_string(),
+            owners,
+            line: idx + 1,
+            matcher: pattern_to_regex(pattern),
+        });
+    }
+
+    rules
+}
+
+// last matching rule wins, mirroring GitHub's CODEOWNERS semantics.
+pub fn owners_for<'a>(rules: &'a [OwnerRule], path: &str) -> Option<&'a [String]> {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(path))
+        .map(|rule| rule.owners.as_slice())
+}
+
+pub fn run_codeowners_checks(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let Some(repo) = &ctx.git_repo else {
+        return issues;
+    };
+
+    let Some(location) = CODEOWNERS_LOCATIONS
+        .iter()
+        .find(|location| ctx.repo_root.join(location).is_file())
+    else {
+        issues.push(Issue::from_rule(
+            rules::GIT_CODEOWNERS_MISSING,
+            Severity::Info,
+            "no CODEOWNERS file found",
+            "add .github/CODEOWNERS so sensitive paths get a required reviewer",
+        ));
+        return issues;
+    };
+
+    let Ok(content) = fs_utils::read_text(&ctx.repo_root.join(location)) else {
+        return issues;
+    };
+    let owner_rules = parse_codeowners(&content);
+    let tracked = git_utils::tracked_paths(repo).unwrap_or_default();
+
+    for rule in &owner_rules {
+        if rule.matcher.is_none() {
+            issues.push(
+                Issue::from_rule(
+                    rules::GIT_CODEOWNERS_INVALID_RULE,
+                    Severity::Warning,
+                    format!("CODEOWNERS pattern {} is not valid", rule.pattern),
+                    "fix the pattern syntax so GitHub applies the rule",
+                )
+                .with_file(location.to_string())
+                .with_line(rule.line),
+            );
+            continue;
+        }
+
+        if !tracked.is_empty() && !tracked.iter().any(|path| rule.matches(path)) {
+            issues.push(
+                Issue::from_rule(
+                    rules::GIT_CODEOWNERS_INVALID_RULE,
+                    Severity::Warning,
+                    format!(
+                        "CODEOWNERS pattern {} matches no tracked files",
+                        rule.pattern
+                    ),
+                    "remove the stale rule or update it to the path's new location",
+                )
+                .with_file(location.to_string())
+                .with_line(rule.line),
+            );
+        }
+
+        for owner in rule.owners.iter().filter(|owner| !OWNER_RE.is_match(owner)) {
+            issues.push(
+                Issue::from_rule(
+                    rules::GIT_CODEOWNERS_INVALID_RULE,
+                    Severity::Warning,
+                    format!("CODEOWNERS owner {} is not a user, team, or email", owner),
+                    "use @user, @org/team, or an email address",
+                )
+                .with_file(location.to_string())
+                .with_line(rule.line),
+            );
+        }
+    }
+
+    for dir in unowned_sensitive_dirs(&owner_rules, &tracked, &cfg.git.codeowners_sensitive_dirs) {
+        issues.push(
+            Issue::from_rule(
+                rules::GIT_SENSITIVE_DIR_UNOWNED,
+                Severity::Warning,
+                "sensitive directory has no code owner",
+                format!("add a CODEOWNERS rule such as `/{}/ @org/security`", dir),
+            )
+            .with_file(dir)
+            .with_description(format!("CODEOWNERS file: {}", location)),
+        );
+    }
+
+    issues
+}
+
+fn unowned_sensitive_dirs(
+    owner_rules: &[OwnerRule],
+    tracked: &[String],
+    sensitive_names: &[String],
+) -> BTreeSet<String> {
+    let mut unowned = BTreeSet::new();
+
+    for path in tracked {
+        let segments = path.split('/').collect::<Vec<_>>();
+        let Some(depth) = segments[..segments.len().saturating_sub(1)]
+            .iter()
+            .position(|segment| {
+                sensitive_names
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(segment))
+            })
+        else {
+            continue;
+        };
+
+        let has_owner = owners_for(owner_rules, path).is_some_and(|owners| !owners.is_empty());
+        if !has_owner {
+            unowned.insert(segments[..=depth].join("/"));
+        }
+    }
+
+    unowned
+}
+
+fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let body = trimmed.trim_start_matches('/');
+    if body.is_empty() {
+        return None;
+    }
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    regex.push_str(&glob::glob_body(body));
+    regex.push_str(if dir_only { "/.*$" } else { "(?:/.*)?$" });
+
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_follow_gitignore_style_matching() {
+        let rules = parse_codeowners(
+            "# owners\n* @org/everyone\n/src/auth/ @org/security\n*.sql @dba@example.com\ndocs/ \n",
+        );
+        assert_eq!(rules.len(), 4);
+
+        assert_eq!(
+            owners_for(&rules, "src/auth/login.ts"),
+            Some(&["@org/security".to_string()][..])
+        );
+        assert_eq!(
+            owners_for(&rules, "db/migrations/001.sql"),
+            Some(&["@dba@example.com".to_string()][..])
+        );
+        assert_eq!(owners_for(&rules, "docs/readme.md"), Some(&[][..]));
+        assert!(!rules[1].matches("lib/src/auth/login.ts"));
+    }
+
+    #[test]
+    fn finds_sensitive_directories_without_owners() {
+        let rules = parse_codeowners("/src/auth/ @org/security\n");
+        let tracked = vec![
+            "src/auth/login.ts".to_string(),
+            "src/payments/charge.ts".to_string(),
+            "src/payments".to_string(),
+            "README.md".to_string(),
+        ];
+        let sensitive = vec!["auth".to_string(), "payments".to_string()];
+
+        let unowned = unowned_sensitive_dirs(&rules, &tracked, &sensitive);
+        assert_eq!(
+            unowned.into_iter().collect::<Vec<_>>(),
+            vec!["src/payments"]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use std::ops::Range;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    JavaScript,
+    Python,
+    Rust,
+    Sql,
+}
+
+impl Language {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Some(Self::JavaScript),
+            "py" | "pyi" => Some(Self::Python),
+            "rs" => Some(Self::Rust),
+            "sql" => Some(Self::Sql),
+            _ => None,
+        }
+    }
+
+    fn line_comment(self) -> &'static [u8] {
+        match self {
+            Self::JavaScript | Self::Rust => b"//",
+            Self::Python => b"#",
+            Self::Sql => b"--",
+        }
+    }
+
+    fn has_block_comments(self) -> bool {
+        self != Self::Python
+    }
+
+    fn is_quote(self, byte: u8) -> bool {
+        match self {
+            Self::JavaScript => matches!(byte, b'"' | b'\'' | b'`'),
+            Self::Python | Self::Sql => matches!(byte, b'"' | b'\''),
+            // single quotes are char literals or lifetimes, handled separately
+            Self::Rust => byte == b'"',
+        }
+    }
+}
+
+// byte ranges of every comment in a file, found by a small tokenizer that understands
+// string literals well enough not to mistake `"http://..."` or `'#'` for a comment.
+// template literal interpolation and js regex literals are not parsed; both are rare
+// enough around secrets that treating them as plain text is fine.
+#[derive(Debug, Default)]
+pub struct Comments {
+    ranges: Vec<Range<usize>>,
+}
+
+impl Comments {
+    pub fn parse(content: &str, language: Language) -> Self {
+        let bytes = content.as_bytes();
+        let mut ranges = Vec::new();
+        let mut idx = 0;
+
+        while idx < bytes.len() {
+            let rest = &bytes[idx..];
+            if rest.starts_with(language.line_comment()) {
+                let end = find_from(bytes, idx, b"\n").unwrap_or(bytes.len());
+                ranges.push(idx..end);
+                idx = end;
+            } else if language.has_block_comments() && rest.starts_with(b"/*") {
+                let end = block_comment_end(bytes, idx, language == Language::Rust);
+                ranges.push(idx..end);
+                idx = end;
+            } else if language == Language::Rust && bytes[idx] == b'\'' {
+                idx = skip_rust_char(content, idx);
+            } else if language == Language::Rust
+                && let Some(end) = rust_raw_string_end(bytes, idx)
+            {
+                idx = end;
+            } else if language == Language::Python
+                && (rest.starts_with(b"\"\"\"") || rest.starts_with(b"'''"))
+            {
+                idx = string_end(bytes, idx + 3, &rest[..3], true);
+            } else if language.is_quote(bytes[idx]) {
+                // sql doubles its quotes instead of escaping, which this handles as two strings
+                let escapes = language != Language::Sql;
+                idx = string_end(bytes, idx + 1, &rest[..1], escapes);
+            } else {
+                idx += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    // true when the whole byte range sits inside a single comment
+    pub fn covers(&self, range: Range<usize>) -> bool {
+        let after = self
+            .ranges
+            .partition_point(|comment| comment.start <= range.start);
+        after > 0 && range.end <= self.ranges[after - 1].end
+    }
+}
+
+fn find_from(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| from + pos)
+}
+
+fn string_end(bytes: &[u8], mut idx: usize, quote: &[u8], escapes: bool) -> usize {
+    while idx < bytes.len() {
+        if escapes && bytes[idx] == b'\\' {
+            idx += 2;
+        } else if bytes[idx..].starts_with(quote) {
+            return idx + quote.len();
+        } else if bytes[idx] == b'\n' && quote.len() == 1 && quote[0] != b'`' {
+            // an unterminated single-line string ends at the newline rather than eating the file
+            return idx;
+        } else {
+            idx += 1;
+        }
+    }
+    bytes.len()
+}
+
+// rust block comments nest; js and sql ones end at the first `*/`
+fn block_comment_end(bytes: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 0;
+    let mut idx = start;
+    while idx < bytes.len() {
+        if bytes[idx..].starts_with(b"/*") {
+            depth += 1;
+            idx += 2;
+        } else if bytes[idx..].starts_with(b"*/") {
+            depth -= 1;
+            idx += 2;
+            if depth == 0 || !nested {
+                return idx;
+            }
+        } else {
+            idx += 1;
+        }
+    }
+    bytes.len()
+}
+
+// `'a'` and `'\n'` are skipped whole, a lifetime like `'static` only by its quote
+fn skip_rust_char(content: &str, idx: usize) -> usize {
+    let rest = &content[idx + 1..];
+    if let Some(escaped) = rest.strip_prefix('\\') {
+        return match escaped.get(1..).and_then(|tail| tail.find('\'')) {
+            Some(pos) => idx + 3 + pos + 1,
+            None => idx + 1,
+        };
+    }
+    match rest.chars().next() {
+        Some(ch) if rest[ch.len_utf8()..].starts_with('\'') => idx + 1 + ch.len_utf8() + 1,
+        _ => idx + 1,
+    }
+}
+
+// `r"..."`, `r#"..."#`, `br##"..."##`
+fn rust_raw_string_end(bytes: &[u8], idx: usize) -> Option<usize> {
+    let is_ident = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    if bytes[idx] != b'r' {
+        return None;
+    }
+    let prefix_start = match idx.checked_sub(1).map(|prev| bytes[prev]) {
+        Some(b'b') => idx - 1,
+        _ => idx,
+    };
+    if prefix_start > 0 && is_ident(bytes[prefix_start - 1]) {
+        return None;
+    }
+    let hashes = bytes[idx + 1..]
+        .iter()
+        .take_while(|byte| **byte == b'#')
+        .count();
+    let open = idx + 1 + hashes;
+    if bytes.get(open) != Some(&b'"') {
+        return None;
+    }
+    let mut close = vec![b'"'];
+    close.resize(hashes + 1, b'#');
+    Some(find_from(bytes, open + 1, &close).map_or(bytes.len(), |pos| pos + close.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment_text(content: &str, language: Language) -> Vec<&str> {
+        Comments::parse(content, language)
+            .ranges
+            .into_iter()
+            .map(|range| &content[range])
+            .collect()
+    }
+
+    #[test]
+    fn javascript_strings_hide_comment_markers() {
+        let content = "const url = \"https://api.example.com\"; // real comment\nconst s = '/* not */';\n/* block\n comment */ const t = `//${x}`;\n";
+        assert_eq!(
+            comment_text(content, Language::JavaScript),
+            ["// real comment", "/* block\n comment */"]
+        );
+    }
+
+    #[test]
+    fn python_and_sql_comments() {
+        let content = "color = '#fff'  # the default\ndoc = \"\"\"\n# not a comment\n\"\"\"\n";
+        assert_eq!(comment_text(content, Language::Python), ["# the default"]);
+
+        let sql = "select 'it''s -- fine' from t; -- trailing\n/* note */ select 1;\n";
+        assert_eq!(
+            comment_text(sql, Language::Sql),
+            ["-- trailing", "/* note */"]
+        );
+    }
+
+    #[test]
+    fn rust_nested_blocks_raw_strings_and_chars() {
+        let content = "let q = '\"'; // after char\nlet r = r#\"// raw \"# ;\n/* outer /* inner */ still */ fn f<'a>() {}\n";
+        assert_eq!(
+            comment_text(content, Language::Rust),
+            ["// after char", "/* outer /* inner */ still */"]
+        );
+    }
+
+    #[test]
+    fn covers_requires_the_whole_range() {
+        let content = "key = 1 // abc\n";
+        let comments = Comments::parse(content, Language::JavaScript);
+        let start = content.find("abc").expect("present");
+        assert!(comments.covers(start..start + 3));
+        assert!(!comments.covers(0..3));
+        assert!(!comments.covers(start..content.len()));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::core::{Issue, RemediationAction, Severity, rules};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+// DER-encoded OID 1.2.840.113549.1.7.1 (pkcs7-data), which opens every PKCS#12 auth safe
+const PKCS7_DATA_OID: &[u8] = &[
+    0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x01,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialFile {
+    Kubeconfig,
+    DockerAuths,
+    GcloudCredentials,
+    PrivateJwk,
+    Pkcs12,
+}
+
+static KUBE_KIND_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^kind:\s*Config\s*$").expect("valid kubeconfig kind regex"));
+static KUBE_SECTION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^(clusters|users):\s*$").expect("valid kubeconfig section regex")
+});
+static KUBE_CREDENTIAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\s*(token|client-key-data|password|client-certificate-data):\s*\S+")
+        .expect("valid kubeconfig credential regex")
+});
+
+// recognises credential files by content so renamed copies are still caught.
+pub fn detect(content: &str) -> Option<CredentialFile> {
+    if is_kubeconfig(content) {
+        return Some(CredentialFile::Kubeconfig);
+    }
+
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with('{') {
+        return None;
+    }
+    let json = serde_json::from_str::<Value>(trimmed).ok()?;
+    if has_docker_auths(&json) {
+        Some(CredentialFile::DockerAuths)
+    } else if is_gcloud_credentials(&json) {
+        Some(CredentialFile::GcloudCredentials)
+    } else if has_private_jwk(&json) {
+        Some(CredentialFile::PrivateJwk)
+    } else {
+        None
+    }
+}
+
+fn is_kubeconfig(content: &str) -> bool {
+    KUBE_KIND_RE.is_match(content)
+        && KUBE_SECTION_RE.find_iter(content).count() >= 2
+        && KUBE_CREDENTIAL_RE.is_match(content)
+}
+
+fn has_docker_auths(json: &Value) -> bool {
+    json.get("auths")
+        .and_then(Value::as_object)
+        .is_some_and(|auths| {
+            auths.values().any(|entry| {
+                ["auth", "identitytoken", "password"].iter().any(|field| {
+                    entry
+                        .get(field)
+                        .and_then(Value::as_str)
+                        .is_some_and(|v| !v.is_empty())
+                })
+            })
+        })
+}
+
+fn is_gcloud_credentials(json: &Value) -> bool {
+    let has = |field: &str| {
+        json.get(field)
+            .and_then(Value::as_str)
+            .is_some_and(|v| !v.is_empty())
+    };
+    match json.get("type").and_then(Value::as_str) {
+        Some("authorized_user") => has("refresh_token") && has("client_secret"),
+        Some("service_account") => has("private_key"),
+        Some("external_account") => has("audience") && has("credential_source"),
+        _ => false,
+    }
+}
+
+// a JWK (or JWK set) carrying the private exponent `d`
+fn has_private_jwk(json: &Value) -> bool {
+    let is_private = |key: &Value| {
+        key.get("kty").and_then(Value::as_str).is_some()
+            && key
+                .get("d")
+                .and_then(Value::as_str)
+                .is_some_and(|d| !d.is_empty())
+    };
+    is_private(json)
+        || json
+            .get("keys")
+            .and_then(Value::as_array)
+            .is_some_and(|keys| keys.iter().any(is_private))
+}
+
+// PKCS#12 bundles are binary, so they are sniffed by extension and DER header
+// instead of going through the text scanner.
+pub fn detect_pkcs12(path: &Path) -> Option<CredentialFile> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if extension != "pfx" && extension != "p12" {
+        return None;
+    }
+
+    let mut header = Vec::with_capacity(64);
+    File::open(path)
+        .ok()?
+        .take(64)
+        .read_to_end(&mut header)
+        .ok()?;
+    is_pkcs12_header(&header).then_some(CredentialFile::Pkcs12)
+}
+
+fn is_pkcs12_header(header: &[u8]) -> bool {
+    header.first() == Some(&0x30)
+        && header
+            .windows(PKCS7_DATA_OID.len())
+            .any(|window| window == PKCS7_DATA_OID)
+}
+
+pub fn build_issue(kind: CredentialFile, relative_file: &str) -> Issue {
+    let issue = match kind {
+        CredentialFile::Kubeconfig => Issue::from_rule(
+            rules::SECRET_KUBECONFIG,
+            Severity::Error,
+            "kubeconfig with cluster credentials committed",
+            "remove the file from git, revoke the embedded tokens or certificates, and rotate cluster credentials",
+        ),
+        CredentialFile::DockerAuths => Issue::from_rule(
+            rules::SECRET_DOCKER_AUTHS,
+            Severity::Error,
+            "Docker config with registry auths committed",
+            "remove the file from git, revoke the registry tokens, and log in again on each machine",
+        ),
+        CredentialFile::GcloudCredentials => Issue::from_rule(
+            rules::SECRET_GCLOUD_CREDENTIALS,
+            Severity::Error,
+            "Google Cloud credential file committed",
+            "remove the file from git, revoke the credential in the Cloud console, and use workload identity or a secret manager",
+        ),
+        CredentialFile::PrivateJwk => Issue::from_rule(
+            rules::SECRET_PRIVATE_KEY,
+            Severity::Error,
+            "JWK with private key components committed",
+            "remove the key from source, rotate it, and publish only the public JWK",
+        ),
+        CredentialFile::Pkcs12 => Issue::from_rule(
+            rules::SECRET_PRIVATE_KEY,
+            Severity::Error,
+            "PKCS#12 key bundle committed",
+            "remove the .pfx/.p12 file from git and reissue the certificate and key",
+        ),
+    };
+    let rotate = match kind {
+        CredentialFile::Kubeconfig => RemediationAction::rotate_key("kubernetes"),
+        CredentialFile::DockerAuths => RemediationAction::rotate_key("docker"),
+        CredentialFile::GcloudCredentials => RemediationAction::rotate_key("gcloud"),
+        CredentialFile::PrivateJwk | CredentialFile::Pkcs12 => {
+            RemediationAction::RotateKey { provider: None }
+        }
+    };
+    issue
+        .with_file(relative_file.to_string())
+        .with_action(RemediationAction::UntrackFile {
+            path: relative_file.to_string(),
+        })
+        .with_action(rotate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_credential_files_by_content() {
+        let kubeconfig = "apiVersion: v1\nkind: Config\nclusters:\n- name: prod\nusers:\n- name: admin\n  user:\n    token: abc123\n";
+        assert_eq!(detect(kubeconfig), Some(CredentialFile::Kubeconfig));
+
+        let docker = r#"{"auths": {"ghcr.io": {"auth": "dXNlcjpwYXNz"}}}"#;
+        assert_eq!(detect(docker), Some(CredentialFile::DockerAuths));
+
+        let adc = r#"{"type": "authorized_user", "client_id": "x", "client_secret": "y", "refresh_token": "z"}"#;
+        assert_eq!(detect(adc), Some(CredentialFile::GcloudCredentials));
+    }
+
+    #[test]
+    fn detects_private_jwks_and_pkcs12_headers() {
+        let jwks = r#"{"keys": [{"kty": "RSA", "n": "abc", "e": "AQAB", "d": "secret"}]}"#;
+        assert_eq!(detect(jwks), Some(CredentialFile::PrivateJwk));
+        assert_eq!(detect(r#"{"kty": "RSA", "n": "abc", "e": "AQAB"}"#), None);
+
+        let mut header = vec![0x30, 0x82, 0x0A, 0x01, 0x02, 0x01, 0x03, 0x30, 0x82];
+        header.extend_from_slice(PKCS7_DATA_OID);
+        assert!(is_pkcs12_header(&header));
+        assert!(!is_pkcs12_header(b"-----BEGIN CERTIFICATE-----"));
+    }
+
+    #[test]
+    fn ignores_lookalikes_without_credentials() {
+        assert_eq!(detect(r#"{"auths": {}, "credsStore": "desktop"}"#), None);
+        assert_eq!(detect("kind: Config\nclusters:\nusers:\n"), None);
+        assert_eq!(detect(r#"{"type": "module", "name": "app"}"#), None);
+    }
+}
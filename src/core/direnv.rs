@@ -0,0 +1,231 @@
+use crate::config::Config;
+use crate::core::scanner::{self, Allowlist};
+use crate::core::{Issue, RepoContext, Severity, rules, structured};
+use crate::utils::fs::{decode_text, relative_path};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+
+static ASSIGNMENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:export\s+)?([A-Za-z_][A-Za-z0-9_]*)=(.*)$").expect("valid assignment regex")
+});
+// direnv's stdlib loaders plus plain shell sourcing; bare `dotenv` loads `.env`
+static SOURCE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(source_env_if_exists|source_env|dotenv_if_exists|dotenv|source|\.)(?:\s+["']?([^"'\s;]+)["']?)?\s*(?:[;#].*)?$"#)
+        .expect("valid source regex")
+});
+
+#[derive(Debug, PartialEq, Eq)]
+struct Assignment {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Source {
+    directive: String,
+    file: String,
+    line: usize,
+}
+
+pub fn check_shell_env_files(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let direnv = &cfg.env.direnv;
+    let allowlist = Allowlist::new(&cfg.scan.allowlist);
+    let forbidden = cfg
+        .env
+        .forbid_commit
+        .iter()
+        .map(|name| name.to_ascii_lowercase())
+        .collect::<HashSet<_>>();
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "direnv checks") {
+        let file_name = entry.file_name().to_string_lossy();
+        if !direnv.files.iter().any(|name| *name == file_name) {
+            continue;
+        }
+        let is_envrc = file_name == ".envrc";
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = decode_text(&bytes);
+        let rel = relative_path(&ctx.repo_root, entry.path());
+        let tracked = ctx.tracked_status(entry.path()) == Some(true);
+        let assignments = parse_assignments(&content);
+
+        for assignment in &assignments {
+            if !is_secret_env_value(&assignment.key, &assignment.value, &allowlist) {
+                continue;
+            }
+            issues.push(
+                Issue::from_rule(
+                    rules::ENV_SHELL_FILE_SECRET,
+                    if tracked {
+                        Severity::Error
+                    } else {
+                        Severity::Warning
+                    },
+                    format!("{} exports secret value {}", rel, assignment.key),
+                    if tracked {
+                        "rotate the value, remove it from git history, and load it from an ignored file or a secret manager"
+                    } else {
+                        "move the value into an ignored .env (dotenv_if_exists) or read it from a secret manager"
+                    },
+                )
+                .with_file(rel.clone())
+                .with_line(assignment.line),
+            );
+        }
+
+        if is_envrc && tracked {
+            let literal = assignments
+                .iter()
+                .filter(|assignment| !assignment.value.contains('$'))
+                .count();
+            let issue = Issue::from_rule(
+                rules::ENV_ENVRC_TRACKED,
+                if literal > 0 {
+                    Severity::Warning
+                } else {
+                    Severity::Info
+                },
+                format!("{} is tracked", rel),
+                "keep loader lines such as dotenv_if_exists in the tracked .envrc and put values in an ignored file",
+            )
+            .with_file(rel.clone());
+            issues.push(if literal > 0 {
+                issue.with_description(format!(
+                    "{} literal value(s) are exported to everyone who runs direnv allow",
+                    literal
+                ))
+            } else {
+                issue
+            });
+        }
+
+        if is_envrc && direnv.warn_forbidden_sources {
+            for source in parse_sources(&content) {
+                let name = source.file.rsplit('/').next().unwrap_or(&source.file);
+                if !forbidden.contains(&name.to_ascii_lowercase()) {
+                    continue;
+                }
+                issues.push(
+                    Issue::from_rule(
+                        rules::ENV_ENVRC_FORBIDDEN_SOURCE,
+                        Severity::Warning,
+                        format!("{} loads forbidden env file {}", rel, source.file),
+                        "load a local-only file instead of one listed in env.forbid_commit",
+                    )
+                    .with_file(rel.clone())
+                    .with_line(source.line)
+                    .with_description(format!("via {}", source.directive)),
+                );
+            }
+        }
+    }
+
+    issues
+}
+
+// provider key formats count under any name; other values need a secret-looking name
+pub fn is_secret_env_value(key: &str, value: &str, allowlist: &Allowlist) -> bool {
+    if value.is_empty() || allowlist.allows(value) {
+        return false;
+    }
+    scanner::contains_secret(value, allowlist)
+        || (structured::is_secret_key(key) && structured::looks_like_secret(value))
+}
+
+fn parse_assignments(content: &str) -> Vec<Assignment> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let caps = ASSIGNMENT_RE.captures(line)?;
+            Some(Assignment {
+                key: caps[1].to_string(),
+                value: shell_word(&caps[2]),
+                line: idx + 1,
+            })
+        })
+        .collect()
+}
+
+// the first shell word with its quotes removed; `$(...)` and `${...}` stay as written so
+// references can be told apart from literals
+fn shell_word(raw: &str) -> String {
+    let raw = raw.trim_start();
+    match raw.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let rest = &raw[1..];
+            rest.find(quote)
+                .map_or(rest, |end| &rest[..end])
+                .to_string()
+        }
+        _ => raw
+            .split(|ch: char| ch.is_whitespace() || ch == ';')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+fn parse_sources(content: &str) -> Vec<Source> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let caps = SOURCE_RE.captures(line)?;
+            let directive = caps[1].to_string();
+            let file = match caps.get(2) {
+                Some(file) => file.as_str().to_string(),
+                None if directive.starts_with("dotenv") => ".env".to_string(),
+                None => return None,
+            };
+            Some(Source {
+                directive,
+                file,
+                line: idx + 1,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_and_loaders_are_parsed() {
+        let envrc = "use flake\nexport GITHUB_TOKEN=\"ghp_8f2kQ9xLm3\"\nexport API_KEY=$(op read op://dev/api/key)\nPASSWORD='hunter2!x' # local\nexport NODE_ENV=development\ndotenv\nsource_env_if_exists \"config/.env.production\"\n";
+        let allowlist = Allowlist::default();
+        let secrets = parse_assignments(envrc)
+            .into_iter()
+            .filter(|assignment| {
+                is_secret_env_value(&assignment.key, &assignment.value, &allowlist)
+            })
+            .map(|assignment| (assignment.key, assignment.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            secrets,
+            [("GITHUB_TOKEN".to_string(), 2), ("PASSWORD".to_string(), 4)]
+        );
+
+        let sources = parse_sources(envrc)
+            .into_iter()
+            .map(|source| (source.directive, source.file, source.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sources,
+            [
+                ("dotenv".to_string(), ".env".to_string(), 6),
+                (
+                    "source_env_if_exists".to_string(),
+                    "config/.env.production".to_string(),
+                    7
+                ),
+            ]
+        );
+    }
+}
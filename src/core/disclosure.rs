@@ -0,0 +1,173 @@
+use crate::config::DisclosureConfig;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+use std::net::Ipv4Addr;
+use std::ops::Range;
+
+static IPV4_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b").expect("valid ipv4 regex"));
+static CIDR_SUFFIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^/\d{1,2}(?:[^\w/.]|$)").expect("valid cidr suffix regex"));
+// lowercase only: `System.Internal` style namespaces are code, not hosts
+static HOSTNAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[a-z0-9](?:[a-z0-9-]*[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9-]*[a-z0-9])?)+\b")
+        .expect("valid hostname regex")
+});
+
+// instance metadata services hand out the machine's cloud credentials
+const METADATA_ADDRESSES: [&str; 2] = ["169.254.169.254", "100.100.100.200"];
+const METADATA_HOSTS: [&str; 2] = ["metadata.google.internal", "fd00:ec2::254"];
+// `self.internal` and friends are field accesses that happen to end like a hostname
+const CODE_RECEIVERS: [&str; 12] = [
+    "self", "this", "super", "crate", "cls", "ctx", "module", "exports", "props", "state",
+    "window", "document",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exposure {
+    PrivateAddress,
+    InternalHost,
+    MetadataEndpoint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disclosure {
+    pub kind: Exposure,
+    pub value: String,
+    pub range: Range<usize>,
+}
+
+// each address is reported once per file, at its first occurrence
+pub fn scan(content: &str, cfg: &DisclosureConfig) -> Vec<Disclosure> {
+    let mut found = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push = |kind, range: Range<usize>| {
+        let value = content[range.clone()].to_string();
+        if !allowed(cfg, &value) && seen.insert(value.clone()) {
+            found.push(Disclosure { kind, value, range });
+        }
+    };
+
+    for host in METADATA_HOSTS {
+        if let Some(start) = content.find(host) {
+            push(Exposure::MetadataEndpoint, start..start + host.len());
+        }
+    }
+    for address in IPV4_RE.find_iter(content) {
+        if !standalone(content, address.range()) {
+            continue;
+        }
+        // CIDR blocks such as terraform's `10.0.0.0/16` describe a range every example uses
+        if CIDR_SUFFIX_RE.is_match(&content[address.end()..]) {
+            continue;
+        }
+        let Ok(ip) = address.as_str().parse::<Ipv4Addr>() else {
+            continue;
+        };
+        if METADATA_ADDRESSES.contains(&address.as_str()) {
+            push(Exposure::MetadataEndpoint, address.range());
+        } else if ip.is_private() {
+            push(Exposure::PrivateAddress, address.range());
+        }
+    }
+    for host in HOSTNAME_RE.find_iter(content) {
+        let name = host.as_str();
+        let internal = cfg.host_suffixes.iter().any(|suffix| {
+            let suffix = suffix.trim_start_matches('.');
+            name.strip_suffix(suffix)
+                .is_some_and(|rest| rest.ends_with('.'))
+        });
+        let receiver = name.split('.').next().unwrap_or(name);
+        if internal
+            && !METADATA_HOSTS.contains(&name)
+            && !CODE_RECEIVERS.contains(&receiver)
+            && standalone(content, host.range())
+            && host_context(content, host.range())
+        {
+            push(Exposure::InternalHost, host.range());
+        }
+    }
+
+    found.sort_by_key(|hit| hit.range.start);
+    found
+}
+
+fn allowed(cfg: &DisclosureConfig, value: &str) -> bool {
+    cfg.allow.iter().any(|entry| {
+        entry == value
+            || entry
+                .strip_prefix('.')
+                .is_some_and(|domain| value.ends_with(&format!(".{}", domain)))
+    })
+}
+
+// not a piece of a longer dotted name or version string such as `1.10.0.1.2`
+fn standalone(content: &str, range: Range<usize>) -> bool {
+    let before = content[..range.start].chars().next_back();
+    let mut after = content[range.end..].chars();
+    let continues = match after.next() {
+        Some('.') => after
+            .next()
+            .is_some_and(|next| next.is_ascii_alphanumeric()),
+        Some(next) => next == '-' || next == '_' || next.is_ascii_alphanumeric(),
+        None => false,
+    };
+    !matches!(before, Some('.' | '-' | '_')) && !continues
+}
+
+// where a hostname appears as a value: after a quote, `=`, `:`, `@`, `/`, or whitespace, and
+// not called like a method
+fn host_context(content: &str, range: Range<usize>) -> bool {
+    let before = content[..range.start].chars().next_back();
+    let called = content[range.end..].starts_with('(');
+    !called
+        && before.is_none_or(|before| before.is_whitespace() || "\"'`=:@/,([{<>".contains(before))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(content: &str) -> Vec<(Exposure, String)> {
+        scan(content, &DisclosureConfig::default())
+            .into_iter()
+            .map(|hit| (hit.kind, hit.value))
+            .collect()
+    }
+
+    #[test]
+    fn finds_internal_addresses_and_metadata_endpoints() {
+        let content = "DB_HOST=10.12.0.7\nREPLICA=10.12.0.7\ncache: \"redis.prod.internal:6379\"\nurl = \"http://169.254.169.254/latest/meta-data/\"\nlet token = fetch(\"http://metadata.google.internal/computeMetadata/v1/\")\nldap://ad01.corp\n";
+        assert_eq!(
+            found(content),
+            [
+                (Exposure::PrivateAddress, "10.12.0.7".to_string()),
+                (Exposure::InternalHost, "redis.prod.internal".to_string()),
+                (Exposure::MetadataEndpoint, "169.254.169.254".to_string()),
+                (
+                    Exposure::MetadataEndpoint,
+                    "metadata.google.internal".to_string()
+                ),
+                (Exposure::InternalHost, "ad01.corp".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_code_ranges_and_public_names() {
+        let content = "cidr_block = \"10.0.0.0/16\"\nversion = \"1.10.0.1.2\"\nreturn self.internal;\nx = item.internal()\nSystem.Internal.Foo\nhost = \"api.corp.example.com\"\npublic = 8.8.8.8\n";
+        assert!(found(content).is_empty());
+
+        let cfg = DisclosureConfig {
+            allow: vec![".build.internal".to_string(), "192.168.1.1".to_string()],
+            ..DisclosureConfig::default()
+        };
+        let hits = scan(
+            "ci = ci.build.internal\nrouter = 192.168.1.1\nnas = 192.168.1.20\n",
+            &cfg,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].value, "192.168.1.20");
+    }
+}
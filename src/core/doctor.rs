@@ -0,0 +1,245 @@
+use crate::config::{self, Config};
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::providers;
+use crate::utils::git as git_utils;
+use crate::utils::net;
+use crate::utils::process::{self, CommandOutput};
+use std::path::Path;
+use std::time::Duration;
+
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct ProviderCli {
+    provider: &'static str,
+    program: &'static str,
+    login_args: &'static [&'static str],
+    login_hint: &'static str,
+}
+
+const PROVIDER_CLIS: &[ProviderCli] = &[
+    ProviderCli {
+        provider: "supabase",
+        program: "supabase",
+        login_args: &["projects", "list"],
+        login_hint: "run `supabase login`",
+    },
+    ProviderCli {
+        provider: "vercel",
+        program: "vercel",
+        login_args: &["whoami"],
+        login_hint: "run `vercel login`",
+    },
+    ProviderCli {
+        provider: "stripe",
+        program: "stripe",
+        login_args: &["config", "--list"],
+        login_hint: "run `stripe login`",
+    },
+];
+
+pub fn run_doctor(repo_root: &Path, config_path: Option<&Path>, cwd: &Path) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    issues.push(check_git_cli());
+    let cfg = match config::load_config(config_path, cwd) {
+        Ok(loaded) => {
+            let source = loaded
+                .source
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "built-in defaults".to_string());
+            issues.push(
+                Issue::from_rule(
+                    rules::DOCTOR_CONFIG,
+                    Severity::Pass,
+                    "config parsed",
+                    "no action needed",
+                )
+                .with_description(format!("source: {}", source)),
+            );
+            loaded.config
+        }
+        Err(err) => {
+            issues.push(
+                Issue::from_rule(
+                    rules::DOCTOR_CONFIG,
+                    Severity::Error,
+                    "config failed to load",
+                    "fix the reported error or regenerate a config with `devguard init`",
+                )
+                .with_description(format!("{:#}", err)),
+            );
+            Config::default()
+        }
+    };
+
+    let ctx = match RepoContext::build(repo_root, &cfg) {
+        Ok(ctx) => ctx,
+        Err(err) => {
+            issues.push(
+                Issue::from_rule(
+                    rules::DOCTOR_GIT_REPO,
+                    Severity::Error,
+                    "repository path cannot be scanned",
+                    "pass an existing directory with --path",
+                )
+                .with_description(format!("{:#}", err)),
+            );
+            return issues;
+        }
+    };
+
+    issues.push(match &ctx.git_repo {
+        Some(repo) => Issue::from_rule(
+            rules::DOCTOR_GIT_REPO,
+            Severity::Pass,
+            "git repository opened",
+            "no action needed",
+        )
+        .with_description(format!(
+            "workdir: {}",
+            git_utils::workdir(repo)
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "bare repository".to_string())
+        )),
+        None => Issue::from_rule(
+            rules::DOCTOR_GIT_REPO,
+            Severity::Warning,
+            "no git repository found",
+            "run devguard inside a git checkout so tracking checks can run",
+        ),
+    });
+
+    for provider in providers::all_providers() {
+        let name = provider.name();
+        let detected = provider.detected(&ctx, &cfg);
+        let enabled = provider.is_enabled(&cfg);
+        issues.push(
+            Issue::from_rule(
+                rules::DOCTOR_PROVIDER_DETECTION,
+                if detected {
+                    Severity::Pass
+                } else {
+                    Severity::Info
+                },
+                format!(
+                    "{} {}",
+                    name,
+                    if detected { "detected" } else { "not detected" }
+                ),
+                if detected {
+                    "no action needed".to_string()
+                } else {
+                    format!("provider checks for {} will be skipped", name)
+                },
+            )
+            .with_description(format!(
+                "provider is {} in config",
+                if enabled { "enabled" } else { "disabled" }
+            )),
+        );
+
+        if !detected {
+            continue;
+        }
+
+        if let Some(cli) = PROVIDER_CLIS.iter().find(|cli| cli.provider == name) {
+            issues.extend(check_provider_cli(cli));
+        }
+    }
+
+    issues
+}
+
+fn check_git_cli() -> Issue {
+    match process::run_git(&["--version"], COMMAND_TIMEOUT) {
+        Some(output) if output.success => Issue::from_rule(
+            rules::DOCTOR_GIT_CLI,
+            Severity::Pass,
+            format!("git available: {}", output.first_line()),
+            "no action needed",
+        ),
+        _ => Issue::from_rule(
+            rules::DOCTOR_GIT_CLI,
+            Severity::Warning,
+            "git executable not found",
+            "install git and make sure it is on PATH",
+        ),
+    }
+}
+
+fn check_provider_cli(cli: &ProviderCli) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let version = match process::run_with_timeout(cli.program, &["--version"], COMMAND_TIMEOUT) {
+        Some(output) if output.success => output,
+        _ => {
+            issues.push(Issue::from_rule(
+                rules::DOCTOR_PROVIDER_CLI,
+                Severity::Info,
+                format!("{} CLI not installed", cli.program),
+                format!(
+                    "install the {} CLI to verify the project manually",
+                    cli.program
+                ),
+            ));
+            return issues;
+        }
+    };
+
+    issues.push(Issue::from_rule(
+        rules::DOCTOR_PROVIDER_CLI,
+        Severity::Pass,
+        format!("{} CLI available: {}", cli.program, version.first_line()),
+        "no action needed",
+    ));
+
+    // login checks talk to the provider's API
+    if net::is_offline() {
+        issues.push(
+            Issue::from_rule(
+                rules::DOCTOR_PROVIDER_LOGIN,
+                Severity::Info,
+                format!("{} CLI login not checked", cli.program),
+                "run doctor without --offline or --sandbox to check provider logins",
+            )
+            .with_description("devguard is running offline"),
+        );
+        return issues;
+    }
+
+    let login = process::run_with_timeout(cli.program, cli.login_args, COMMAND_TIMEOUT);
+    issues.push(if is_logged_in(cli, login.as_ref()) {
+        Issue::from_rule(
+            rules::DOCTOR_PROVIDER_LOGIN,
+            Severity::Pass,
+            format!("{} CLI is logged in", cli.program),
+            "no action needed",
+        )
+    } else {
+        Issue::from_rule(
+            rules::DOCTOR_PROVIDER_LOGIN,
+            Severity::Info,
+            format!("{} CLI is not logged in", cli.program),
+            cli.login_hint,
+        )
+        .with_description(format!(
+            "`{} {}` did not succeed",
+            cli.program,
+            cli.login_args.join(" ")
+        ))
+    });
+
+    issues
+}
+
+fn is_logged_in(cli: &ProviderCli, output: Option<&CommandOutput>) -> bool {
+    let Some(output) = output else {
+        return false;
+    };
+    if !output.success {
+        return false;
+    }
+
+    // `stripe config --list` succeeds even when logged out, so look for a stored key.
+    cli.program != "stripe" || output.stdout.contains("api_key")
+}
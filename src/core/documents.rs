@@ -0,0 +1,345 @@
+use crate::config::Config;
+use crate::core::scanner::{self, Allowlist};
+use crate::core::{Issue, RepoContext, Severity, rules, structured};
+use crate::utils::fs::relative_path;
+use crate::utils::zip;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const EXTENSIONS: [&str; 6] = ["docx", "docm", "xlsx", "xlsm", "pptx", "pptm"];
+// per xml part, whatever the archive directory claims
+const MAX_INFLATED: usize = 64 * 1024 * 1024;
+
+static PARAGRAPH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<(?:w|a):p(?:\s[^>]*)?>(.*?)</(?:w|a):p>").expect("valid paragraph regex")
+});
+static TEXT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<(?:w:|a:)?t(?:\s[^>]*)?>(.*?)</(?:w:|a:)?t>").expect("valid text regex")
+});
+static SHARED_STRING_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<si>(.*?)</si>").expect("valid shared string regex"));
+static CELL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<c\s([^>]*?)(?:/>|>(.*?)</c>)").expect("valid cell regex"));
+static CELL_REF_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\br="([A-Z]+)(\d+)""#).expect("valid cell ref regex"));
+static CELL_TYPE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bt="(\w+)""#).expect("valid cell type regex"));
+static CELL_VALUE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<v>(.*?)</v>").expect("valid cell value regex"));
+// "Password: hunter2!" in running text
+static LABELLED_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(password|passwd|pwd|secret|token|api[ _-]?key|access[ _-]?key)\b\s*[:=]\s*(\S+)",
+    )
+    .expect("valid labelled secret regex")
+});
+
+// a run of document text and where it sits: `document paragraph 4`, `sheet1!C7`
+#[derive(Debug)]
+struct Segment {
+    location: String,
+    text: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Finding {
+    location: String,
+    what: String,
+}
+
+fn is_document(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+// office documents are zip archives of xml; only their text is checked, so embedded images
+// and attachments are not
+pub fn scan_documents(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let allowlist = Allowlist::new(&cfg.scan.allowlist);
+    let max_bytes = cfg.scan.documents.max_size_kb * 1024;
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "document scan") {
+        if !is_document(entry.path()) {
+            continue;
+        }
+        let scannable = entry.metadata().is_ok_and(|meta| meta.len() <= max_bytes);
+        let data = scannable.then(|| fs::read(entry.path()).ok()).flatten();
+        ctx.record_read(entry.path(), data.as_ref().map(|data| data.len() as u64));
+        let Some(data) = data else {
+            continue;
+        };
+        let rel = relative_path(&ctx.repo_root, entry.path());
+        // encrypted or damaged documents are skipped like unreadable files
+        let Ok(findings) = scan_document(&data, &allowlist) else {
+            continue;
+        };
+        for finding in findings {
+            issues.push(
+                Issue::from_rule(
+                    rules::SECRET_IN_DOCUMENT,
+                    Severity::Error,
+                    format!("{} in {} at {}", finding.what, rel, finding.location),
+                    "remove the document or the credentials in it, rotate them, and purge the file from git history",
+                )
+                .with_file(rel.clone()),
+            );
+        }
+    }
+
+    issues
+}
+
+fn scan_document(data: &[u8], allowlist: &Allowlist) -> Result<Vec<Finding>> {
+    let entries = zip::entries(data)?;
+    let read = |name: &str| -> Option<String> {
+        let entry = entries.iter().find(|entry| entry.name == name)?;
+        let bytes = zip::read_entry(data, entry, MAX_INFLATED).ok()?;
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    };
+
+    let mut findings = Vec::new();
+    let mut parts = entries
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .filter(|name| name.ends_with(".xml"))
+        .collect::<Vec<_>>();
+    parts.sort();
+
+    let shared_strings = read("xl/sharedStrings.xml")
+        .map(|xml| shared_strings(&xml))
+        .unwrap_or_default();
+    for part in parts {
+        let stem = part
+            .rsplit('/')
+            .next()
+            .unwrap_or(part)
+            .trim_end_matches(".xml");
+        let segments = if part.starts_with("xl/worksheets/") {
+            let Some(xml) = read(part) else { continue };
+            let rows = sheet_rows(&xml, &shared_strings);
+            findings.extend(secret_columns(stem, &rows, allowlist));
+            rows.into_iter()
+                .flat_map(|(row, cells)| {
+                    cells.into_iter().map(move |(column, text)| Segment {
+                        location: format!("{}!{}{}", stem, column, row),
+                        text,
+                    })
+                })
+                .collect()
+        } else if (part.starts_with("word/") || part.starts_with("ppt/slides/"))
+            && !part.contains("/_rels/")
+        {
+            let Some(xml) = read(part) else { continue };
+            paragraphs(&xml)
+                .into_iter()
+                .enumerate()
+                .map(|(idx, text)| Segment {
+                    location: format!("{} paragraph {}", stem, idx + 1),
+                    text,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        findings.extend(
+            segments
+                .iter()
+                .filter_map(|segment| check_segment(segment, allowlist)),
+        );
+    }
+    Ok(findings)
+}
+
+fn check_segment(segment: &Segment, allowlist: &Allowlist) -> Option<Finding> {
+    let what = if scanner::contains_secret(&segment.text, allowlist) {
+        "secret pattern".to_string()
+    } else {
+        let caps = LABELLED_RE.captures(&segment.text)?;
+        let value = caps[2].trim_end_matches(['.', ',', ';', ')']);
+        if !structured::looks_like_secret(value) || allowlist.allows(value) {
+            return None;
+        }
+        format!("{} value", caps[1].to_ascii_lowercase())
+    };
+    Some(Finding {
+        location: segment.location.clone(),
+        what,
+    })
+}
+
+// a header cell naming a secret makes its whole column suspect; one finding per column so a
+// sheet of passwords is one issue rather than hundreds
+fn secret_columns(
+    sheet: &str,
+    rows: &BTreeMap<usize, BTreeMap<String, String>>,
+    allowlist: &Allowlist,
+) -> Vec<Finding> {
+    let Some((header_row, headers)) = rows.iter().next() else {
+        return Vec::new();
+    };
+    let mut findings = Vec::new();
+    for (column, header) in headers {
+        if !structured::is_secret_key(&header.trim().replace(' ', "_")) {
+            continue;
+        }
+        let values = rows
+            .range(header_row + 1..)
+            .filter_map(|(_, cells)| cells.get(column))
+            .filter(|value| structured::looks_like_secret(value) && !allowlist.allows(value))
+            .count();
+        if values > 0 {
+            findings.push(Finding {
+                location: format!("{}!{}", sheet, column),
+                what: format!(
+                    "{} secret-looking value(s) under \"{}\"",
+                    values,
+                    header.trim()
+                ),
+            });
+        }
+    }
+    findings
+}
+
+fn paragraphs(xml: &str) -> Vec<String> {
+    PARAGRAPH_RE
+        .captures_iter(xml)
+        .map(|caps| joined_text(&caps[1]))
+        .filter(|text| !text.trim().is_empty())
+        .collect()
+}
+
+fn shared_strings(xml: &str) -> Vec<String> {
+    SHARED_STRING_RE
+        .captures_iter(xml)
+        .map(|caps| joined_text(&caps[1]))
+        .collect()
+}
+
+// row number -> column letters -> cell text, with shared strings resolved
+fn sheet_rows(xml: &str, shared: &[String]) -> BTreeMap<usize, BTreeMap<String, String>> {
+    let mut rows = BTreeMap::<usize, BTreeMap<String, String>>::new();
+    for caps in CELL_RE.captures_iter(xml) {
+        let attributes = &caps[1];
+        let Some(cell_ref) = CELL_REF_RE.captures(attributes) else {
+            continue;
+        };
+        let Ok(row) = cell_ref[2].parse::<usize>() else {
+            continue;
+        };
+        let body = caps.get(2).map_or("", |body| body.as_str());
+        let value = CELL_VALUE_RE
+            .captures(body)
+            .map(|value| unescape(&value[1]));
+        let text = match CELL_TYPE_RE.captures(attributes).as_ref().map(|ty| &ty[1]) {
+            Some("s") => value
+                .and_then(|idx| idx.trim().parse::<usize>().ok())
+                .and_then(|idx| shared.get(idx).cloned()),
+            Some("inlineStr") => Some(joined_text(body)),
+            _ => value,
+        };
+        if let Some(text) = text.filter(|text| !text.trim().is_empty()) {
+            rows.entry(row)
+                .or_default()
+                .insert(cell_ref[1].to_string(), text);
+        }
+    }
+    rows
+}
+
+fn joined_text(xml: &str) -> String {
+    TEXT_RE
+        .captures_iter(xml)
+        .map(|caps| unescape(&caps[1]))
+        .collect()
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(ch) => {
+                out.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spreadsheet_columns_and_document_paragraphs_are_checked() {
+        let shared = shared_strings(
+            "<sst><si><t>Service</t></si><si><t>Password</t></si><si><t>stripe</t></si><si><r><t>hunter2</t></r><r><t>&amp;Go!</t></r></si></sst>",
+        );
+        assert_eq!(shared[3], "hunter2&Go!");
+        let sheet = r#"<sheetData><row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row><row r="2"><c r="A2" t="s"><v>2</v></c><c r="B2" t="s"><v>3</v></c></row><row r="3"><c r="A3" t="inlineStr"><is><t>aws</t></is></c><c r="B3"><v>42</v></c></row></sheetData>"#;
+        let rows = sheet_rows(sheet, &shared);
+        assert_eq!(rows[&3]["A"], "aws");
+        let allowlist = Allowlist::default();
+        assert_eq!(
+            secret_columns("sheet1", &rows, &allowlist),
+            [Finding {
+                location: "sheet1!B".to_string(),
+                what: "1 secret-looking value(s) under \"Password\"".to_string(),
+            }]
+        );
+
+        let document = r#"<w:body><w:p><w:pPr/><w:r><w:t>Staging admin</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">Password: </w:t></w:r><w:r><w:t>Xk9#mQ2vL7</w:t></w:r></w:p><w:p><w:r><w:t>Reset your password: see the wiki.</w:t></w:r></w:p></w:body>"#;
+        let findings = paragraphs(document)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, text)| {
+                check_segment(
+                    &Segment {
+                        location: format!("document paragraph {}", idx + 1),
+                        text,
+                    },
+                    &allowlist,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings,
+            [Finding {
+                location: "document paragraph 2".to_string(),
+                what: "password value".to_string(),
+            }]
+        );
+    }
+}
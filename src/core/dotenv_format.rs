@@ -0,0 +1,159 @@
+use crate::config::Config;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs::{self as fs_utils, relative_path};
+use std::fs;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+// byte-level problems that devguard reads through but dotenv loaders do not: the key then
+// exists in the file and still never reaches the app
+pub fn check_dotenv_format(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut files = super::discover_dotenv_files(&ctx.repo_root, cfg);
+    for example in &cfg.env.example_files {
+        let path = ctx.repo_root.join(example);
+        if path.is_file() && !files.contains(&path) {
+            files.push(path);
+        }
+    }
+
+    let mut issues = Vec::new();
+    for path in files {
+        let Ok(bytes) = fs::read(&path) else {
+            continue;
+        };
+        let rel = relative_path(&ctx.repo_root, &path);
+
+        if let Some(problem) = encoding_problem(&bytes) {
+            issues.push(
+                Issue::from_rule(
+                    rules::ENV_DOTENV_ENCODING,
+                    Severity::Warning,
+                    format!("{} {}", rel, problem),
+                    "re-save the file as UTF-8 without a byte order mark",
+                )
+                .with_file(rel.clone())
+                .with_line(1)
+                .with_description(
+                    "most loaders read dotenv files as plain UTF-8, so the first key (or every key) gets stray bytes and the app never sees it",
+                ),
+            );
+        }
+
+        let content = fs_utils::decode_text(&bytes);
+        let crlf_lines = content
+            .split('\n')
+            .enumerate()
+            .filter(|(_, line)| line.ends_with('\r'))
+            .map(|(idx, _)| idx + 1)
+            .collect::<Vec<_>>();
+        if !cfg.env.format.allow_crlf && !crlf_lines.is_empty() {
+            issues.push(
+                Issue::from_rule(
+                    rules::ENV_DOTENV_CRLF,
+                    Severity::Warning,
+                    format!("{} uses CRLF line endings", rel),
+                    "convert the file to LF line endings, and add `.env* text eol=lf` to .gitattributes",
+                )
+                .with_file(rel.clone())
+                .with_line(crlf_lines[0])
+                .with_description(format!(
+                    "{} line(s) end in \\r\\n; `docker run --env-file`, `source .env`, and `set -a; . .env` keep the \\r at the end of each value",
+                    crlf_lines.len()
+                )),
+            );
+        }
+
+        let lines = content.lines().collect::<Vec<_>>();
+        for entry in fs_utils::parse_dotenv(&content) {
+            let raw_key = lines[entry.line - 1]
+                .split_once('=')
+                .map_or("", |(key, _)| key);
+            let Some(hidden) = raw_key.chars().find(|ch| !ch.is_ascii()) else {
+                continue;
+            };
+            let visible = raw_key
+                .trim_start()
+                .trim_start_matches("export ")
+                .chars()
+                .filter(|ch| ch.is_ascii_graphic())
+                .collect::<String>();
+            issues.push(
+                Issue::from_rule(
+                    rules::ENV_DOTENV_HIDDEN_KEY_CHAR,
+                    Severity::Warning,
+                    format!(
+                        "key {} in {} contains the non-ASCII character U+{:04X}",
+                        visible, rel, hidden as u32
+                    ),
+                    format!("retype the key name {} using plain ASCII", visible),
+                )
+                .with_file(rel.clone())
+                .with_line(entry.line)
+                .with_description(
+                    "invisible characters such as zero-width or non-breaking spaces make the variable name differ from the one the app reads",
+                ),
+            );
+        }
+    }
+
+    issues
+}
+
+fn encoding_problem(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(UTF8_BOM) {
+        Some("starts with a UTF-8 byte order mark")
+    } else if fs_utils::is_utf16(bytes) {
+        Some("is encoded as UTF-16")
+    } else if std::str::from_utf8(bytes).is_err() {
+        Some("is not valid UTF-8")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_bom_crlf_and_hidden_key_characters() {
+        let root = std::env::temp_dir().join(format!("devguard-dotenv-fmt-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("dir created");
+        fs::write(
+            root.join(".env"),
+            [
+                UTF8_BOM,
+                b"DATABASE_URL=postgres://db\r\nAPI\xe2\x80\x8b_KEY=abc\r\n".as_slice(),
+            ]
+            .concat(),
+        )
+        .expect("file written");
+        fs::write(root.join(".env.example"), "DATABASE_URL=\nAPI_KEY=\n").expect("file written");
+
+        let cfg = Config::default();
+        let ctx = RepoContext::build(&root, &cfg).expect("context builds");
+        let issues = check_dotenv_format(&ctx, &cfg);
+        let mut allow_crlf = cfg.clone();
+        allow_crlf.env.format.allow_crlf = true;
+        let without_crlf = check_dotenv_format(&ctx, &allow_crlf).len();
+        fs::remove_dir_all(&root).ok();
+
+        let found = issues
+            .iter()
+            .map(|issue| (issue.code, issue.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                ("DG_ENV_013", Some(1)),
+                ("DG_ENV_014", Some(1)),
+                ("DG_ENV_015", Some(2))
+            ]
+        );
+        assert_eq!(
+            issues[2].title,
+            "key API_KEY in .env contains the non-ASCII character U+200B"
+        );
+        assert_eq!(without_crlf, 2);
+    }
+}
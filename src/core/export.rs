@@ -0,0 +1,256 @@
+use crate::config::{FailOn, NetConfig};
+use crate::core::Issue;
+use crate::utils::github::GithubClient;
+use crate::utils::net;
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashSet};
+
+const TRACKER_LABEL: &str = "devguard";
+const FINGERPRINT_MARKER: &str = "<!-- devguard:fingerprint=";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub closed: usize,
+}
+
+#[derive(Debug)]
+enum SyncAction<'a> {
+    Create(&'a Issue),
+    Update(u64, &'a Issue),
+    Close(u64),
+}
+
+pub fn exportable(issues: &[Issue], threshold: FailOn) -> Vec<&Issue> {
+    let mut seen = HashSet::new();
+    issues
+        .iter()
+        .filter(|issue| issue.severity.meets_fail_on(threshold))
+        .filter(|issue| seen.insert(issue.fingerprint()))
+        .collect()
+}
+
+// one tracker issue per fingerprint: create new ones, refresh existing ones, and close
+// tracker issues whose finding no longer shows up. `close_missing` is false when the run
+// did not cover everything (narrowed checks, a timeout, nothing read), since a finding
+// missing from such a run may simply not have been looked for
+pub fn sync_github_issues(
+    client: &GithubClient,
+    slug: &str,
+    findings: &[&Issue],
+    close_missing: bool,
+) -> Result<SyncSummary> {
+    let existing = open_tracker_issues(client, slug)?;
+    let mut summary = SyncSummary::default();
+
+    for action in plan_sync(findings, &existing, close_missing) {
+        match action {
+            SyncAction::Create(issue) => {
+                client.send(
+                    "POST",
+                    &format!("/repos/{}/issues", slug),
+                    Some(&json!({
+                        "title": tracker_title(issue),
+                        "body": tracker_body(issue),
+                        "labels": [TRACKER_LABEL, issue.severity.slug()],
+                    })),
+                )?;
+                summary.created += 1;
+            }
+            SyncAction::Update(number, issue) => {
+                client.send(
+                    "PATCH",
+                    &format!("/repos/{}/issues/{}", slug, number),
+                    Some(&json!({
+                        "title": tracker_title(issue),
+                        "body": tracker_body(issue),
+                    })),
+                )?;
+                summary.updated += 1;
+            }
+            SyncAction::Close(number) => {
+                client.send(
+                    "PATCH",
+                    &format!("/repos/{}/issues/{}", slug, number),
+                    Some(&json!({ "state": "closed", "state_reason": "completed" })),
+                )?;
+                summary.closed += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn plan_sync<'a>(
+    findings: &[&'a Issue],
+    existing: &BTreeMap<String, u64>,
+    close_missing: bool,
+) -> Vec<SyncAction<'a>> {
+    let mut actions = Vec::new();
+    let mut current = HashSet::new();
+    for issue in findings {
+        let fingerprint = issue.fingerprint();
+        actions.push(match existing.get(&fingerprint) {
+            Some(number) => SyncAction::Update(*number, issue),
+            None => SyncAction::Create(issue),
+        });
+        current.insert(fingerprint);
+    }
+    for (fingerprint, number) in existing {
+        if close_missing && !current.contains(fingerprint) {
+            actions.push(SyncAction::Close(*number));
+        }
+    }
+    actions
+}
+
+fn open_tracker_issues(client: &GithubClient, slug: &str) -> Result<BTreeMap<String, u64>> {
+    let mut found = BTreeMap::new();
+    for page in 1.. {
+        let path = format!(
+            "/repos/{}/issues?state=open&labels={}&per_page=100&page={}",
+            slug, TRACKER_LABEL, page
+        );
+        let batch = client.get(&path)?.with_context(|| {
+            format!(
+                "GitHub repository {} not found or not visible to this token",
+                slug
+            )
+        })?;
+        let items = batch.as_array().cloned().unwrap_or_default();
+        for item in &items {
+            let body = item.get("body").and_then(Value::as_str).unwrap_or("");
+            if let (Some(fingerprint), Some(number)) = (
+                parse_fingerprint(body),
+                item.get("number").and_then(Value::as_u64),
+            ) {
+                found.insert(fingerprint, number);
+            }
+        }
+        if items.len() < 100 {
+            break;
+        }
+    }
+    Ok(found)
+}
+
+fn parse_fingerprint(body: &str) -> Option<String> {
+    let start = body.find(FINGERPRINT_MARKER)? + FINGERPRINT_MARKER.len();
+    let rest = &body[start..];
+    Some(rest[..rest.find(" -->")?].trim().to_string())
+}
+
+fn tracker_title(issue: &Issue) -> String {
+    match &issue.file {
+        Some(file) => format!("[devguard] {}: {} ({})", issue.code, issue.title, file),
+        None => format!("[devguard] {}: {}", issue.code, issue.title),
+    }
+}
+
+fn tracker_body(issue: &Issue) -> String {
+    let mut lines = vec![
+        format!("**Rule:** `{}` - {}", issue.code, issue.rule_title),
+        format!("**Severity:** {}", issue.severity.slug()),
+        format!("**Category:** {}", issue.category.slug()),
+    ];
+    if let Some(location) = issue.location() {
+        lines.push(format!("**Location:** `{}`", location));
+    }
+    if let Some(description) = &issue.description {
+        lines.push(format!("**Details:** {}", description));
+    }
+    lines.push(format!("**Remediation:** {}", issue.remediation));
+    if let Some(url) = issue.url {
+        lines.push(format!("**Docs:** {}", url));
+    }
+    lines.push(String::new());
+    lines.push("This issue is managed by `devguard export` and closes automatically once the finding is gone.".to_string());
+    lines.push(format!("{}{} -->", FINGERPRINT_MARKER, issue.fingerprint()));
+    lines.join("\n")
+}
+
+// generic trackers (Jira automation, Linear, custom receivers) get the full open set
+// on every run and reconcile by fingerprint themselves.
+pub fn post_webhook(
+    cfg: &NetConfig,
+    url: &str,
+    repository: &str,
+    findings: &[&Issue],
+) -> Result<()> {
+    let payload = json!({
+        "source": "devguard",
+        "repository": repository,
+        "findings": findings
+            .iter()
+            .map(|issue| {
+                json!({
+                    "fingerprint": issue.fingerprint(),
+                    "code": issue.code,
+                    "title": issue.title,
+                    "severity": issue.severity,
+                    "category": issue.category,
+                    "file": issue.file,
+                    "line": issue.line,
+                    "remediation": issue.remediation,
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+
+    let response = net::Client::new(cfg)?
+        .request("POST", url, &[], Some(&payload))
+        .with_context(|| format!("webhook POST to {} failed", url))?;
+    if !response.is_success() {
+        bail!("webhook POST to {} returned {}", url, response.status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Severity, rules};
+
+    fn finding(file: &str) -> Issue {
+        Issue::from_rule(
+            rules::SECRET_AWS_ACCESS_KEY,
+            Severity::Error,
+            "AWS access key pattern detected",
+            "rotate",
+        )
+        .with_file(file)
+    }
+
+    #[test]
+    fn plan_creates_updates_and_closes_by_fingerprint() {
+        let kept = finding("src/a.js");
+        let new = finding("src/b.js");
+        let existing =
+            BTreeMap::from([(kept.fingerprint(), 7), ("0000000000000000".to_string(), 9)]);
+
+        let actions = plan_sync(&[&kept, &new], &existing, true);
+        assert_eq!(actions.len(), 3);
+        assert!(matches!(actions[0], SyncAction::Update(7, issue) if issue.file == kept.file));
+        assert!(matches!(actions[1], SyncAction::Create(issue) if issue.file == new.file));
+        assert!(matches!(actions[2], SyncAction::Close(9)));
+
+        // an incomplete run never closes anything
+        let actions = plan_sync(&[&kept], &existing, false);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], SyncAction::Update(7, _)));
+    }
+
+    #[test]
+    fn tracker_body_round_trips_fingerprint() {
+        let issue = finding("src/a.js").with_line(3);
+        let body = tracker_body(&issue);
+        assert_eq!(parse_fingerprint(&body), Some(issue.fingerprint()));
+        assert_eq!(
+            issue.fingerprint(),
+            finding("src/a.js").with_line(40).fingerprint()
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::config::GeneratedFiles;
+use crate::core::{Issue, Severity};
+
+const VENDORED_DIRS: [&str; 4] = ["vendor", "vendored", "third_party", "third-party"];
+const MINIFIED_SUFFIXES: [&str; 6] = [
+    ".min.js",
+    ".min.mjs",
+    ".min.css",
+    ".bundle.js",
+    ".chunk.js",
+    ".map",
+];
+// lines this long are machine-written; a file counts once most of its bytes sit in them
+const LONG_LINE: usize = 1000;
+
+// why a file looks generated, vendored, or minified, if it does
+pub fn generated_reason(rel: &str, content: &str) -> Option<&'static str> {
+    let name = rel.rsplit('/').next().unwrap_or(rel).to_ascii_lowercase();
+    if MINIFIED_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+    {
+        return Some("minified or bundled file name");
+    }
+    if rel
+        .split('/')
+        .rev()
+        .skip(1)
+        .any(|dir| VENDORED_DIRS.contains(&dir))
+    {
+        return Some("vendored directory");
+    }
+    if content.lines().take(5).any(|line| {
+        line.contains("@generated")
+            || (line.contains("Code generated") && line.contains("DO NOT EDIT"))
+    }) {
+        return Some("generated file marker");
+    }
+    if content
+        .lines()
+        .rev()
+        .take(3)
+        .any(|line| line.contains("sourceMappingURL="))
+    {
+        return Some("source map comment");
+    }
+    let long_bytes = content
+        .lines()
+        .filter(|line| line.len() > LONG_LINE)
+        .map(str::len)
+        .sum::<usize>();
+    if long_bytes > 0 && long_bytes * 2 >= content.len() {
+        return Some("very long lines");
+    }
+    None
+}
+
+// `scan.generated_files` applied to the findings of one file
+pub fn apply(mode: GeneratedFiles, reason: Option<&str>, issues: Vec<Issue>) -> Vec<Issue> {
+    let Some(reason) = reason else {
+        return issues;
+    };
+    match mode {
+        GeneratedFiles::Scan => issues,
+        GeneratedFiles::Skip => Vec::new(),
+        GeneratedFiles::Downgrade => issues
+            .into_iter()
+            .map(|mut issue| {
+                issue.severity = Severity::Info;
+                let note = format!("in a generated file ({})", reason);
+                issue.description = Some(match issue.description.take() {
+                    Some(description) => format!("{}; {}", description, note),
+                    None => note,
+                });
+                issue
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundles_vendored_code_and_markers_are_generated() {
+        assert_eq!(
+            generated_reason("public/app.min.js", "x"),
+            Some("minified or bundled file name")
+        );
+        assert_eq!(
+            generated_reason("static/vendor/jquery.js", "x"),
+            Some("vendored directory")
+        );
+        assert_eq!(
+            generated_reason(
+                "src/api.pb.go",
+                "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n"
+            ),
+            Some("generated file marker")
+        );
+        assert_eq!(
+            generated_reason(
+                "dist2/main.js",
+                "var a=1;\n//# sourceMappingURL=main.js.map\n"
+            ),
+            Some("source map comment")
+        );
+        let blob = format!("const data = \"{}\";\n", "QUJD".repeat(400));
+        assert_eq!(
+            generated_reason("src/assets.js", &blob),
+            Some("very long lines")
+        );
+        assert_eq!(generated_reason("src/vendor.ts", "export {}\n"), None);
+        assert_eq!(generated_reason("src/config.ts", "const a = 1;\n"), None);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::config::Config;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs as fs_utils;
+use crate::utils::git as git_utils;
+use crate::utils::glob;
+use regex::Regex;
+use std::collections::BTreeSet;
+
+const ATTRIBUTES_FILE: &str = ".gitattributes";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrState {
+    Set,
+    Unset,
+    Unspecified,
+    Value(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct AttrRule {
+    pub pattern: String,
+    pub attrs: Vec<(String, AttrState)>,
+    pub line: usize,
+    matcher: Option<Regex>,
+}
+
+impl AttrRule {
+    pub fn matches(&self, path: &str) -> bool {
+        self.matcher
+            .as_ref()
+            .is_some_and(|matcher| matcher.is_match(path))
+    }
+
+    fn state(&self, name: &str) -> Option<&AttrState> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, state)| state)
+    }
+}
+
+pub fn parse_attributes(content: &str) -> Vec<AttrRule> {
+    let mut rules = Vec::new();
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        // macro definitions and quoted patterns are rare enough to leave alone
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with('[')
+            || line.starts_with('"')
+        {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let Some(pattern) = parts.next() else {
+            continue;
+        };
+        let attrs = parts
+            .map(|attr| {
+                if let Some(name) = attr.strip_prefix('-') {
+                    (name.to_string(), AttrState::Unset)
+                } else if let Some(name) = attr.strip_prefix('!') {
+                    (name.to_string(), AttrState::Unspecified)
+                } else if let Some((name, value)) = attr.split_once('=') {
+                    (name.to_string(), AttrState::Value(value.to_string()))
+                } else {
+                    (attr.to_string(), AttrState::Set)
+                }
+            })
+            .collect();
+
+        rules.push(AttrRule {
+            pattern: pattern.to_string(),
+            attrs,
+            line: idx + 1,
+            matcher: pattern_to_regex(pattern),
+        });
+    }
+
+    rules
+}
+
+// later lines override earlier ones, attribute by attribute
+pub fn attribute<'a>(rules: &'a [AttrRule], path: &str, name: &str) -> Option<&'a AttrState> {
+    rules
+        .iter()
+        .rev()
+        .filter(|rule| rule.matches(path))
+        .find_map(|rule| rule.state(name))
+}
+
+pub fn run_attribute_checks(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let Some(repo) = &ctx.git_repo else {
+        return issues;
+    };
+    let Ok(content) = fs_utils::read_text(&ctx.repo_root.join(ATTRIBUTES_FILE)) else {
+        return issues;
+    };
+    let attr_rules = parse_attributes(&content);
+    let tracked = git_utils::tracked_paths(repo).unwrap_or_default();
+
+    let filters = attr_rules
+        .iter()
+        .filter_map(|rule| match rule.state("filter") {
+            Some(AttrState::Value(filter)) => Some(filter.as_str()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    if !filters.is_empty() {
+        let secret_paths = matchers(&cfg.git.encrypted_paths);
+        for path in tracked.iter().filter(|path| {
+            secret_paths.iter().any(|matcher| matcher.is_match(path)) && !is_example_file(path, cfg)
+        }) {
+            if matches!(
+                attribute(&attr_rules, path, "filter"),
+                Some(AttrState::Value(_))
+            ) {
+                continue;
+            }
+            issues.push(
+                Issue::from_rule(
+                    rules::GIT_SECRET_FILE_UNFILTERED,
+                    Severity::Warning,
+                    format!("{} is committed without an encryption filter", path),
+                    format!(
+                        "add `{} filter={}` to .gitattributes and re-add the file",
+                        path,
+                        filters.iter().next().expect("not empty")
+                    ),
+                )
+                .with_file(path.clone())
+                .with_description(format!(
+                    "this repo encrypts files with filter={} but this one is stored in plain text",
+                    filters
+                        .iter()
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", filter=")
+                )),
+            );
+        }
+    }
+
+    for dir in &cfg.git.export_ignore_dirs {
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        let shipped = tracked
+            .iter()
+            .filter(|path| path.starts_with(&prefix))
+            .filter(|path| attribute(&attr_rules, path, "export-ignore") != Some(&AttrState::Set))
+            .count();
+        if shipped == 0 {
+            continue;
+        }
+        issues.push(
+            Issue::from_rule(
+                rules::GIT_TOOLING_NOT_EXPORT_IGNORED,
+                Severity::Info,
+                format!("{} is not export-ignored", dir),
+                format!("add `/{}/** export-ignore` to .gitattributes", dir),
+            )
+            .with_file(dir.clone())
+            .with_description(format!(
+                "{} tracked file(s) would ship in `git archive` and release tarballs",
+                shipped
+            )),
+        );
+    }
+
+    let protected = matchers(&cfg.git.merge_protected_paths);
+    for rule in attr_rules
+        .iter()
+        .filter(|rule| is_ours(rule.state("merge")))
+    {
+        let affected = tracked
+            .iter()
+            .filter(|path| rule.matches(path))
+            .filter(|path| protected.iter().any(|matcher| matcher.is_match(path)))
+            .filter(|path| is_ours(attribute(&attr_rules, path, "merge")))
+            .cloned()
+            .collect::<Vec<_>>();
+        if affected.is_empty() {
+            continue;
+        }
+        issues.push(
+            Issue::from_rule(
+                rules::GIT_MERGE_OURS_ON_PROTECTED,
+                Severity::Warning,
+                format!("merge=ours driver on security-critical {}", rule.pattern),
+                "drop the merge=ours attribute so incoming changes to these files are merged and reviewed",
+            )
+            .with_file(ATTRIBUTES_FILE)
+            .with_line(rule.line)
+            .with_description(format!(
+                "merges silently keep the local copy of: {}",
+                affected.join(", ")
+            )),
+        );
+    }
+
+    issues
+}
+
+fn is_ours(state: Option<&AttrState>) -> bool {
+    matches!(state, Some(AttrState::Value(driver)) if driver == "ours")
+}
+
+fn matchers(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| pattern_to_regex(pattern))
+        .collect()
+}
+
+fn is_example_file(path: &str, cfg: &Config) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    cfg.env
+        .example_files
+        .iter()
+        .any(|example| example.eq_ignore_ascii_case(name))
+}
+
+// attributes apply to the matched paths themselves, never to files below a matched directory
+fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let body = pattern.trim_start_matches('/');
+    if body.is_empty() || body.ends_with('/') {
+        return None;
+    }
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    regex.push_str(&glob::glob_body(body));
+    regex.push('$');
+
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_lines_override_attributes_per_path() {
+        let rules = parse_attributes(
+            "# crypt\n*.env filter=git-crypt diff=git-crypt\n.env.example -filter\n/.github/** export-ignore\nCODEOWNERS merge=ours\n",
+        );
+        assert_eq!(rules.len(), 4);
+
+        assert_eq!(
+            attribute(&rules, "config/prod.env", "filter"),
+            Some(&AttrState::Value("git-crypt".to_string()))
+        );
+        assert_eq!(
+            attribute(&rules, ".env.example", "filter"),
+            Some(&AttrState::Unset)
+        );
+        assert_eq!(
+            attribute(&rules, ".github/workflows/ci.yml", "export-ignore"),
+            Some(&AttrState::Set)
+        );
+        assert_eq!(attribute(&rules, "src/.github/x", "export-ignore"), None);
+        assert_eq!(
+            attribute(&rules, ".github/CODEOWNERS", "merge"),
+            Some(&AttrState::Value("ours".to_string()))
+        );
+        assert_eq!(attribute(&rules, "README.md", "filter"), None);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::config::Config;
+use crate::core::{Category, Issue, RepoContext, Severity, rules, scanner};
+use crate::utils::github::GithubClient;
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+#[derive(Debug)]
+pub enum Protection {
+    Missing,
+    Unreadable,
+    Present(Value),
+    // only rulesets cover the branch; they hold the active rules, not a protection object
+    Rulesets(Vec<Value>),
+}
+
+pub fn run_github_audit(
+    ctx: &RepoContext,
+    cfg: &Config,
+    client: &GithubClient,
+    slug: &str,
+) -> Result<Vec<Issue>> {
+    let repo = client.get(&format!("/repos/{}", slug))?.with_context(|| {
+        format!(
+            "GitHub repository {} not found or not visible to this token",
+            slug
+        )
+    })?;
+    let branch = repo
+        .get("default_branch")
+        .and_then(Value::as_str)
+        .unwrap_or("main")
+        .to_string();
+
+    // the protection endpoint needs admin rights; a 403 means "can't tell", not "unprotected".
+    // it also answers 404 for a branch that only rulesets protect, so those are asked
+    // about before the branch is called unprotected
+    let protection = match client.get(&format!("/repos/{}/branches/{}/protection", slug, branch)) {
+        Ok(Some(protection)) => Protection::Present(protection),
+        Ok(None) => match client.get(&format!("/repos/{}/rules/branches/{}", slug, branch)) {
+            Ok(Some(Value::Array(rules))) if !rules.is_empty() => Protection::Rulesets(rules),
+            Ok(_) => Protection::Missing,
+            Err(_) => Protection::Unreadable,
+        },
+        Err(_) => Protection::Unreadable,
+    };
+
+    let is_public = repo.get("private").and_then(Value::as_bool) == Some(false);
+    let local_secrets = if is_public {
+        scanner::scan_secrets(ctx, cfg)
+            .iter()
+            .filter(|issue| {
+                issue.category == Category::Secrets
+                    && matches!(issue.severity, Severity::Error | Severity::Warning)
+            })
+            .count()
+    } else {
+        0
+    };
+
+    Ok(audit_repo(&repo, &branch, &protection, local_secrets))
+}
+
+pub fn audit_repo(
+    repo: &Value,
+    branch: &str,
+    protection: &Protection,
+    local_secrets: usize,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    match protection {
+        Protection::Missing => issues.push(Issue::from_rule(
+            rules::GITHUB_BRANCH_PROTECTION,
+            Severity::Error,
+            format!("default branch `{}` has no branch protection", branch),
+            "add a branch protection rule or ruleset requiring pull requests and status checks",
+        )),
+        Protection::Unreadable => issues.push(Issue::from_rule(
+            rules::GITHUB_SETTINGS_UNAVAILABLE,
+            Severity::Info,
+            format!("branch protection for `{}` could not be read", branch),
+            "rerun with a token that has admin access to the repository",
+        )),
+        Protection::Present(protection) => {
+            issues.push(Issue::from_rule(
+                rules::GITHUB_BRANCH_PROTECTION,
+                Severity::Pass,
+                format!("default branch `{}` is protected", branch),
+                "no action needed",
+            ));
+            let force_pushes = protection
+                .pointer("/allow_force_pushes/enabled")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            issues.push(if force_pushes {
+                Issue::from_rule(
+                    rules::GITHUB_FORCE_PUSH,
+                    Severity::Error,
+                    format!("force pushes are allowed on `{}`", branch),
+                    "disable \"Allow force pushes\" in the branch protection rule",
+                )
+            } else {
+                Issue::from_rule(
+                    rules::GITHUB_FORCE_PUSH,
+                    Severity::Pass,
+                    format!("force pushes are blocked on `{}`", branch),
+                    "no action needed",
+                )
+            });
+        }
+        Protection::Rulesets(rules) => {
+            issues.push(Issue::from_rule(
+                rules::GITHUB_BRANCH_PROTECTION,
+                Severity::Pass,
+                format!("default branch `{}` is protected by a ruleset", branch),
+                "no action needed",
+            ));
+            let blocks_force_pushes = rules
+                .iter()
+                .any(|rule| rule.get("type").and_then(Value::as_str) == Some("non_fast_forward"));
+            issues.push(if blocks_force_pushes {
+                Issue::from_rule(
+                    rules::GITHUB_FORCE_PUSH,
+                    Severity::Pass,
+                    format!("force pushes are blocked on `{}`", branch),
+                    "no action needed",
+                )
+            } else {
+                Issue::from_rule(
+                    rules::GITHUB_FORCE_PUSH,
+                    Severity::Error,
+                    format!("force pushes are allowed on `{}`", branch),
+                    "enable \"Block force pushes\" in a ruleset that targets the branch",
+                )
+            });
+        }
+    }
+
+    let security = repo.get("security_and_analysis");
+    for (feature, rule, label, remediation) in [
+        (
+            "secret_scanning",
+            rules::GITHUB_SECRET_SCANNING,
+            "secret scanning",
+            "enable secret scanning under Settings > Code security",
+        ),
+        (
+            "secret_scanning_push_protection",
+            rules::GITHUB_PUSH_PROTECTION,
+            "secret scanning push protection",
+            "enable push protection so commits containing secrets are rejected",
+        ),
+    ] {
+        let status = security
+            .and_then(|security| security.pointer(&format!("/{}/status", feature)))
+            .and_then(Value::as_str);
+        issues.push(match status {
+            Some("enabled") => Issue::from_rule(
+                rule,
+                Severity::Pass,
+                format!("{} is enabled", label),
+                "no action needed",
+            ),
+            Some(_) => Issue::from_rule(
+                rule,
+                Severity::Warning,
+                format!("{} is disabled", label),
+                remediation,
+            ),
+            None => Issue::from_rule(
+                rules::GITHUB_SETTINGS_UNAVAILABLE,
+                Severity::Info,
+                format!("{} status could not be read", label),
+                "rerun with a token that has admin access to the repository",
+            ),
+        });
+    }
+
+    if local_secrets > 0 {
+        issues.push(
+            Issue::from_rule(
+                rules::GITHUB_PUBLIC_WITH_SECRETS,
+                Severity::Error,
+                "public repository contains detected secrets",
+                "rotate the exposed credentials now, then purge them from history or make the repository private",
+            )
+            .with_description(format!(
+                "{} secret finding(s); run `devguard scan secrets` for details",
+                local_secrets
+            )),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flags_missing_protection_and_disabled_scanning() {
+        let repo = json!({
+            "private": false,
+            "default_branch": "main",
+            "security_and_analysis": {
+                "secret_scanning": {"status": "disabled"},
+                "secret_scanning_push_protection": {"status": "enabled"}
+            }
+        });
+        let issues = audit_repo(&repo, "main", &Protection::Missing, 2);
+        let find = |code: &str| {
+            issues
+                .iter()
+                .find(|issue| issue.code == code)
+                .map(|i| i.severity)
+        };
+
+        assert_eq!(find("DG_GH_001"), Some(Severity::Error));
+        assert_eq!(find("DG_GH_003"), Some(Severity::Warning));
+        assert_eq!(find("DG_GH_004"), Some(Severity::Pass));
+        assert_eq!(find("DG_GH_005"), Some(Severity::Error));
+    }
+
+    #[test]
+    fn flags_force_push_on_protected_branch() {
+        let protection = Protection::Present(json!({"allow_force_pushes": {"enabled": true}}));
+        let issues = audit_repo(&json!({"private": true}), "main", &protection, 0);
+
+        assert!(
+            issues
+                .iter()
+                .any(|issue| issue.code == "DG_GH_002" && issue.severity == Severity::Error)
+        );
+        assert!(issues.iter().any(|issue| issue.code == "DG_GH_006"));
+    }
+
+    #[test]
+    fn rulesets_count_as_protection() {
+        let protection = Protection::Rulesets(vec![
+            json!({"type": "pull_request", "ruleset_id": 1}),
+            json!({"type": "non_fast_forward", "ruleset_id": 1}),
+        ]);
+        let issues = audit_repo(&json!({"private": true}), "main", &protection, 0);
+        let find = |code: &str| {
+            issues
+                .iter()
+                .find(|issue| issue.code == code)
+                .map(|i| i.severity)
+        };
+
+        assert_eq!(find("DG_GH_001"), Some(Severity::Pass));
+        assert_eq!(find("DG_GH_002"), Some(Severity::Pass));
+    }
+}
@@ -0,0 +1,262 @@
+use crate::config::Config;
+use crate::core::{Issue, RemediationAction, RepoContext, Severity, rules};
+use crate::utils::fs as fs_utils;
+use crate::utils::glob;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+
+const IGNORE_FILE: &str = ".gitignore";
+const INFO_EXCLUDE: &str = ".git/info/exclude";
+
+// one pattern line from .git/info/exclude or a .gitignore; `base` is the directory the
+// file sits in, with a trailing slash, or empty for the repo root
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    pub source: String,
+    pub line: usize,
+    pub raw: String,
+    pub negated: bool,
+    base: String,
+    dir_only: bool,
+    matcher: Regex,
+}
+
+impl IgnoreRule {
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        path.strip_prefix(&self.base)
+            .is_some_and(|rel| self.matcher.is_match(rel))
+    }
+}
+
+pub fn parse_ignore(source: &str, base: &str, content: &str) -> Vec<IgnoreRule> {
+    let mut rules = Vec::new();
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+        let anchored = pattern.contains('/');
+        let body = pattern.trim_start_matches('/');
+        if body.is_empty() {
+            continue;
+        }
+        let prefix = if anchored { "^" } else { "^(?:.*/)?" };
+        let Ok(matcher) = Regex::new(&format!("{}{}$", prefix, glob::glob_body(body))) else {
+            continue;
+        };
+
+        rules.push(IgnoreRule {
+            source: source.to_string(),
+            line: idx + 1,
+            raw: line.to_string(),
+            negated,
+            base: base.to_string(),
+            dir_only,
+            matcher,
+        });
+    }
+
+    rules
+}
+
+// the rule git settles on for a file: a parent directory that is ignored cannot be
+// re-included from below, otherwise the last matching line wins. `rules` is in precedence
+// order, lowest first
+pub fn deciding_rule<'a>(rules: &'a [IgnoreRule], path: &str) -> Option<&'a IgnoreRule> {
+    let mut dir = String::new();
+    for segment in path.split('/').collect::<Vec<_>>().split_last()?.1 {
+        dir.push_str(segment);
+        if let Some(rule) = last_match(rules, &dir, true).filter(|rule| !rule.negated) {
+            return Some(rule);
+        }
+        dir.push('/');
+    }
+    last_match(rules, path, false)
+}
+
+fn last_match<'a>(rules: &'a [IgnoreRule], path: &str, is_dir: bool) -> Option<&'a IgnoreRule> {
+    rules.iter().rev().find(|rule| rule.matches(path, is_dir))
+}
+
+pub fn run_ignore_checks(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let forbidden = cfg
+        .env
+        .forbid_commit
+        .iter()
+        .map(|name| name.to_ascii_lowercase())
+        .collect::<BTreeSet<_>>();
+    if forbidden.is_empty() {
+        return issues;
+    }
+
+    // the forbidden names are checked in every directory that has its own .gitignore or
+    // already holds one of them
+    let mut ignore_files = Vec::new();
+    let mut dirs = BTreeSet::from([String::new()]);
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "gitignore check") {
+        let rel = fs_utils::relative_path(&ctx.repo_root, entry.path());
+        let name = entry.file_name().to_string_lossy();
+        let dir = rel.strip_suffix(name.as_ref()).unwrap_or("").to_string();
+        if name == IGNORE_FILE {
+            ignore_files.push(rel);
+            dirs.insert(dir);
+        } else if forbidden.contains(&name.to_ascii_lowercase()) {
+            dirs.insert(dir);
+        }
+    }
+    ignore_files.sort_by_key(|rel| (rel.matches('/').count(), rel.clone()));
+
+    let mut ignore_rules = fs_utils::read_text(&ctx.repo_root.join(INFO_EXCLUDE))
+        .map(|content| parse_ignore(INFO_EXCLUDE, "", &content))
+        .unwrap_or_default();
+    for rel in &ignore_files {
+        let Ok(content) = fs_utils::read_text(&ctx.repo_root.join(rel)) else {
+            continue;
+        };
+        let base = rel.strip_suffix(IGNORE_FILE).unwrap_or("");
+        ignore_rules.extend(parse_ignore(rel, base, &content));
+    }
+
+    let candidates = dirs
+        .iter()
+        .flat_map(|dir| {
+            cfg.env
+                .forbid_commit
+                .iter()
+                .map(move |name| format!("{}{}", dir, name))
+        })
+        .collect::<Vec<_>>();
+    let mut protecting = BTreeSet::new();
+    let mut negations = BTreeMap::<(&str, usize), (&IgnoreRule, Vec<&str>)>::new();
+    let mut uncovered = BTreeMap::<&str, Vec<&str>>::new();
+    for path in &candidates {
+        match deciding_rule(&ignore_rules, path) {
+            Some(rule) if !rule.negated => {
+                protecting.insert((rule.source.as_str(), rule.line));
+            }
+            Some(rule)
+                if ignore_rules
+                    .iter()
+                    .any(|earlier| !earlier.negated && earlier.matches(path, false)) =>
+            {
+                negations
+                    .entry((rule.source.as_str(), rule.line))
+                    .or_insert_with(|| (rule, Vec::new()))
+                    .1
+                    .push(path);
+            }
+            _ => {
+                let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+                let nearest = ignore_files
+                    .iter()
+                    .rev()
+                    .find(|rel| {
+                        let base = rel.strip_suffix(IGNORE_FILE).unwrap_or("");
+                        format!("{}/", dir).starts_with(base)
+                    })
+                    .map_or(IGNORE_FILE, String::as_str);
+                uncovered.entry(nearest).or_default().push(path);
+            }
+        }
+    }
+
+    for (rule, paths) in negations.into_values() {
+        issues.push(
+            Issue::from_rule(
+                rules::GIT_IGNORE_NEGATION_EXPOSES,
+                Severity::Warning,
+                format!("`{}` re-includes {}", rule.raw, paths.join(", ")),
+                format!(
+                    "delete the `{}` line or narrow it to example files such as .env.example",
+                    rule.raw
+                ),
+            )
+            .with_file(rule.source.clone())
+            .with_line(rule.line)
+            .with_description(format!(
+                "env.forbid_commit names these files, and an earlier pattern ignored them until this negation made them trackable again: {}",
+                paths.join(", ")
+            )),
+        );
+    }
+
+    // a repo with no env patterns at all is a different problem; this is about rules that
+    // were written and miss
+    if protecting.is_empty() {
+        return issues;
+    }
+    for (source, paths) in uncovered {
+        let base = source.strip_suffix(IGNORE_FILE).unwrap_or("");
+        let narrow = ignore_rules
+            .iter()
+            .filter(|rule| protecting.contains(&(rule.source.as_str(), rule.line)))
+            .filter(|rule| base.starts_with(&rule.base))
+            .map(|rule| format!("`{}` ({}:{})", rule.raw, rule.source, rule.line))
+            .collect::<Vec<_>>();
+        let mut issue = Issue::from_rule(
+            rules::GIT_IGNORE_PATTERN_TOO_NARROW,
+            Severity::Warning,
+            format!("{} leaves {} trackable", source, paths.join(", ")),
+            "add unanchored patterns for these files, such as `.env` and `.env.*`, and re-include examples with `!.env.example`",
+        )
+        .with_file(source)
+        .with_description(if narrow.is_empty() {
+            "env.forbid_commit files elsewhere in the repo are ignored, but not these".to_string()
+        } else {
+            format!(
+                "the patterns here only cover other forbidden files: {}",
+                narrow.join(", ")
+            )
+        });
+        for path in paths {
+            issue = issue.with_action(RemediationAction::GitignoreAdd {
+                path: path.to_string(),
+            });
+        }
+        issues.push(issue);
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negations_and_ignored_parents_decide_like_git() {
+        let mut rules = parse_ignore(".gitignore", "", ".env*\n!.env.production\nsecrets/\n");
+        rules.extend(parse_ignore("secrets/.gitignore", "secrets/", "!.env\n"));
+        rules.extend(parse_ignore(
+            "apps/web/.gitignore",
+            "apps/web/",
+            "/.env.local\n",
+        ));
+
+        let decided = |path| deciding_rule(&rules, path).map(|rule| (rule.negated, rule.line));
+        assert_eq!(decided(".env"), Some((false, 1)));
+        assert_eq!(decided("apps/api/.env"), Some((false, 1)));
+        assert_eq!(decided(".env.production"), Some((true, 2)));
+        assert_eq!(decided("secrets/.env"), Some((false, 3)));
+        assert_eq!(decided("apps/web/.env.local"), Some((false, 1)));
+        assert_eq!(decided("apps/web/nested/.env.local"), Some((false, 1)));
+        assert_eq!(decided("README.md"), None);
+
+        let anchored = parse_ignore(".gitignore", "", "/.env\nconfig/*.key\n");
+        assert!(deciding_rule(&anchored, ".env").is_some());
+        assert!(deciding_rule(&anchored, "apps/web/.env").is_none());
+        assert!(deciding_rule(&anchored, "config/prod.key").is_some());
+        assert!(deciding_rule(&anchored, "apps/config/prod.key").is_none());
+    }
+}
@@ -1,9 +1,10 @@
 use crate::config::FailOn;
+use crate::utils::date::format_timestamp;
 use colored::Colorize;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
@@ -58,9 +59,18 @@ impl Severity {
             Self::Pass => None,
         }
     }
+
+    pub fn cyclonedx_severity(self) -> Option<&'static str> {
+        match self {
+            Self::Error => Some("high"),
+            Self::Warning => Some("medium"),
+            Self::Info => Some("info"),
+            Self::Pass => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum Category {
     Secrets,
@@ -69,16 +79,22 @@ pub enum Category {
     Supabase,
     Vercel,
     Stripe,
+    Release,
+    Supply,
+    Tooling,
 }
 
 impl Category {
-    pub const ALL: [Self; 6] = [
+    pub const ALL: [Self; 9] = [
         Self::Secrets,
         Self::Env,
         Self::Git,
         Self::Supabase,
         Self::Vercel,
         Self::Stripe,
+        Self::Release,
+        Self::Supply,
+        Self::Tooling,
     ];
 
     pub fn label(self) -> &'static str {
@@ -89,6 +105,9 @@ impl Category {
             Self::Supabase => "Supabase",
             Self::Vercel => "Vercel",
             Self::Stripe => "Stripe",
+            Self::Release => "Release",
+            Self::Supply => "Supply chain",
+            Self::Tooling => "Tooling",
         }
     }
 
@@ -100,6 +119,9 @@ impl Category {
             Self::Supabase => "supabase",
             Self::Vercel => "vercel",
             Self::Stripe => "stripe",
+            Self::Release => "release",
+            Self::Supply => "supply",
+            Self::Tooling => "tooling",
         }
     }
 }
@@ -115,6 +137,9 @@ pub struct RuleSpec {
     pub code: &'static str,
     pub rule_title: &'static str,
     pub category: Category,
+    pub url: Option<&'static str>,
+    // the option that switches this check off, when there is one besides `[rules]`
+    pub config_key: Option<&'static str>,
 }
 
 impl RuleSpec {
@@ -123,49 +148,149 @@ impl RuleSpec {
             code,
             rule_title,
             category,
+            url: None,
+            config_key: None,
         }
     }
+
+    pub const fn with_url(mut self, url: &'static str) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    pub const fn with_config_key(mut self, key: &'static str) -> Self {
+        self.config_key = Some(key);
+        self
+    }
+
+    pub fn config_key(&self) -> String {
+        self.config_key
+            .map_or_else(|| format!("rules.{}", self.code), str::to_string)
+    }
 }
 
 pub mod rules {
     use super::{Category, RuleSpec};
 
+    const GITHUB_REMOVE_SENSITIVE_DATA: &str = "https://docs.github.com/en/authentication/keeping-your-account-and-data-secure/removing-sensitive-data-from-a-repository";
+    const GITHUB_BRANCH_PROTECTION_DOCS: &str = "https://docs.github.com/en/repositories/configuring-branches-and-merges-in-your-repository/managing-protected-branches/about-protected-branches";
+    const GITHUB_SECRET_SCANNING_DOCS: &str = "https://docs.github.com/en/code-security/secret-scanning/introduction/about-secret-scanning";
+    const STRIPE_KEYS_DOCS: &str = "https://docs.stripe.com/keys";
+    const STRIPE_WEBHOOK_DOCS: &str = "https://docs.stripe.com/webhooks#verify-events";
+    const SUPABASE_API_KEYS_DOCS: &str = "https://supabase.com/docs/guides/api/api-keys";
+    const SUPABASE_STORAGE_DOCS: &str =
+        "https://supabase.com/docs/guides/storage/security/access-control";
+    const VERCEL_ENV_DOCS: &str = "https://vercel.com/docs/projects/environment-variables";
+
     pub const SECRET_STRIPE_LIVE_PATTERN: RuleSpec = RuleSpec::new(
         "DG_SEC_001",
         "Committed Stripe live secret detected",
         Category::Secrets,
-    );
+    )
+    .with_url(STRIPE_KEYS_DOCS);
     pub const SECRET_STRIPE_TEST_PATTERN: RuleSpec = RuleSpec::new(
         "DG_SEC_002",
         "Committed Stripe test secret detected",
         Category::Secrets,
-    );
+    )
+    .with_url(STRIPE_KEYS_DOCS);
     pub const SECRET_VERCEL_TOKEN: RuleSpec = RuleSpec::new(
         "DG_SEC_003",
         "Committed Vercel token detected",
         Category::Secrets,
-    );
+    )
+    .with_url("https://vercel.com/docs/rest-api#creating-an-access-token");
     pub const SECRET_AWS_ACCESS_KEY: RuleSpec = RuleSpec::new(
         "DG_SEC_004",
         "Committed AWS access key detected",
         Category::Secrets,
-    );
+    )
+    .with_url("https://docs.aws.amazon.com/IAM/latest/UserGuide/id_credentials_access-keys.html");
     pub const SECRET_PRIVATE_KEY: RuleSpec = RuleSpec::new(
         "DG_SEC_005",
         "Committed private key material detected",
         Category::Secrets,
-    );
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA);
     pub const SECRET_SUPABASE_JWT: RuleSpec = RuleSpec::new(
         "DG_SEC_006",
         "Committed Supabase JWT-like secret detected",
         Category::Secrets,
-    );
+    )
+    .with_url(SUPABASE_API_KEYS_DOCS);
+    pub const SECRET_KUBECONFIG: RuleSpec = RuleSpec::new(
+        "DG_SEC_007",
+        "Committed kubeconfig with cluster credentials",
+        Category::Secrets,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA);
+    pub const SECRET_DOCKER_AUTHS: RuleSpec = RuleSpec::new(
+        "DG_SEC_008",
+        "Committed Docker registry credentials",
+        Category::Secrets,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA);
+    pub const SECRET_GCLOUD_CREDENTIALS: RuleSpec = RuleSpec::new(
+        "DG_SEC_009",
+        "Committed Google Cloud credential file",
+        Category::Secrets,
+    )
+    .with_url("https://cloud.google.com/docs/authentication/application-default-credentials");
+    pub const SECRET_IN_DOCUMENT: RuleSpec = RuleSpec::new(
+        "DG_SEC_011",
+        "Credentials committed in an office document",
+        Category::Secrets,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA)
+    .with_config_key("scan.documents.enabled");
+    pub const SECRET_STRUCTURED_CONFIG_VALUE: RuleSpec = RuleSpec::new(
+        "DG_SEC_010",
+        "Secret value committed in structured config",
+        Category::Secrets,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA)
+    .with_config_key("scan.structured");
+    pub const SECRET_TOOL_CONFIG: RuleSpec = RuleSpec::new(
+        "DG_SEC_014",
+        "Credential in committed IDE or API client config",
+        Category::Secrets,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA)
+    .with_config_key("scan.tool_configs");
+    pub const DISCLOSURE_PRIVATE_ADDRESS: RuleSpec = RuleSpec::new(
+        "DG_SEC_015",
+        "Private network address committed",
+        Category::Secrets,
+    )
+    .with_config_key("scan.disclosure.enabled");
+    pub const DISCLOSURE_INTERNAL_HOST: RuleSpec = RuleSpec::new(
+        "DG_SEC_016",
+        "Internal hostname committed",
+        Category::Secrets,
+    )
+    .with_config_key("scan.disclosure.enabled");
+    pub const DISCLOSURE_METADATA_ENDPOINT: RuleSpec = RuleSpec::new(
+        "DG_SEC_017",
+        "Cloud metadata endpoint referenced",
+        Category::Secrets,
+    )
+    .with_config_key("scan.disclosure.enabled");
+    pub const SECRET_IN_STASH: RuleSpec =
+        RuleSpec::new("DG_SEC_012", "Secret in a git stash", Category::Secrets)
+            .with_config_key("scan.local_history.enabled");
+    pub const SECRET_IN_REFLOG_COMMIT: RuleSpec = RuleSpec::new(
+        "DG_SEC_013",
+        "Secret in a commit only the reflog references",
+        Category::Secrets,
+    )
+    .with_config_key("scan.local_history.enabled");
 
     pub const ENV_REQUIRED_VAR_MISSING: RuleSpec = RuleSpec::new(
         "DG_ENV_001",
         "Required environment variable is missing",
         Category::Env,
-    );
+    )
+    .with_config_key("env.required");
     pub const ENV_EXAMPLE_MISSING_KEY: RuleSpec = RuleSpec::new(
         "DG_ENV_002",
         "Environment example file is missing an active key",
@@ -180,12 +305,95 @@ pub mod rules {
         "DG_ENV_004",
         "Forbidden environment file appears tracked",
         Category::Env,
-    );
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA)
+    .with_config_key("env.forbid_commit");
     pub const ENV_FORBIDDEN_FILE_PRESENT: RuleSpec = RuleSpec::new(
         "DG_ENV_005",
         "Forbidden environment file exists and should be secured",
         Category::Env,
-    );
+    )
+    .with_config_key("env.forbid_commit");
+    pub const ENV_RENAMED_DOTENV_TRACKED: RuleSpec = RuleSpec::new(
+        "DG_ENV_006",
+        "Tracked file looks like a dotenv file with secrets",
+        Category::Env,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA)
+    .with_config_key("env.detect_by_content");
+    pub const ENV_EXAMPLE_OUTDATED: RuleSpec = RuleSpec::new(
+        "DG_ENV_007",
+        "Env example file has not kept up with dotenv changes",
+        Category::Env,
+    )
+    .with_config_key("staleness.enabled");
+    pub const ENV_SHELL_FILE_SECRET: RuleSpec = RuleSpec::new(
+        "DG_ENV_008",
+        "direnv or shell rc file exports a secret value",
+        Category::Env,
+    )
+    .with_url("https://direnv.net/man/direnv-stdlib.1.html")
+    .with_config_key("env.direnv.enabled");
+    pub const ENV_ENVRC_TRACKED: RuleSpec =
+        RuleSpec::new("DG_ENV_009", "direnv .envrc file is tracked", Category::Env)
+            .with_url("https://direnv.net/man/direnv-stdlib.1.html")
+            .with_config_key("env.direnv.enabled");
+    pub const ENV_ENVRC_FORBIDDEN_SOURCE: RuleSpec = RuleSpec::new(
+        "DG_ENV_010",
+        "direnv .envrc loads a forbidden env file",
+        Category::Env,
+    )
+    .with_config_key("env.direnv.warn_forbidden_sources");
+    pub const ENV_MISE_SECRET: RuleSpec = RuleSpec::new(
+        "DG_ENV_011",
+        "mise config env block holds a secret value",
+        Category::Env,
+    )
+    .with_url("https://mise.jdx.dev/environments/secrets/")
+    .with_config_key("env.mise.enabled");
+    pub const ENV_EXAMPLE_REAL_VALUE: RuleSpec = RuleSpec::new(
+        "DG_ENV_012",
+        "Env example file holds a real secret copied from a dotenv file",
+        Category::Env,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA);
+
+    pub const ENV_DOTENV_ENCODING: RuleSpec = RuleSpec::new(
+        "DG_ENV_013",
+        "Dotenv file has a byte order mark or non-UTF-8 encoding",
+        Category::Env,
+    )
+    .with_config_key("env.format.enabled");
+    pub const ENV_DOTENV_CRLF: RuleSpec = RuleSpec::new(
+        "DG_ENV_014",
+        "Dotenv file uses CRLF line endings",
+        Category::Env,
+    )
+    .with_config_key("env.format.allow_crlf");
+    pub const ENV_DOTENV_HIDDEN_KEY_CHAR: RuleSpec = RuleSpec::new(
+        "DG_ENV_015",
+        "Dotenv key contains an invisible or non-ASCII character",
+        Category::Env,
+    )
+    .with_config_key("env.format.enabled");
+    pub const ENV_MANAGER_NOT_FOUND: RuleSpec = RuleSpec::new(
+        "DG_ENV_016",
+        "Env var is declared as managed by a secret manager the repo does not use",
+        Category::Env,
+    )
+    .with_config_key("env.managed");
+    pub const ENV_REQUIRED_VAR_EMPTY: RuleSpec = RuleSpec::new(
+        "DG_ENV_017",
+        "Required environment variable has no real value",
+        Category::Env,
+    )
+    .with_config_key("env.required_non_empty");
+    pub const ENV_REQUIRED_VAR_PRESENT: RuleSpec = RuleSpec::new(
+        "DG_ENV_018",
+        "Required environment variable is set",
+        Category::Env,
+    )
+    .with_config_key("env.check_process_env");
 
     pub const GIT_NOT_A_REPO: RuleSpec = RuleSpec::new(
         "DG_GIT_001",
@@ -204,14 +412,92 @@ pub mod rules {
         "DG_GIT_006",
         "Repository is in detached HEAD state",
         Category::Git,
-    );
+    )
+    .with_url("https://git-scm.com/docs/git-checkout#_detached_head");
     pub const GIT_HEAD_UNAVAILABLE: RuleSpec =
         RuleSpec::new("DG_GIT_007", "Unable to resolve git HEAD", Category::Git);
     pub const GIT_LARGE_FILE: RuleSpec = RuleSpec::new(
         "DG_GIT_008",
         "Large repository file detected",
         Category::Git,
+    )
+    .with_url("https://docs.github.com/en/repositories/working-with-files/managing-large-files/about-git-large-file-storage");
+    pub const GIT_SYMLINK_OUTSIDE_REPO: RuleSpec = RuleSpec::new(
+        "DG_GIT_009",
+        "Symlink points outside the repository",
+        Category::Git,
     );
+    pub const GIT_CODEOWNERS_MISSING: RuleSpec =
+        RuleSpec::new("DG_GIT_010", "CODEOWNERS file is missing", Category::Git).with_url(
+            "https://docs.github.com/en/repositories/managing-your-repositorys-settings-and-features/customizing-your-repository/about-code-owners",
+        );
+    pub const GIT_CODEOWNERS_INVALID_RULE: RuleSpec = RuleSpec::new(
+        "DG_GIT_011",
+        "CODEOWNERS rule is invalid or stale",
+        Category::Git,
+    );
+    pub const GIT_SENSITIVE_DIR_UNOWNED: RuleSpec = RuleSpec::new(
+        "DG_GIT_012",
+        "Sensitive directory has no code owner",
+        Category::Git,
+    )
+    .with_config_key("git.codeowners_sensitive_dirs");
+    pub const GIT_SECRET_FILE_UNFILTERED: RuleSpec = RuleSpec::new(
+        "DG_GIT_014",
+        "Secret file is not covered by an encryption filter",
+        Category::Git,
+    )
+    .with_config_key("git.encrypted_paths");
+    pub const GIT_TOOLING_NOT_EXPORT_IGNORED: RuleSpec = RuleSpec::new(
+        "DG_GIT_015",
+        "Internal tooling directory is not export-ignored",
+        Category::Git,
+    )
+    .with_config_key("git.export_ignore_dirs");
+    pub const GIT_MERGE_OURS_ON_PROTECTED: RuleSpec = RuleSpec::new(
+        "DG_GIT_016",
+        "Security-critical file uses a merge=ours driver",
+        Category::Git,
+    )
+    .with_config_key("git.merge_protected_paths");
+    pub const GIT_CASE_COLLISION: RuleSpec = RuleSpec::new(
+        "DG_GIT_017",
+        "Tracked paths differ only by case",
+        Category::Git,
+    )
+    .with_config_key("git.portable_paths");
+    pub const GIT_PATH_INVALID_ON_WINDOWS: RuleSpec = RuleSpec::new(
+        "DG_GIT_018",
+        "Tracked path is invalid on Windows",
+        Category::Git,
+    )
+    .with_url("https://learn.microsoft.com/en-us/windows/win32/fileio/naming-a-file")
+    .with_config_key("git.portable_paths");
+    pub const GIT_IGNORE_NEGATION_EXPOSES: RuleSpec = RuleSpec::new(
+        "DG_GIT_019",
+        "Gitignore negation re-includes a forbidden env file",
+        Category::Git,
+    )
+    .with_url("https://git-scm.com/docs/gitignore#_pattern_format")
+    .with_config_key("git.gitignore_coverage");
+    pub const GIT_IGNORE_PATTERN_TOO_NARROW: RuleSpec = RuleSpec::new(
+        "DG_GIT_020",
+        "Gitignore patterns leave forbidden files trackable",
+        Category::Git,
+    )
+    .with_config_key("git.gitignore_coverage");
+    pub const GIT_MERGE_LEFTOVER: RuleSpec = RuleSpec::new(
+        "DG_GIT_021",
+        "Merge or patch leftover in the repository",
+        Category::Git,
+    )
+    .with_config_key("git.merge_leftovers");
+    pub const GIT_MIGRATIONS_BEHIND_SCHEMA: RuleSpec = RuleSpec::new(
+        "DG_GIT_013",
+        "Migrations are older than schema changes",
+        Category::Git,
+    )
+    .with_config_key("staleness.enabled");
 
     pub const SUPABASE_PROVIDER_DISABLED: RuleSpec = RuleSpec::new(
         "DG_SUPABASE_001",
@@ -227,12 +513,16 @@ pub mod rules {
         "DG_SUPABASE_003",
         "Supabase migrations directory is missing",
         Category::Supabase,
-    );
+    )
+    .with_url("https://supabase.com/docs/guides/deployment/database-migrations")
+    .with_config_key("providers.supabase.require_migrations");
     pub const SUPABASE_SQL_MIGRATIONS_MISSING: RuleSpec = RuleSpec::new(
         "DG_SUPABASE_004",
         "Supabase migrations directory has no SQL files",
         Category::Supabase,
-    );
+    )
+    .with_url("https://supabase.com/docs/guides/deployment/database-migrations")
+    .with_config_key("providers.supabase.require_migrations");
     pub const SUPABASE_REQUIRED_ENV_MISSING: RuleSpec = RuleSpec::new(
         "DG_SUPABASE_005",
         "Required Supabase environment variable is missing",
@@ -242,39 +532,339 @@ pub mod rules {
         "DG_SUPABASE_006",
         "Supabase service role reference found in client code",
         Category::Supabase,
-    );
+    )
+    .with_url(SUPABASE_API_KEYS_DOCS)
+    .with_config_key("providers.supabase.forbid_service_role_in_client");
+    pub const SUPABASE_PUBLIC_BUCKET: RuleSpec = RuleSpec::new(
+        "DG_SUPABASE_007",
+        "Supabase storage bucket is public",
+        Category::Supabase,
+    )
+    .with_url(SUPABASE_STORAGE_DOCS)
+    .with_config_key("providers.supabase.review_storage_policies");
+    pub const SUPABASE_ANON_STORAGE_WRITE: RuleSpec = RuleSpec::new(
+        "DG_SUPABASE_008",
+        "Supabase storage policy allows anonymous writes",
+        Category::Supabase,
+    )
+    .with_url(SUPABASE_STORAGE_DOCS)
+    .with_config_key("providers.supabase.review_storage_policies");
+    pub const SUPABASE_ANON_CLIENT_PRIVILEGED: RuleSpec = RuleSpec::new(
+        "DG_SUPABASE_009",
+        "Supabase anon key client used for privileged calls",
+        Category::Supabase,
+    )
+    .with_url(SUPABASE_API_KEYS_DOCS)
+    .with_config_key("providers.supabase.check_server_anon_clients");
 
     pub const VERCEL_JSON_ENV: RuleSpec = RuleSpec::new(
         "DG_VERCEL_001",
         "vercel.json contains committed environment keys",
         Category::Vercel,
-    );
+    )
+    .with_url(VERCEL_ENV_DOCS);
     pub const VERCEL_DIR_TRACKED: RuleSpec = RuleSpec::new(
         "DG_VERCEL_002",
         ".vercel directory appears tracked",
         Category::Vercel,
-    );
+    )
+    .with_url("https://vercel.com/docs/cli/project-linking");
     pub const VERCEL_DIR_PRESENT: RuleSpec = RuleSpec::new(
         "DG_VERCEL_003",
         ".vercel directory exists locally",
         Category::Vercel,
     );
+    pub const VERCEL_EDGE_CONFIG_COMMITTED: RuleSpec = RuleSpec::new(
+        "DG_VERCEL_004",
+        "Edge Config connection string committed",
+        Category::Vercel,
+    )
+    .with_url("https://vercel.com/docs/edge-config/using-edge-config")
+    .with_config_key("providers.vercel.check_edge_runtime");
+    pub const VERCEL_EDGE_SECRET_ENV: RuleSpec = RuleSpec::new(
+        "DG_VERCEL_005",
+        "Secret env var read in edge runtime code",
+        Category::Vercel,
+    )
+    .with_url("https://vercel.com/docs/functions/runtimes/edge")
+    .with_config_key("providers.vercel.check_edge_runtime");
+    pub const VERCEL_PROVIDER_DISABLED: RuleSpec = RuleSpec::new(
+        "DG_VERCEL_006",
+        "Vercel provider is disabled",
+        Category::Vercel,
+    );
+    pub const VERCEL_NOT_DETECTED: RuleSpec = RuleSpec::new(
+        "DG_VERCEL_007",
+        "Vercel markers were not detected",
+        Category::Vercel,
+    );
 
     pub const STRIPE_LIVE_KEY_IN_DOTENV: RuleSpec = RuleSpec::new(
         "DG_STRIPE_001",
         "Live Stripe key found in dotenv file",
         Category::Stripe,
-    );
+    )
+    .with_url(STRIPE_KEYS_DOCS)
+    .with_config_key("providers.stripe.warn_live_keys");
     pub const STRIPE_TEST_KEY_IN_DOTENV: RuleSpec = RuleSpec::new(
         "DG_STRIPE_002",
         "Test Stripe key found in dotenv file",
         Category::Stripe,
-    );
+    )
+    .with_url(STRIPE_KEYS_DOCS);
     pub const STRIPE_MIXED_MODES: RuleSpec = RuleSpec::new(
         "DG_STRIPE_003",
         "Mixed Stripe modes detected",
         Category::Stripe,
+    )
+    .with_url(STRIPE_KEYS_DOCS);
+    pub const STRIPE_DUPLICATE_WEBHOOK_HANDLERS: RuleSpec = RuleSpec::new(
+        "DG_STRIPE_004",
+        "Stripe webhook events verified by several handlers",
+        Category::Stripe,
+    )
+    .with_url(STRIPE_WEBHOOK_DOCS)
+    .with_config_key("providers.stripe.check_code");
+    pub const STRIPE_HARDCODED_WEBHOOK_SECRET: RuleSpec = RuleSpec::new(
+        "DG_STRIPE_005",
+        "Hard-coded Stripe webhook signing secret",
+        Category::Stripe,
+    )
+    .with_url(STRIPE_WEBHOOK_DOCS)
+    .with_config_key("providers.stripe.check_code");
+    pub const STRIPE_OLD_API_VERSION: RuleSpec = RuleSpec::new(
+        "DG_STRIPE_006",
+        "Stripe API version pinned to an old release",
+        Category::Stripe,
+    )
+    .with_url("https://docs.stripe.com/upgrades")
+    .with_config_key("providers.stripe.min_api_version");
+    pub const STRIPE_TEST_CLOCK_IN_CODE: RuleSpec = RuleSpec::new(
+        "DG_STRIPE_007",
+        "Stripe test clock used in application code",
+        Category::Stripe,
+    )
+    .with_url("https://docs.stripe.com/billing/testing/test-clocks")
+    .with_config_key("providers.stripe.check_code");
+    pub const STRIPE_PROVIDER_DISABLED: RuleSpec = RuleSpec::new(
+        "DG_STRIPE_008",
+        "Stripe provider is disabled",
+        Category::Stripe,
+    );
+    pub const STRIPE_NOT_DETECTED: RuleSpec = RuleSpec::new(
+        "DG_STRIPE_009",
+        "Stripe markers were not detected",
+        Category::Stripe,
     );
+
+    pub const RELEASE_CONSOLE_ENV: RuleSpec = RuleSpec::new(
+        "DG_REL_001",
+        "Environment object logged to console",
+        Category::Release,
+    );
+    pub const RELEASE_DEBUGGER_STATEMENT: RuleSpec = RuleSpec::new(
+        "DG_REL_002",
+        "Debugger statement in source",
+        Category::Release,
+    );
+    pub const RELEASE_DEBUG_ENABLED: RuleSpec = RuleSpec::new(
+        "DG_REL_003",
+        "Debug flag enabled in production config",
+        Category::Release,
+    );
+    pub const RELEASE_REMOVE_MARKER: RuleSpec = RuleSpec::new(
+        "DG_REL_004",
+        "Remove-before-launch marker present",
+        Category::Release,
+    );
+    pub const RELEASE_LOCALHOST_URL: RuleSpec = RuleSpec::new(
+        "DG_REL_005",
+        "Localhost URL in production config",
+        Category::Release,
+    );
+
+    pub const GITHUB_BRANCH_PROTECTION: RuleSpec =
+        RuleSpec::new("DG_GH_001", "Default branch protection", Category::Git)
+            .with_url(GITHUB_BRANCH_PROTECTION_DOCS);
+    pub const GITHUB_FORCE_PUSH: RuleSpec = RuleSpec::new(
+        "DG_GH_002",
+        "Force pushes on the default branch",
+        Category::Git,
+    )
+    .with_url(GITHUB_BRANCH_PROTECTION_DOCS);
+    pub const GITHUB_SECRET_SCANNING: RuleSpec =
+        RuleSpec::new("DG_GH_003", "GitHub secret scanning", Category::Git)
+            .with_url(GITHUB_SECRET_SCANNING_DOCS);
+    pub const GITHUB_PUSH_PROTECTION: RuleSpec = RuleSpec::new(
+        "DG_GH_004",
+        "GitHub secret scanning push protection",
+        Category::Git,
+    )
+    .with_url(GITHUB_SECRET_SCANNING_DOCS);
+    pub const GITHUB_PUBLIC_WITH_SECRETS: RuleSpec = RuleSpec::new(
+        "DG_GH_005",
+        "Public repository contains detected secrets",
+        Category::Git,
+    )
+    .with_url(GITHUB_REMOVE_SENSITIVE_DATA);
+    pub const GITHUB_SETTINGS_UNAVAILABLE: RuleSpec = RuleSpec::new(
+        "DG_GH_006",
+        "GitHub repository settings unavailable",
+        Category::Git,
+    );
+
+    pub const SUPPLY_LIFECYCLE_SCRIPT: RuleSpec = RuleSpec::new(
+        "DG_SUPPLY_001",
+        "Installed package lifecycle script runs a suspicious command",
+        Category::Supply,
+    );
+    pub const SUPPLY_BIN_SCRIPT: RuleSpec = RuleSpec::new(
+        "DG_SUPPLY_002",
+        "node_modules/.bin script runs a suspicious command",
+        Category::Supply,
+    );
+    pub const SUPPLY_HOOK_SCRIPT: RuleSpec = RuleSpec::new(
+        "DG_SUPPLY_003",
+        "Git hook runs a suspicious command",
+        Category::Supply,
+    );
+
+    pub const DOCTOR_GIT_CLI: RuleSpec = RuleSpec::new(
+        "DG_DOCTOR_001",
+        "git executable availability",
+        Category::Tooling,
+    );
+    pub const DOCTOR_GIT_REPO: RuleSpec = RuleSpec::new(
+        "DG_DOCTOR_002",
+        "Repository is readable by the git backend",
+        Category::Tooling,
+    );
+    pub const DOCTOR_CONFIG: RuleSpec =
+        RuleSpec::new("DG_DOCTOR_003", "Config file parses", Category::Tooling);
+    pub const DOCTOR_PROVIDER_DETECTION: RuleSpec = RuleSpec::new(
+        "DG_DOCTOR_004",
+        "Provider detection markers",
+        Category::Tooling,
+    );
+    pub const DOCTOR_PROVIDER_CLI: RuleSpec = RuleSpec::new(
+        "DG_DOCTOR_005",
+        "Provider CLI availability",
+        Category::Tooling,
+    );
+    pub const DOCTOR_PROVIDER_LOGIN: RuleSpec = RuleSpec::new(
+        "DG_DOCTOR_006",
+        "Provider CLI login status",
+        Category::Tooling,
+    );
+
+    pub const PROVIDER_CHECK_FAILED: RuleSpec = RuleSpec::new(
+        "DG_PROVIDER_001",
+        "Provider check failed or timed out",
+        Category::Tooling,
+    );
+
+    pub const SCAN_TIMED_OUT: RuleSpec = RuleSpec::new(
+        "DG_SCAN_001",
+        "Scan time budget exceeded",
+        Category::Tooling,
+    );
+    pub const SCAN_LIMIT_REACHED: RuleSpec = RuleSpec::new(
+        "DG_SCAN_003",
+        "Scan file or size limit reached",
+        Category::Tooling,
+    );
+    pub const SCAN_NOTHING_SCANNED: RuleSpec =
+        RuleSpec::new("DG_SCAN_002", "No files were scanned", Category::Tooling);
+    pub const SCAN_BLAME_UNAVAILABLE: RuleSpec = RuleSpec::new(
+        "DG_SCAN_004",
+        "Blame is not available in this build",
+        Category::Tooling,
+    )
+    .with_config_key("scan.blame");
+
+    pub const PACK_CUSTOM_PATTERN: RuleSpec =
+        RuleSpec::new("DG_PACK_001", "Custom pattern matched", Category::Secrets)
+            .with_config_key("custom_patterns");
+    pub const PACK_REQUIRED_FILE_MISSING: RuleSpec =
+        RuleSpec::new("DG_PACK_002", "Required file is missing", Category::Git)
+            .with_config_key("required_files");
+
+    pub const SUPPRESSION_EXPIRED: RuleSpec = RuleSpec::new(
+        "DG_SUPPRESS_001",
+        "Suppression has expired",
+        Category::Tooling,
+    );
+}
+
+// the commit that last touched a line; `author` is `Name <email>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineOrigin {
+    pub commit: String,
+    pub author: String,
+    pub time: u64,
+}
+
+// 1-based positions. columns count characters and `end_column` is exclusive, matching SARIF
+// regions with `columnKind: unicodeCodePoints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    pub fn from_offsets(content: &str, start: usize, end: usize) -> Self {
+        let (line, column) = position(content, start);
+        let (end_line, end_column) = position(content, end);
+        Self {
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line = before.bytes().filter(|byte| *byte == b'\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+// a fix a tool can apply without parsing the free-text `remediation` hint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RemediationAction {
+    GitignoreAdd {
+        path: String,
+    },
+    UntrackFile {
+        path: String,
+    },
+    RotateKey {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        provider: Option<String>,
+    },
+    SetEnvVar {
+        key: String,
+    },
+    AddExampleKey {
+        key: String,
+    },
+    RemoveExampleKey {
+        key: String,
+    },
+}
+
+impl RemediationAction {
+    pub fn rotate_key(provider: &str) -> Self {
+        Self::RotateKey {
+            provider: Some(provider.to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -285,17 +875,53 @@ pub struct Issue {
     pub description: Option<String>,
     pub severity: Severity,
     pub category: Category,
+    // the label a custom pattern declares in place of a built-in category; reports group
+    // by it while scoring and category filters keep using `category`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_category: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introduced_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introduced_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introduced_in: Option<String>,
+    // the team or address `[owners]` routes this finding to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
     pub remediation: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<RemediationAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<&'static str>,
+    pub config_key: String,
+    // how many identical findings were merged into this one
+    #[serde(skip_serializing_if = "is_zero")]
+    pub duplicates: usize,
     #[serde(skip)]
     pub weight_override: Option<u8>,
+    // the English title once a message catalog has translated `title`
+    #[serde(skip)]
+    pub original_title: Option<String>,
     #[serde(skip)]
     pub rule_title: &'static str,
 }
 
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
 impl Issue {
     pub fn from_rule(
         rule: RuleSpec,
@@ -309,10 +935,24 @@ impl Issue {
             description: None,
             severity,
             category: rule.category,
+            custom_category: None,
             file: None,
             line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            snippet: None,
+            introduced_by: None,
+            introduced_at: None,
+            introduced_in: None,
+            owner: None,
             remediation: remediation.into(),
+            actions: Vec::new(),
+            url: rule.url,
+            config_key: rule.config_key(),
+            duplicates: 0,
             weight_override: None,
+            original_title: None,
             rule_title: rule.rule_title,
         }
     }
@@ -322,6 +962,11 @@ impl Issue {
         self
     }
 
+    pub fn with_action(mut self, action: RemediationAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
     pub fn with_file(mut self, file: impl Into<String>) -> Self {
         self.file = Some(file.into());
         self
@@ -332,9 +977,69 @@ impl Issue {
         self
     }
 
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.line = Some(span.line);
+        self.column = Some(span.column);
+        self.end_line = Some(span.end_line);
+        self.end_column = Some(span.end_column);
+        self
+    }
+
+    pub fn with_snippet(mut self, snippet: Vec<String>) -> Self {
+        self.snippet = Some(snippet);
+        self
+    }
+
+    // who committed the flagged line, from git blame
+    pub fn with_origin(mut self, origin: LineOrigin) -> Self {
+        self.introduced_by = Some(origin.author);
+        self.introduced_at = Some(format_timestamp(origin.time));
+        self.introduced_in = Some(origin.commit);
+        self
+    }
+
+    pub fn category_label(&self) -> &str {
+        self.custom_category
+            .as_deref()
+            .unwrap_or(self.category.label())
+    }
+
+    // `internal-tools` for a custom `Internal Tools` label
+    pub fn category_slug(&self) -> String {
+        match &self.custom_category {
+            Some(label) => label
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_ascii_lowercase(),
+            None => self.category.slug().to_string(),
+        }
+    }
+
+    // stable across runs and line shifts: rule, file, and title only
+    pub fn fingerprint(&self) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let title = self.original_title.as_deref().unwrap_or(&self.title);
+        let parts = [self.code, self.file.as_deref().unwrap_or(""), title];
+        for byte in parts.join("\u{1f}").bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("{:016x}", hash)
+    }
+
+    // the directory holding the file, `.` for files at the repository root
+    pub fn directory(&self) -> Option<&str> {
+        let file = self.file.as_deref()?;
+        Some(file.rsplit_once('/').map_or(".", |(dir, _)| dir))
+    }
+
     pub fn location(&self) -> Option<String> {
         match (&self.file, self.line) {
-            (Some(file), Some(line)) => Some(format!("{}:{}", file, line)),
+            (Some(file), Some(line)) => Some(match self.column {
+                Some(column) => format!("{}:{}:{}", file, line, column),
+                None => format!("{}:{}", file, line),
+            }),
             (Some(file), None) => Some(file.clone()),
             _ => None,
         }
@@ -0,0 +1,190 @@
+use crate::config::Config;
+use crate::core::issue::RuleSpec;
+use crate::core::push::{self, CommitChanges};
+use crate::core::scanner::Allowlist;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::process;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// stashes and commits only the HEAD reflog still points at never reach a remote, so the
+// regular scan and `scan push` miss them until one is popped or cherry-picked in a hurry
+pub fn scan_local_history(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    if ctx.git_repo.is_none() {
+        return Vec::new();
+    }
+    let timeout = cfg
+        .scan
+        .timeout_secs
+        .map_or(push::GIT_TIMEOUT, Duration::from_secs);
+    let allowlist = Allowlist::new(&cfg.scan.allowlist);
+    let mut issues = Vec::new();
+
+    // the git CLI may be missing or the repo may have no stash; both just mean nothing to scan
+    let stashes =
+        push::read_patches(&ctx.repo_root, &["stash", "list"], &[], timeout).unwrap_or_default();
+    for (index, changes) in stashes.iter().enumerate() {
+        let name = format!("stash@{{{}}}", index);
+        let remediation = format!(
+            "remove the secret before applying the stash, then `git stash drop {}`; rotate it if the stash was ever shared",
+            name
+        );
+        issues.extend(
+            scan(ctx, cfg, &allowlist, changes, &name)
+                .into_iter()
+                .map(|issue| relabel(issue, rules::SECRET_IN_STASH, &name, &remediation)),
+        );
+    }
+
+    let mut revs = reflog_commits(&ctx.repo_root, cfg.scan.local_history.reflog_days, timeout);
+    if revs.is_empty() {
+        return issues;
+    }
+    revs.extend(["--not", "--branches", "--tags", "--remotes"].map(str::to_string));
+    let commits = push::read_patches(&ctx.repo_root, &["log"], &revs, timeout).unwrap_or_default();
+    for changes in &commits {
+        let name = format!(
+            "commit {}",
+            &changes.origin.commit[..changes.origin.commit.len().min(12)]
+        );
+        issues.extend(scan(ctx, cfg, &allowlist, changes, &name).into_iter().map(|issue| {
+            relabel(
+                issue,
+                rules::SECRET_IN_REFLOG_COMMIT,
+                &name,
+                "rotate the secret if the commit was ever pushed or shared, then drop it with `git reflog expire --expire-unreachable=now --all && git gc --prune=now`",
+            )
+        }));
+    }
+
+    issues
+}
+
+fn scan(
+    ctx: &RepoContext,
+    cfg: &Config,
+    allowlist: &Allowlist,
+    changes: &CommitChanges,
+    name: &str,
+) -> Vec<Issue> {
+    let (issues, allowed) = push::scan_commit(changes, allowlist, cfg, name);
+    for finding in allowed {
+        ctx.record_suppressed(finding);
+    }
+    issues
+}
+
+// distinct commits the HEAD reflog has recorded in the last `days` days, newest first
+fn reflog_commits(repo_root: &Path, days: u64, timeout: Duration) -> Vec<String> {
+    let root = repo_root.to_string_lossy();
+    let Some(output) = process::run_git(
+        &[
+            "-C",
+            &root,
+            "reflog",
+            "show",
+            "--format=%H %gd",
+            "--date=unix",
+            "HEAD",
+        ],
+        timeout,
+    )
+    .filter(|output| output.success) else {
+        return Vec::new();
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    parse_reflog(&output.stdout, now.saturating_sub(days * 24 * 60 * 60))
+}
+
+// `<sha> HEAD@{<unix time>}` per entry
+fn parse_reflog(output: &str, since: u64) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    output
+        .lines()
+        .filter_map(|line| {
+            let (sha, selector) = line.split_once(' ')?;
+            let time = selector
+                .strip_prefix("HEAD@{")?
+                .strip_suffix('}')?
+                .parse::<u64>()
+                .ok()?;
+            (time >= since && seen.insert(sha)).then(|| sha.to_string())
+        })
+        .collect()
+}
+
+fn relabel(issue: Issue, rule: RuleSpec, name: &str, remediation: &str) -> Issue {
+    let mut relabeled = Issue::from_rule(
+        rule,
+        Severity::Warning,
+        format!("{} in {}", issue.title, name),
+        remediation,
+    );
+    relabeled.description = issue.description;
+    relabeled.file = issue.file;
+    relabeled.line = issue.line;
+    relabeled.column = issue.column;
+    relabeled.end_line = issue.end_line;
+    relabeled.end_column = issue.end_column;
+    relabeled.snippet = issue.snippet;
+    relabeled.introduced_by = issue.introduced_by;
+    relabeled.introduced_at = issue.introduced_at;
+    relabeled.introduced_in = issue.introduced_in;
+    relabeled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_recent_reflog_entries_once() {
+        let output = "aaa111 HEAD@{1700000300}\n\
+bbb222 HEAD@{1700000200}\n\
+aaa111 HEAD@{1700000100}\n\
+ccc333 HEAD@{1600000000}\n\
+not a reflog line\n";
+        assert_eq!(parse_reflog(output, 1_700_000_000), ["aaa111", "bbb222"]);
+    }
+
+    #[test]
+    fn stash_patches_never_run_the_repos_diff_programs() {
+        let root = std::env::temp_dir().join(format!("devguard-stash-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).expect("repo dir");
+        let marker = root.join("ran");
+        let git = |args: &[&str]| {
+            let dir = root.to_string_lossy();
+            let identity = ["-c", "user.name=Ada", "-c", "user.email=ada@example.com"];
+            let output = process::run_with_timeout(
+                "git",
+                &[&["-C", dir.as_ref()], &identity[..], args].concat(),
+                Duration::from_secs(30),
+            )
+            .expect("git runs");
+            assert!(output.success, "{}", output.first_line());
+        };
+        git(&["init", "--quiet"]);
+        std::fs::write(root.join(".gitattributes"), "*.env diff=evil\n").expect("attributes");
+        std::fs::write(root.join("app.env"), "A=1\n").expect("file");
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "init"]);
+        std::fs::write(root.join("app.env"), "A=1\nB=2\n").expect("file");
+        git(&["stash", "--quiet"]);
+        let command = format!("touch {}", marker.display());
+        git(&["config", "diff.evil.textconv", &command]);
+        git(&["config", "core.fsmonitor", &command]);
+
+        let stashes = push::read_patches(&root, &["stash", "list"], &[], Duration::from_secs(30))
+            .expect("stash read");
+        let marker_written = marker.exists();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(!marker_written, "a repo-configured program ran");
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].added.iter().any(|added| added.text == "B=2"));
+    }
+}
@@ -0,0 +1,271 @@
+use crate::config::ScanConfig;
+use crate::core::RepoContext;
+use crate::utils::fs as fs_utils;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ecosystem {
+    Python,
+    Ruby,
+    Go,
+    Rust,
+}
+
+impl Ecosystem {
+    pub const ALL: [Ecosystem; 4] = [
+        Ecosystem::Python,
+        Ecosystem::Ruby,
+        Ecosystem::Go,
+        Ecosystem::Rust,
+    ];
+}
+
+// dependency names declared by non-JS manifests anywhere in the repo
+#[derive(Debug, Default)]
+pub struct DependencyManifests {
+    names: HashSet<(Ecosystem, String)>,
+}
+
+impl DependencyManifests {
+    // `files` comes from the context's one manifest walk; reads count against the scan's limits
+    pub fn load(ctx: &RepoContext, files: &[PathBuf], scan: &ScanConfig) -> Self {
+        let mut manifests = Self::default();
+        for (index, path) in files.iter().enumerate() {
+            let Some((ecosystem, parse)) = parser(path) else {
+                continue;
+            };
+            if ctx.budget.expired() {
+                ctx.budget.record_stopped(
+                    "dependency manifests",
+                    files[index..]
+                        .iter()
+                        .filter(|path| is_manifest(path))
+                        .map(|path| fs_utils::relative_path(&ctx.repo_root, path)),
+                );
+                break;
+            }
+            let Some(bytes) = ctx.read_scannable(path, scan) else {
+                continue;
+            };
+            manifests.names.extend(
+                parse(&fs_utils::decode_text(&bytes))
+                    .into_iter()
+                    .map(|name| (ecosystem, name)),
+            );
+        }
+        manifests
+    }
+
+    pub fn contains(&self, ecosystem: Ecosystem, name: &str) -> bool {
+        self.names
+            .contains(&(ecosystem, normalize(ecosystem, name)))
+    }
+}
+
+pub fn is_manifest(path: &Path) -> bool {
+    parser(path).is_some()
+}
+
+type Parser = fn(&str) -> Vec<String>;
+
+fn parser(path: &Path) -> Option<(Ecosystem, Parser)> {
+    let file_name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+    Some(match file_name.as_str() {
+        "pyproject.toml" => (Ecosystem::Python, pyproject_dependencies),
+        "gemfile" => (Ecosystem::Ruby, gemfile_dependencies),
+        "go.mod" => (Ecosystem::Go, go_mod_dependencies),
+        "cargo.toml" => (Ecosystem::Rust, cargo_dependencies),
+        name if name.starts_with("requirements") && name.ends_with(".txt") => {
+            (Ecosystem::Python, requirements_dependencies)
+        }
+        _ => return None,
+    })
+}
+
+// pip treats `Foo_Bar` and `foo-bar` as the same project; go major versions live in the path
+fn normalize(ecosystem: Ecosystem, name: &str) -> String {
+    match ecosystem {
+        Ecosystem::Python => name.to_ascii_lowercase().replace(['_', '.'], "-"),
+        Ecosystem::Go => match name.rsplit_once("/v") {
+            Some((module, major)) if major.chars().all(|ch| ch.is_ascii_digit()) => {
+                module.to_string()
+            }
+            _ => name.to_string(),
+        },
+        Ecosystem::Ruby | Ecosystem::Rust => name.to_string(),
+    }
+}
+
+// `stripe==7.0`, `supabase>=2 ; python_version > "3.8"`, `stripe[async]`
+fn pep508_name(requirement: &str) -> Option<String> {
+    let name = requirement
+        .trim()
+        .split(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.')))
+        .next()?;
+    (!name.is_empty()).then(|| normalize(Ecosystem::Python, name))
+}
+
+fn requirements_dependencies(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .filter_map(pep508_name)
+        .collect()
+}
+
+fn pyproject_dependencies(content: &str) -> Vec<String> {
+    let Ok(value) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    if let Some(project) = value.get("project") {
+        let optional = project
+            .get("optional-dependencies")
+            .and_then(toml::Value::as_table)
+            .into_iter()
+            .flat_map(|table| table.values());
+        for list in project.get("dependencies").into_iter().chain(optional) {
+            names.extend(
+                list.as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(toml::Value::as_str)
+                    .filter_map(pep508_name),
+            );
+        }
+    }
+    if let Some(poetry) = value.get("tool").and_then(|tool| tool.get("poetry")) {
+        names.extend(
+            poetry
+                .get("dependencies")
+                .and_then(toml::Value::as_table)
+                .into_iter()
+                .flat_map(|table| table.keys())
+                .filter(|name| *name != "python")
+                .map(|name| normalize(Ecosystem::Python, name)),
+        );
+    }
+    names
+}
+
+fn gemfile_dependencies(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("gem "))
+        .filter_map(|rest| {
+            let rest = rest.trim_start();
+            let quote = rest.chars().next().filter(|ch| matches!(ch, '\'' | '"'))?;
+            let name = rest[1..].split(quote).next()?;
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+fn go_mod_dependencies(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let module = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line.split_whitespace().next()
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else {
+            line.strip_prefix("require ")
+                .and_then(|rest| rest.split_whitespace().next())
+        };
+        names.extend(module.map(|module| normalize(Ecosystem::Go, module)));
+    }
+    names
+}
+
+fn cargo_dependencies(content: &str) -> Vec<String> {
+    let Ok(value) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let workspace = value.get("workspace");
+    ["dependencies", "dev-dependencies", "build-dependencies"]
+        .iter()
+        .flat_map(|section| [value.get(section), workspace.and_then(|ws| ws.get(section))])
+        .flatten()
+        .filter_map(toml::Value::as_table)
+        .flat_map(|table| table.keys().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn python_manifests_are_normalized() {
+        let requirements = "# payments\nStripe==7.0.0\nsupabase[async] >= 2 ; python_version > '3.8'\n-r base.txt\n";
+        assert_eq!(
+            requirements_dependencies(requirements),
+            ["stripe", "supabase"]
+        );
+
+        let pyproject = r#"
+[project]
+dependencies = ["stripe>=7"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+Supabase_Py = "^2"
+"#;
+        assert_eq!(pyproject_dependencies(pyproject), ["stripe", "supabase-py"]);
+    }
+
+    #[test]
+    fn gemfile_go_mod_and_cargo_are_parsed() {
+        assert_eq!(
+            gemfile_dependencies(
+                "source 'https://rubygems.org'\ngem 'stripe', '~> 10'\ngem \"rails\"\n"
+            ),
+            ["stripe", "rails"]
+        );
+
+        let go_mod = "module example.com/api\n\nrequire github.com/stripe/stripe-go/v76 v76.0.0\nrequire (\n\tgithub.com/supabase-community/supabase-go v0.0.4 // indirect\n)\n";
+        assert_eq!(
+            go_mod_dependencies(go_mod),
+            [
+                "github.com/stripe/stripe-go",
+                "github.com/supabase-community/supabase-go"
+            ]
+        );
+
+        let cargo = "[package]\nname = \"api\"\n\n[dependencies]\nasync-stripe = \"0.37\"\n";
+        assert_eq!(cargo_dependencies(cargo), ["async-stripe"]);
+    }
+
+    #[test]
+    fn manifest_reads_stop_at_the_file_limit() {
+        let root = std::env::temp_dir().join(format!("devguard-manifests-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("svc")).expect("dir created");
+        std::fs::write(root.join("requirements.txt"), "stripe==7\n").expect("written");
+        std::fs::write(root.join("svc/go.mod"), "require github.com/x/y v1\n").expect("written");
+
+        let mut cfg = crate::config::Config::default();
+        cfg.scan.max_files = Some(1);
+        let ctx = RepoContext::build(&root, &cfg).expect("context");
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(ctx.has_manifest_dependency(Ecosystem::Python, "stripe"));
+        assert!(!ctx.has_manifest_dependency(Ecosystem::Go, "github.com/x/y"));
+        assert_eq!(ctx.stats.files_scanned(), 1);
+        assert!(
+            ctx.budget
+                .skipped()
+                .iter()
+                .any(|skipped| skipped == "dependency manifests: stopped with 1 file(s) unscanned")
+        );
+    }
+}
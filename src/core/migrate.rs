@@ -0,0 +1,217 @@
+use crate::config::Config;
+use anyhow::{Context, Result, bail};
+use toml_edit::{DocumentMut, Item, Key, Table, TableLike};
+
+// a key that moved between config layouts. paths are dotted from the top of devguard.toml.
+// config loading does not read this list: a file using an old name must be migrated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub from: &'static str,
+    pub to: &'static str,
+}
+
+// empty until a release renames or moves a key
+pub const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Debug, Clone)]
+pub struct Migrated {
+    pub content: String,
+    pub applied: Vec<Migration>,
+}
+
+// rewrites the file in place of each old key, keeping comments, ordering, and formatting of
+// everything else
+pub fn migrate(content: &str) -> Result<Migrated> {
+    migrate_with(content, MIGRATIONS)
+}
+
+fn migrate_with(content: &str, migrations: &[Migration]) -> Result<Migrated> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .context("failed parsing config")?;
+    let mut applied = Vec::new();
+    for migration in migrations {
+        if apply(&mut doc, migration)? {
+            applied.push(*migration);
+        }
+    }
+
+    let content = if applied.is_empty() {
+        content.to_string()
+    } else {
+        doc.to_string()
+    };
+    toml::from_str::<Config>(&content).context("the migrated config does not parse")?;
+    Ok(Migrated { content, applied })
+}
+
+fn apply(doc: &mut DocumentMut, migration: &Migration) -> Result<bool> {
+    let (from_parent, from_key) = split(migration.from);
+    let (to_parent, to_key) = split(migration.to);
+    let root = doc.as_table_mut() as &mut dyn TableLike;
+    if !table_at(root, &from_parent).is_some_and(|table| table.contains_key(from_key)) {
+        return Ok(false);
+    }
+    if table_at(root, &to_parent).is_some_and(|table| table.contains_key(to_key)) {
+        bail!(
+            "both `{}` and `{}` are set; keep one and migrate again",
+            migration.from,
+            migration.to
+        );
+    }
+
+    if from_parent == to_parent {
+        if let Some(table) = table_at(root, &from_parent) {
+            rename(table, from_key, to_key);
+        }
+        return Ok(true);
+    }
+
+    let Some((key, item)) = table_at(root, &from_parent).and_then(|table| {
+        let key = table.key(from_key)?.clone();
+        Some((key, table.remove(from_key)?))
+    }) else {
+        return Ok(false);
+    };
+    // a section left with nothing in it would only be noise
+    if let Some((section, parent)) = from_parent.split_last()
+        && let Some(parent) = table_at(root, parent)
+        && parent
+            .get(section)
+            .and_then(Item::as_table_like)
+            .is_some_and(TableLike::is_empty)
+    {
+        parent.remove(section);
+    }
+    let mut target = root;
+    for section in &to_parent {
+        target = target
+            .entry(section)
+            .or_insert_with(|| {
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            })
+            .as_table_like_mut()
+            .with_context(|| format!("`{}` is not a table", section))?;
+    }
+    target.entry_format(&renamed(&key, to_key)).or_insert(item);
+    Ok(true)
+}
+
+fn split(path: &str) -> (Vec<&str>, &str) {
+    let mut parts = path.split('.').collect::<Vec<_>>();
+    let key = parts.pop().unwrap_or(path);
+    (parts, key)
+}
+
+fn table_at<'a>(root: &'a mut dyn TableLike, path: &[&str]) -> Option<&'a mut dyn TableLike> {
+    path.iter().try_fold(root, |table, section| {
+        table.get_mut(section)?.as_table_like_mut()
+    })
+}
+
+// entries are taken out and put back in order so the renamed key keeps its place
+fn rename(table: &mut dyn TableLike, from: &str, to: &str) {
+    let keys = table
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+    let entries = keys
+        .iter()
+        .filter_map(|key| Some((table.key(key)?.clone(), table.remove(key)?)))
+        .collect::<Vec<_>>();
+    for (key, item) in entries {
+        let key = if key.get() == from {
+            renamed(&key, to)
+        } else {
+            key
+        };
+        table.entry_format(&key).or_insert(item);
+    }
+}
+
+fn renamed(key: &Key, name: &str) -> Key {
+    Key::new(name)
+        .with_leaf_decor(key.leaf_decor().clone())
+        .with_dotted_decor(key.dotted_decor().clone())
+}
+
+// a line diff of the two files, one `@@ line N @@` hunk per run of changed lines
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    // common[i][j] is the longest common run of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    let mut in_hunk = false;
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            in_hunk = false;
+            i += 1;
+            j += 1;
+            continue;
+        }
+        if !in_hunk {
+            out.push_str(&format!("@@ line {} @@\n", i + 1));
+            in_hunk = true;
+        }
+        if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            out.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENAMED: &[Migration] = &[Migration {
+        from: "policy.error_budget",
+        to: "policy.max_errors",
+    }];
+
+    #[test]
+    fn renames_old_keys_in_place() {
+        let old = "# gate merges\n[policy]\n# no errors at all\nerror_budget = 0 # strict\nmax_warnings = 5\n\n[policy.rules]\nDG_SEC_001 = \"error\"\n";
+        let migrated = migrate_with(old, RENAMED).expect("migrated");
+        assert_eq!(migrated.applied, RENAMED);
+        assert_eq!(
+            migrated.content,
+            old.replace("error_budget = 0", "max_errors = 0")
+        );
+        assert_eq!(
+            line_diff(old, &migrated.content),
+            "@@ line 4 @@\n-error_budget = 0 # strict\n+max_errors = 0 # strict\n"
+        );
+
+        let dotted = migrate_with("policy.error_budget = 2\n", RENAMED).expect("migrated");
+        assert_eq!(dotted.content, "policy.max_errors = 2\n");
+
+        let current = migrate_with(&migrated.content, RENAMED).expect("migrated");
+        assert!(current.applied.is_empty());
+        assert_eq!(current.content, migrated.content);
+
+        let both = migrate_with("[policy]\nerror_budget = 0\nmax_errors = 1\n", RENAMED)
+            .expect_err("both keys set");
+        assert!(both.to_string().contains("keep one"));
+        assert!(migrate(old).expect("nothing to do").applied.is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+#[cfg(not(feature = "wasm"))]
+use crate::config::Config;
+#[cfg(not(feature = "wasm"))]
+use crate::core::scanner::Allowlist;
+#[cfg(not(feature = "wasm"))]
+use crate::core::{Issue, RepoContext, Severity, direnv, rules};
+#[cfg(not(feature = "wasm"))]
+use crate::utils::fs::{decode_text, relative_path};
+use std::path::Path;
+#[cfg(not(feature = "wasm"))]
+use toml::Value;
+
+// `mise.toml`, `mise.local.toml`, `mise.production.toml`, their dotted and `.config/`
+// variants, the legacy `.rtx.toml`, and the files under `.config/mise/`
+pub fn is_mise_config(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let in_config_dir = path
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|dir| dir == "mise");
+    let stem = name.trim_start_matches('.');
+    name.ends_with(".toml")
+        && (stem.starts_with("mise.")
+            || stem.starts_with("rtx.")
+            || (in_config_dir && (name == "config.toml" || name.starts_with("config."))))
+}
+
+// the scanner only needs `is_mise_config`; the env check below walks the repo
+#[cfg(not(feature = "wasm"))]
+#[derive(Debug, PartialEq, Eq)]
+struct EnvValue {
+    table: String,
+    key: String,
+    value: String,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn check_mise_env(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let allowlist = Allowlist::new(&cfg.scan.allowlist);
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "mise checks") {
+        if !is_mise_config(entry.path()) {
+            continue;
+        }
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = decode_text(&bytes);
+        let Ok(document) = toml::from_str::<Value>(&content) else {
+            continue;
+        };
+        let rel = relative_path(&ctx.repo_root, entry.path());
+        let tracked = ctx.tracked_status(entry.path()) == Some(true);
+
+        for env in env_values(&document) {
+            if !direnv::is_secret_env_value(&env.key, &env.value, &allowlist) {
+                continue;
+            }
+            let mut issue = Issue::from_rule(
+                rules::ENV_MISE_SECRET,
+                if tracked {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                format!("{} [{}] sets secret value {}", rel, env.table, env.key),
+                if tracked {
+                    "rotate the value and load it with _.file from an ignored dotenv file or a secret manager"
+                } else {
+                    "keep secrets in mise.local.toml or an ignored dotenv file loaded with _.file"
+                },
+            )
+            .with_file(rel.clone());
+            if let Some(line) = line_of(&content, &env.key, &env.value) {
+                issue = issue.with_line(line);
+            }
+            issues.push(issue);
+        }
+    }
+
+    issues
+}
+
+// `[env]` (a table or an array of tables) and every `[tasks.<name>].env`; `_` holds mise
+// directives such as `_.file`, and `{ value = "..." }` is the long form of a value
+#[cfg(not(feature = "wasm"))]
+fn env_values(document: &Value) -> Vec<EnvValue> {
+    let mut tables = Vec::new();
+    match document.get("env") {
+        Some(Value::Table(env)) => tables.push(("env".to_string(), env)),
+        Some(Value::Array(envs)) => tables.extend(
+            envs.iter()
+                .filter_map(Value::as_table)
+                .map(|env| ("env".to_string(), env)),
+        ),
+        _ => {}
+    }
+    if let Some(tasks) = document.get("tasks").and_then(Value::as_table) {
+        for (name, task) in tasks {
+            if let Some(env) = task.get("env").and_then(Value::as_table) {
+                tables.push((format!("tasks.{}.env", name), env));
+            }
+        }
+    }
+
+    let mut values = Vec::new();
+    for (table, env) in tables {
+        for (key, value) in env {
+            if key == "_" {
+                continue;
+            }
+            let value = match value {
+                Value::String(value) => value,
+                Value::Table(long) => match long.get("value") {
+                    Some(Value::String(value)) => value,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            values.push(EnvValue {
+                table: table.clone(),
+                key: key.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+    values
+}
+
+#[cfg(not(feature = "wasm"))]
+fn line_of(content: &str, key: &str, value: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| line.contains(key) && line.contains(value))
+        .map(|idx| idx + 1)
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_tables_and_task_env_are_collected() {
+        let content = r#"
+[tools]
+node = "22"
+
+[env]
+_.file = ".env"
+NODE_ENV = "development"
+NPM_TOKEN = "npm_8f2kQ9xLm3pQ"
+SENTRY_AUTH_TOKEN = { value = "sntrys_4b7c9d1e", redact = true }
+GITHUB_TOKEN = "{{ exec(command='gh auth token') }}"
+
+[tasks.deploy]
+run = "fly deploy"
+env = { FLY_API_TOKEN = "fo1_x8k2m9q4" }
+"#;
+        let document = toml::from_str::<Value>(content).expect("valid toml");
+        let allowlist = Allowlist::default();
+        let found = env_values(&document)
+            .into_iter()
+            .filter(|env| direnv::is_secret_env_value(&env.key, &env.value, &allowlist))
+            .map(|env| format!("{}.{}", env.table, env.key))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                "env.NPM_TOKEN",
+                "env.SENTRY_AUTH_TOKEN",
+                "tasks.deploy.env.FLY_API_TOKEN"
+            ]
+        );
+        assert_eq!(line_of(content, "NPM_TOKEN", "npm_8f2kQ9xLm3pQ"), Some(8));
+
+        assert!(is_mise_config(Path::new("mise.toml")));
+        assert!(is_mise_config(Path::new(".mise.local.toml")));
+        assert!(is_mise_config(Path::new("apps/web/mise.production.toml")));
+        assert!(is_mise_config(Path::new(".config/mise/config.toml")));
+        assert!(is_mise_config(Path::new(".config/mise.toml")));
+        assert!(!is_mise_config(Path::new("config.toml")));
+        assert!(!is_mise_config(Path::new("Cargo.toml")));
+    }
+}
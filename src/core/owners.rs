@@ -0,0 +1,127 @@
+use crate::config::Config;
+use crate::core::Issue;
+use crate::report::{self, FinalReport};
+use crate::utils::glob;
+use anyhow::{Result, bail};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// `[owners]` maps path globs to the team or address that fixes findings there; when several
+// patterns match a path, the longest one wins
+pub struct Owners {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Owners {
+    pub fn new(owners: &BTreeMap<String, String>) -> Self {
+        let mut rules = owners
+            .iter()
+            .filter_map(|(pattern, owner)| Some((pattern.len(), matcher(pattern)?, owner.clone())))
+            .collect::<Vec<_>>();
+        rules.sort_by_key(|(len, _, _)| std::cmp::Reverse(*len));
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|(_, matcher, owner)| (matcher, owner))
+                .collect(),
+        }
+    }
+
+    pub fn owner_of(&self, path: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(path))
+            .map(|(_, owner)| owner.as_str())
+    }
+
+    pub fn assign(&self, issues: &mut [Issue]) {
+        for issue in issues {
+            issue.owner = issue
+                .file
+                .as_deref()
+                .and_then(|file| self.owner_of(file))
+                .map(str::to_string);
+        }
+    }
+}
+
+// a plain path covers itself and everything below it, like a suppression's `path`
+fn matcher(pattern: &str) -> Option<Regex> {
+    if glob::is_glob(pattern) {
+        return glob::path_glob(pattern);
+    }
+    let path = pattern
+        .trim_start_matches("./")
+        .trim_start_matches('/')
+        .trim_end_matches('/');
+    Regex::new(&format!("^{}(?:/.*)?$", regex::escape(path))).ok()
+}
+
+// `--owner`: the report is rebuilt from that owner's findings alone, so the score, exit
+// status, and suppressed list all describe the part of the repo the owner is responsible for
+pub fn retain_owner(report: FinalReport, owner: &str, cfg: &Config) -> Result<FinalReport> {
+    if cfg.owners.is_empty() {
+        bail!("--owner needs an [owners] table in the config");
+    }
+    let owners = Owners::new(&cfg.owners);
+    let issues = report
+        .issues
+        .into_iter()
+        .filter(|issue| issue.owner.as_deref() == Some(owner))
+        .collect();
+    let suppressed = report
+        .suppressed
+        .into_iter()
+        .filter(|finding| {
+            finding
+                .file
+                .as_deref()
+                .and_then(|file| owners.owner_of(file))
+                == Some(owner)
+        })
+        .collect();
+
+    let mut filtered = report::build_report(
+        Path::new(&report.repository_path),
+        issues,
+        report.min_score,
+        report.fail_on,
+        &cfg.policy,
+    );
+    filtered.repository_path = report.repository_path;
+    filtered.partial = report.partial;
+    filtered.empty = report.empty;
+    filtered.run = report.run;
+    filtered.providers = report.providers;
+    report::record_suppressed(&mut filtered, suppressed, &cfg.policy);
+    filtered.owners = report::owner_summaries(&filtered.issues);
+    Ok(filtered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_matching_pattern_owns_the_path() {
+        let owners = Owners::new(&BTreeMap::from([
+            ("src".to_string(), "platform".to_string()),
+            ("src/payments/".to_string(), "payments-team".to_string()),
+            ("**/*.sql".to_string(), "dba@example.com".to_string()),
+        ]));
+
+        assert_eq!(owners.owner_of("src/main.ts"), Some("platform"));
+        assert_eq!(
+            owners.owner_of("src/payments/stripe.ts"),
+            Some("payments-team")
+        );
+        assert_eq!(
+            owners.owner_of("src/payments/schema.sql"),
+            Some("payments-team")
+        );
+        assert_eq!(owners.owner_of("db/schema.sql"), Some("dba@example.com"));
+        assert_eq!(owners.owner_of("srcs/main.ts"), None);
+        assert_eq!(owners.owner_of("README.md"), None);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::config::ScanConfig;
+use crate::core::RepoContext;
+use crate::utils::{fs as fs_utils, glob};
+use serde_json::Value;
+use std::path::PathBuf;
+
+const DEPENDENCY_FIELDS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+#[derive(Debug, Clone)]
+pub struct PackageJson {
+    value: Value,
+}
+
+impl PackageJson {
+    pub fn parse(content: &str) -> Option<Self> {
+        let value = serde_json::from_str::<Value>(content).ok()?;
+        value.is_object().then_some(Self { value })
+    }
+
+    pub fn has_dependency(&self, name: &str) -> bool {
+        DEPENDENCY_FIELDS.iter().any(|field| {
+            self.get_field(field)
+                .and_then(Value::as_object)
+                .is_some_and(|deps| deps.contains_key(name))
+        })
+    }
+
+    pub fn has_script_containing(&self, needle: &str) -> bool {
+        self.get_field("scripts")
+            .and_then(Value::as_object)
+            .is_some_and(|scripts| {
+                scripts
+                    .values()
+                    .filter_map(Value::as_str)
+                    .any(|script| script.contains(needle))
+            })
+    }
+
+    // npm and pnpm use an array, yarn classic also allows { "packages": [...] }
+    pub fn workspace_patterns(&self) -> Vec<String> {
+        let workspaces = self.get_field("workspaces");
+        workspaces
+            .and_then(Value::as_array)
+            .or_else(|| workspaces?.get("packages")?.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    }
+
+    // dotted path into the manifest, e.g. "engines.node"
+    pub fn get_field(&self, path: &str) -> Option<&Value> {
+        path.split('.')
+            .try_fold(&self.value, |value, key| value.get(key))
+    }
+}
+
+// the root package.json followed by every npm/yarn/pnpm workspace member, picked from the
+// context's one manifest walk, which stops with the scan's deadline and limits
+pub fn load_packages(ctx: &RepoContext, files: &[PathBuf], scan: &ScanConfig) -> Vec<PackageJson> {
+    let repo_root = &ctx.repo_root;
+    let root = fs_utils::read_text(&repo_root.join("package.json"))
+        .ok()
+        .and_then(|content| PackageJson::parse(&content));
+
+    let mut patterns = root
+        .as_ref()
+        .map(PackageJson::workspace_patterns)
+        .unwrap_or_default();
+    if let Ok(content) = fs_utils::read_text(&repo_root.join("pnpm-workspace.yaml")) {
+        patterns.extend(pnpm_workspace_patterns(&content));
+    }
+
+    let mut packages = root.into_iter().collect::<Vec<_>>();
+    if patterns.is_empty() {
+        return packages;
+    }
+
+    let (excluded, included): (Vec<_>, Vec<_>) = patterns
+        .iter()
+        .partition(|pattern| pattern.starts_with('!'));
+    let included = included
+        .iter()
+        .filter_map(|pattern| glob::path_glob(pattern.trim_end_matches('/')))
+        .collect::<Vec<_>>();
+    let excluded = excluded
+        .iter()
+        .filter_map(|pattern| glob::path_glob(pattern[1..].trim_end_matches('/')))
+        .collect::<Vec<_>>();
+
+    let mut members = files
+        .iter()
+        .filter(|path| path.file_name().is_some_and(|name| name == "package.json"))
+        .filter(|path| {
+            let Some(parent) = path.parent() else {
+                return false;
+            };
+            let dir = fs_utils::relative_path(repo_root, parent);
+            !dir.is_empty()
+                && included.iter().any(|glob| glob.is_match(&dir))
+                && !excluded.iter().any(|glob| glob.is_match(&dir))
+        })
+        .collect::<Vec<_>>();
+    members.sort();
+
+    packages.extend(members.iter().filter_map(|path| {
+        PackageJson::parse(&fs_utils::decode_text(&ctx.read_scannable(path, scan)?))
+    }));
+    packages
+}
+
+// pnpm-workspace.yaml is only ever a `packages:` list, so a line scan avoids a yaml parser
+fn pnpm_workspace_patterns(content: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if in_packages && let Some(item) = trimmed.strip_prefix('-') {
+            let item = item.split(" #").next().unwrap_or_default().trim();
+            patterns.push(item.trim_matches(['"', '\'']).to_string());
+        }
+    }
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "name": "web",
+        "description": "not actually using \"stripe\" here",
+        "scripts": { "deploy": "vercel deploy --prod" },
+        "dependencies": { "@supabase/supabase-js": "^2.0.0" },
+        "devDependencies": { "vercel": "^33.0.0" },
+        "engines": { "node": ">=20" }
+    }"#;
+
+    #[test]
+    fn dependency_lookup_ignores_other_fields() {
+        let package = PackageJson::parse(MANIFEST).expect("manifest parses");
+        assert!(package.has_dependency("@supabase/supabase-js"));
+        assert!(package.has_dependency("vercel"));
+        assert!(!package.has_dependency("stripe"));
+    }
+
+    #[test]
+    fn scripts_and_fields_are_queryable() {
+        let package = PackageJson::parse(MANIFEST).expect("manifest parses");
+        assert!(package.has_script_containing("vercel deploy"));
+        assert!(!package.has_script_containing("supabase"));
+        assert_eq!(
+            package.get_field("engines.node").and_then(Value::as_str),
+            Some(">=20")
+        );
+        assert!(package.get_field("engines.bun").is_none());
+        assert!(PackageJson::parse("[1, 2]").is_none());
+    }
+
+    #[test]
+    fn workspace_patterns_cover_npm_yarn_and_pnpm() {
+        let npm = PackageJson::parse(r#"{"workspaces": ["apps/*", "packages/*"]}"#)
+            .expect("manifest parses");
+        assert_eq!(npm.workspace_patterns(), ["apps/*", "packages/*"]);
+
+        let yarn = PackageJson::parse(r#"{"workspaces": {"packages": ["apps/*"]}}"#)
+            .expect("manifest parses");
+        assert_eq!(yarn.workspace_patterns(), ["apps/*"]);
+
+        let pnpm =
+            "packages:\n  - 'apps/*'\n  - \"!apps/legacy\" # retired\ncatalog:\n  react: ^18\n";
+        assert_eq!(pnpm_workspace_patterns(pnpm), ["apps/*", "!apps/legacy"]);
+    }
+
+    #[test]
+    fn workspace_members_are_loaded_after_the_root() {
+        let root = std::env::temp_dir().join(format!("devguard-workspace-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for dir in ["apps/web", "apps/legacy", "node_modules/stripe"] {
+            std::fs::create_dir_all(root.join(dir)).expect("dir created");
+        }
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"workspaces": ["apps/*", "!apps/legacy"]}"#,
+        )
+        .expect("root written");
+        std::fs::write(
+            root.join("apps/web/package.json"),
+            r#"{"dependencies": {"@supabase/supabase-js": "^2"}}"#,
+        )
+        .expect("member written");
+        std::fs::write(
+            root.join("apps/legacy/package.json"),
+            r#"{"dependencies": {"stripe": "^14"}}"#,
+        )
+        .expect("excluded member written");
+        std::fs::write(
+            root.join("node_modules/stripe/package.json"),
+            r#"{"name": "stripe"}"#,
+        )
+        .expect("dependency written");
+
+        let ctx = RepoContext::build(&root, &crate::config::Config::default()).expect("context");
+        let packages = ctx.packages;
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(packages.len(), 2);
+        assert!(packages[1].has_dependency("@supabase/supabase-js"));
+        assert!(
+            !packages
+                .iter()
+                .any(|package| package.has_dependency("stripe"))
+        );
+    }
+}
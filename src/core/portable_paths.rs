@@ -0,0 +1,152 @@
+use crate::core::{Issue, RepoContext, Severity, rules};
+use std::collections::{BTreeMap, BTreeSet};
+
+const RESERVED_NAMES: [&str; 4] = ["con", "prn", "aux", "nul"];
+const RESERVED_CHARS: [char; 8] = ['<', '>', ':', '"', '\\', '|', '?', '*'];
+
+// macOS and Windows checkouts are case-insensitive by default, so tracked paths that differ
+// only by case overwrite each other there, and Windows cannot create some names at all
+pub fn run_portable_path_checks(ctx: &RepoContext) -> Vec<Issue> {
+    let Some(tracked) = ctx.tracked_set() else {
+        return Vec::new();
+    };
+    let paths = tracked.paths().collect::<Vec<_>>();
+    let mut issues = Vec::new();
+
+    for spellings in case_collisions(&paths) {
+        issues.push(
+            Issue::from_rule(
+                rules::GIT_CASE_COLLISION,
+                Severity::Warning,
+                format!("{} tracked paths differ only by case", spellings.len()),
+                format!(
+                    "`git mv` all but one of them to a distinct name: {}",
+                    spellings.join(", ")
+                ),
+            )
+            .with_file(spellings[0].clone())
+            .with_description(format!(
+                "{} collide on case-insensitive file systems, so a macOS or Windows checkout keeps only one of them and shows the rest as modified",
+                spellings.join(" and ")
+            )),
+        );
+    }
+
+    let mut invalid = paths
+        .iter()
+        .filter_map(|path| windows_problem(path).map(|problem| (*path, problem)))
+        .collect::<Vec<_>>();
+    invalid.sort();
+    for (path, problem) in invalid {
+        issues.push(
+            Issue::from_rule(
+                rules::GIT_PATH_INVALID_ON_WINDOWS,
+                Severity::Warning,
+                format!("{} cannot be checked out on Windows", path),
+                "rename the path with `git mv` so it is valid on every platform",
+            )
+            .with_file(path.to_string())
+            .with_description(problem),
+        );
+    }
+
+    issues
+}
+
+// groups of spellings that fold to the same path, reported at the shallowest level: two
+// directories differing by case are one collision, not one per file below them
+pub fn case_collisions(paths: &[&str]) -> Vec<Vec<String>> {
+    let mut folded: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    for path in paths {
+        for (at, _) in path.match_indices('/').chain([(path.len(), "")]) {
+            let prefix = &path[..at];
+            folded
+                .entry(prefix.to_lowercase())
+                .or_default()
+                .insert(prefix);
+        }
+    }
+
+    let mut reported: Vec<String> = Vec::new();
+    let mut collisions = Vec::new();
+    for (key, spellings) in folded {
+        if spellings.len() < 2
+            || reported.iter().any(|parent| {
+                key.starts_with(parent.as_str()) && key[parent.len()..].starts_with('/')
+            })
+        {
+            continue;
+        }
+        reported.push(key);
+        collisions.push(spellings.into_iter().map(str::to_string).collect());
+    }
+
+    collisions
+}
+
+// the first path component Windows refuses to create, and why
+pub fn windows_problem(path: &str) -> Option<String> {
+    path.split('/').find_map(|component| {
+        if let Some(ch) = component
+            .chars()
+            .find(|ch| RESERVED_CHARS.contains(ch) || ch.is_control())
+        {
+            return Some(format!("`{}` contains the character {:?}", component, ch));
+        }
+        if component.ends_with('.') || component.ends_with(' ') {
+            return Some(format!(
+                "`{}` ends with a dot or space, which Windows strips",
+                component
+            ));
+        }
+        let stem = component
+            .split('.')
+            .next()
+            .unwrap_or(component)
+            .trim_end()
+            .to_ascii_lowercase();
+        let reserved = RESERVED_NAMES.contains(&stem.as_str())
+            || ["com", "lpt"].iter().any(|device| {
+                stem.strip_prefix(device).is_some_and(|digit| {
+                    digit.len() == 1 && digit.as_bytes()[0].is_ascii_digit() && digit != "0"
+                })
+            });
+        reserved.then(|| format!("`{}` uses the reserved device name {}", component, stem))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_case_collisions_once_at_the_shallowest_level() {
+        let paths = [
+            "Docs/guide.md",
+            "docs/guide.md",
+            "docs/intro.md",
+            "src/Config.ts",
+            "src/config.ts",
+            "src/main.ts",
+        ];
+        assert_eq!(
+            case_collisions(&paths),
+            vec![
+                vec!["Docs".to_string(), "docs".to_string()],
+                vec!["src/Config.ts".to_string(), "src/config.ts".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn recognizes_paths_windows_cannot_create() {
+        assert!(windows_problem("src/aux.ts").is_some());
+        assert!(windows_problem("logs/COM1").is_some());
+        assert!(windows_problem("notes./todo.md").is_some());
+        assert!(windows_problem("assets/logo .png ").is_some());
+        assert!(windows_problem("docs/what?.md").is_some());
+        assert!(windows_problem("src/auxiliary.ts").is_none());
+        assert!(windows_problem("src/com0.ts").is_none());
+        assert!(windows_problem(".github/workflows/ci.yml").is_none());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::config::Config;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs::{decode_text, relative_path};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+const SOURCE_EXTENSIONS: [&str; 9] = [
+    "js", "jsx", "ts", "tsx", "mjs", "cjs", "vue", "svelte", "astro",
+];
+
+static CONSOLE_ENV_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"console\.(log|debug|info|dir|table)\(\s*(process\.env|import\.meta\.env)\s*[,)]")
+        .expect("valid console env regex")
+});
+static DEBUGGER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*debugger\s*;?\s*$").expect("valid debugger regex"));
+static REMOVE_MARKER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(todo|fixme|hack)\b.*\bremove\s+(this\s+)?before\s+(launch|release|prod|production|ship)")
+        .expect("valid remove-before-launch regex")
+});
+static DEBUG_TRUE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)^\s*(export\s+)?[A-Z0-9_]*DEBUG\s*=\s*["']?(true|1|yes|on)["']?\s*$"#)
+        .expect("valid debug flag regex")
+});
+static LOCALHOST_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(https?|postgres(ql)?|mysql|redis|mongodb)://(localhost|127\.0\.0\.1|0\.0\.0\.0)\b",
+    )
+    .expect("valid localhost url regex")
+});
+
+pub fn scan_release_readiness(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "release readiness scan") {
+        let is_source = has_source_extension(entry.path());
+        let is_production_config = is_production_config(entry.path(), cfg);
+
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+
+        let content = decode_text(&bytes);
+        let rel = relative_path(&ctx.repo_root, entry.path());
+        issues.extend(scan_text(&content, &rel, is_source, is_production_config));
+    }
+
+    issues
+}
+
+fn scan_text(content: &str, rel: &str, is_source: bool, is_production_config: bool) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+
+        if is_source && CONSOLE_ENV_RE.is_match(line) {
+            issues.push(
+                Issue::from_rule(
+                    rules::RELEASE_CONSOLE_ENV,
+                    Severity::Warning,
+                    "environment object logged to console",
+                    "remove the log statement; it prints every configured secret",
+                )
+                .with_file(rel.to_string())
+                .with_line(line_no),
+            );
+        }
+
+        if is_source && DEBUGGER_RE.is_match(line) {
+            issues.push(
+                Issue::from_rule(
+                    rules::RELEASE_DEBUGGER_STATEMENT,
+                    Severity::Warning,
+                    "debugger statement left in source",
+                    "remove the debugger statement before release",
+                )
+                .with_file(rel.to_string())
+                .with_line(line_no),
+            );
+        }
+
+        if REMOVE_MARKER_RE.is_match(line) {
+            issues.push(
+                Issue::from_rule(
+                    rules::RELEASE_REMOVE_MARKER,
+                    Severity::Warning,
+                    "remove-before-launch marker found",
+                    "finish the cleanup the marker describes, then delete the marker",
+                )
+                .with_file(rel.to_string())
+                .with_line(line_no),
+            );
+        }
+
+        if is_production_config && DEBUG_TRUE_RE.is_match(line) {
+            issues.push(
+                Issue::from_rule(
+                    rules::RELEASE_DEBUG_ENABLED,
+                    Severity::Warning,
+                    "debug flag enabled in production config",
+                    "set the debug flag to false for production",
+                )
+                .with_file(rel.to_string())
+                .with_line(line_no),
+            );
+        }
+
+        if is_production_config && LOCALHOST_RE.is_match(line) {
+            issues.push(
+                Issue::from_rule(
+                    rules::RELEASE_LOCALHOST_URL,
+                    Severity::Warning,
+                    "localhost URL in production config",
+                    "point production config at the deployed service instead of a local address",
+                )
+                .with_file(rel.to_string())
+                .with_line(line_no),
+            );
+        }
+    }
+
+    issues
+}
+
+fn has_source_extension(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+fn is_production_config(path: &Path, cfg: &Config) -> bool {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if cfg
+        .release
+        .production_env_files
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(&file_name))
+    {
+        return true;
+    }
+
+    // config/production.json, docker-compose.prod.yml, app.production.toml
+    file_name
+        .split(['.', '-', '_'])
+        .any(|part| part == "production" || part == "prod")
+        && !has_source_extension(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(issues: &[Issue]) -> Vec<&'static str> {
+        issues.iter().map(|issue| issue.code).collect()
+    }
+
+    #[test]
+    fn flags_debug_leftovers_in_source() {
+        let content = "console.log(process.env);\n  debugger;\n// TODO: remove before launch\nconst debuggerEnabled = false;\n";
+        let issues = scan_text(content, "src/app.ts", true, false);
+        assert_eq!(
+            codes(&issues),
+            vec!["DG_REL_001", "DG_REL_002", "DG_REL_004"]
+        );
+    }
+
+    #[test]
+    fn flags_debug_and_localhost_in_production_config() {
+        let content =
+            "DEBUG=true\nAPI_URL=http://localhost:3000\nDATABASE_URL=postgres://db.internal/app\n";
+        let issues = scan_text(content, ".env.production", false, true);
+        assert_eq!(codes(&issues), vec!["DG_REL_003", "DG_REL_005"]);
+
+        let issues = scan_text(content, ".env", false, false);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn recognizes_production_config_names() {
+        let cfg = Config::default();
+        assert!(is_production_config(Path::new(".env.production"), &cfg));
+        assert!(is_production_config(
+            Path::new("docker-compose.prod.yml"),
+            &cfg
+        ));
+        assert!(!is_production_config(Path::new("src/production.ts"), &cfg));
+        assert!(!is_production_config(Path::new(".env.local"), &cfg));
+    }
+}
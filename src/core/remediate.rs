@@ -0,0 +1,353 @@
+use crate::core::{Category, Issue, rules};
+use anyhow::{Result, bail};
+use std::fs;
+use std::path::Path;
+
+const REPLACEMENT: &str = "***REMOVED***";
+
+// committed secrets and tracked secret files; everything else is fixed in the working tree
+pub fn supports(issue: &Issue) -> bool {
+    issue.file.is_some()
+        && (issue.category == Category::Secrets
+            || issue.code == rules::ENV_FORBIDDEN_FILE_TRACKED.code
+            || issue.code == rules::ENV_RENAMED_DOTENV_TRACKED.code)
+}
+
+pub fn find<'a>(issues: &'a [Issue], fingerprint: &str) -> Result<&'a Issue> {
+    let issue = find_by_fingerprint(issues, fingerprint, "devguard remediate")?;
+    if !supports(issue) {
+        bail!(
+            "{} ({}) is not a committed secret; follow its remediation instead: {}",
+            issue.fingerprint(),
+            issue.code,
+            issue.remediation
+        );
+    }
+    Ok(issue)
+}
+
+// fingerprints can be abbreviated like commit ids, as long as the prefix is unambiguous.
+// `list_command` is what prints them, for the error when nothing matches
+pub fn find_by_fingerprint<'a>(
+    issues: &'a [Issue],
+    fingerprint: &str,
+    list_command: &str,
+) -> Result<&'a Issue> {
+    let wanted = fingerprint.trim().to_ascii_lowercase();
+    if wanted.len() < 4 {
+        bail!(
+            "fingerprint {:?} is too short (use at least 4 characters)",
+            fingerprint
+        );
+    }
+    let mut matches = issues
+        .iter()
+        .filter(|issue| issue.fingerprint().starts_with(&wanted));
+    let Some(issue) = matches.next() else {
+        bail!(
+            "no finding with fingerprint {} (run `{}` to list them)",
+            fingerprint,
+            list_command
+        );
+    };
+    if matches.any(|other| other.fingerprint() != issue.fingerprint()) {
+        bail!(
+            "fingerprint prefix {} matches more than one finding",
+            fingerprint
+        );
+    }
+    Ok(issue)
+}
+
+pub fn render_list(issues: &[Issue]) -> String {
+    let mut seen = std::collections::HashSet::new();
+    let lines = issues
+        .iter()
+        .filter(|issue| supports(issue) && seen.insert(issue.fingerprint()))
+        .map(|issue| {
+            format!(
+                "{}  {}  {}  {}",
+                issue.fingerprint(),
+                issue.code,
+                issue.location().unwrap_or_default(),
+                issue.title
+            )
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return "no committed secrets found\n".to_string();
+    }
+    format!(
+        "{}\n\nrun `devguard remediate <fingerprint>` for rotation and history-rewrite steps\n",
+        lines.join("\n")
+    )
+}
+
+pub fn render_plan(issue: &Issue, repo_root: &Path) -> String {
+    let file = issue.file.as_deref().unwrap_or_default();
+    let mut out = vec![
+        format!(
+            "remediation plan for {} ({})",
+            issue.fingerprint(),
+            issue.code
+        ),
+        format!(
+            "{} in {}",
+            issue.title,
+            issue.location().unwrap_or_default()
+        ),
+        String::new(),
+        "1. rotate the credential first; rewriting history does not un-leak it".to_string(),
+    ];
+    out.extend(
+        rotation_steps(issue)
+            .iter()
+            .map(|step| format!("   - {}", step)),
+    );
+    out.push(String::new());
+
+    out.push("2. remove it from history, in a fresh mirror clone:".to_string());
+    out.push("     git clone --mirror <remote-url> repo.git && cd repo.git".to_string());
+    if issue.line.is_none() {
+        let name = Path::new(file)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file.to_string());
+        out.push("   with git filter-repo:".to_string());
+        out.push(format!(
+            "     git filter-repo --invert-paths --path {}",
+            shell_quote(file)
+        ));
+        out.push(
+            "   or with BFG Repo-Cleaner (matches the file name in every directory):".to_string(),
+        );
+        out.push(format!("     bfg --delete-files {}", shell_quote(&name)));
+    } else {
+        out.push("   save this line as replacements.txt outside the repository:".to_string());
+        out.push(format!(
+            "     {}==>{}",
+            replacement_pattern(issue, repo_root),
+            REPLACEMENT
+        ));
+        out.push("   with git filter-repo:".to_string());
+        out.push("     git filter-repo --replace-text ../replacements.txt".to_string());
+        out.push(
+            "   or with BFG Repo-Cleaner, which leaves the latest commit alone, so commit the fix first:"
+                .to_string(),
+        );
+        out.push("     bfg --replace-text ../replacements.txt".to_string());
+    }
+    out.push(
+        "     git reflog expire --expire=now --all && git gc --prune=now --aggressive".to_string(),
+    );
+    out.push(String::new());
+
+    out.push("3. publish the rewrite and clean up copies:".to_string());
+    out.push("     git push --force --mirror".to_string());
+    out.push(
+        "   - every collaborator must re-clone; merging an old clone brings the secret back"
+            .to_string(),
+    );
+    out.push("   - forks, open pull requests, and CI caches keep the old commits; on GitHub, ask support to purge cached views of them".to_string());
+    if issue.line.is_some() {
+        out.push(format!(
+            "   - remove the value from {} in the working tree too and load it from the environment",
+            file
+        ));
+        out.push("   - delete replacements.txt; it holds the secret itself".to_string());
+    } else {
+        out.push(format!(
+            "   - add {} to .gitignore so it is not committed again",
+            file
+        ));
+    }
+
+    format!("{}\n", out.join("\n"))
+}
+
+const STRIPE_ROTATION: &[&str] = &[
+    "Stripe dashboard > Developers > API keys: roll the key (pick an expiry for the old one if deploys still use it)",
+    "update the key in every deployment environment, then let the old key expire",
+    "review Developers > Logs for requests made with the old key",
+];
+
+const ROTATION_STEPS: &[(&str, &[&str])] = &[
+    (rules::SECRET_STRIPE_LIVE_PATTERN.code, STRIPE_ROTATION),
+    (rules::SECRET_STRIPE_TEST_PATTERN.code, STRIPE_ROTATION),
+    (
+        rules::SECRET_VERCEL_TOKEN.code,
+        &[
+            "Vercel > Account Settings > Tokens: delete the leaked token",
+            "create a replacement with the narrowest scope and an expiration, and update CI secrets",
+            "check the team audit log for activity from the old token",
+        ],
+    ),
+    (
+        rules::SECRET_AWS_ACCESS_KEY.code,
+        &[
+            "aws iam create-access-key --user-name <user>   # then deploy the new key",
+            "aws iam update-access-key --user-name <user> --access-key-id <leaked AKIA id> --status Inactive",
+            "aws iam delete-access-key --user-name <user> --access-key-id <leaked AKIA id>",
+            "review CloudTrail for calls made with the leaked key id",
+        ],
+    ),
+    (
+        rules::SECRET_SUPABASE_JWT.code,
+        &[
+            "Supabase dashboard > Project Settings > API: generate a new JWT secret, which reissues the anon and service_role keys",
+            "update every client and server that uses either key; existing sessions are signed out",
+        ],
+    ),
+    (
+        rules::SECRET_KUBECONFIG.code,
+        &[
+            "revoke the embedded token or client certificate (delete the service account token secret, or rotate the cluster CA for client certs)",
+            "issue a new kubeconfig to the people and systems that need it",
+        ],
+    ),
+    (
+        rules::SECRET_DOCKER_AUTHS.code,
+        &[
+            "revoke the registry access token or password in the registry's settings",
+            "docker logout <registry> && docker login <registry> with a new token on each machine",
+        ],
+    ),
+    (
+        rules::SECRET_GCLOUD_CREDENTIALS.code,
+        &[
+            "gcloud iam service-accounts keys list --iam-account=<service-account-email>",
+            "gcloud iam service-accounts keys delete <key-id> --iam-account=<service-account-email>",
+            "prefer workload identity federation over downloaded keys for the replacement",
+        ],
+    ),
+    (
+        rules::SECRET_PRIVATE_KEY.code,
+        &[
+            "generate a new key pair and replace the public half wherever it is trusted (authorized_keys, deploy keys, certificates)",
+            "revoke the old certificate or remove the old public key",
+        ],
+    ),
+];
+
+// tracked dotenv files can hold anything, so they get the generic steps
+const GENERIC_ROTATION: &[&str] = &[
+    "rotate every credential in the file with the service that issued it",
+    "update deployment environments with the new values",
+];
+
+fn rotation_steps(issue: &Issue) -> &'static [&'static str] {
+    ROTATION_STEPS
+        .iter()
+        .find(|(code, _)| *code == issue.code)
+        .map_or(GENERIC_ROTATION, |(_, steps)| steps)
+}
+
+// the exact committed value when it can still be read from the working tree; a regex
+// otherwise. filter-repo and BFG both read `regex:` lines and treat anything else literally
+fn replacement_pattern(issue: &Issue, repo_root: &Path) -> String {
+    if issue.code == rules::SECRET_PRIVATE_KEY.code {
+        return "regex:-----BEGIN[A-Z ]*PRIVATE KEY( BLOCK)?-----(?s:.*?)-----END[A-Z ]*PRIVATE KEY( BLOCK)?-----".to_string();
+    }
+    committed_value(issue, repo_root).unwrap_or_else(|| "<the leaked value>".to_string())
+}
+
+fn committed_value(issue: &Issue, repo_root: &Path) -> Option<String> {
+    let (line, column, end_column) = (issue.line?, issue.column?, issue.end_column?);
+    if issue.end_line != Some(line) {
+        return None;
+    }
+    let content = fs::read_to_string(repo_root.join(issue.file.as_deref()?)).ok()?;
+    let text = content.lines().nth(line.checked_sub(1)?)?;
+    let matched = text
+        .chars()
+        .skip(column.checked_sub(1)?)
+        .take(end_column.checked_sub(column)?)
+        .collect::<String>();
+    // assignment matches such as `VERCEL_TOKEN="..."` include the key; keep only the value
+    let value = matched
+        .rsplit(['=', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(['"', '\'']);
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn shell_quote(value: &str) -> String {
+    if value
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '/' | '.' | '_' | '-'))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Severity, Span};
+
+    fn aws_issue() -> Issue {
+        Issue::from_rule(
+            rules::SECRET_AWS_ACCESS_KEY,
+            Severity::Error,
+            "AWS access key pattern detected",
+            "revoke and rotate the key",
+        )
+        .with_file("config/app.js")
+        .with_span(Span {
+            line: 2,
+            column: 14,
+            end_line: 2,
+            end_column: 34,
+        })
+    }
+
+    #[test]
+    fn finds_by_unique_prefix_and_rejects_unsupported_findings() {
+        let secret = aws_issue();
+        let missing = Issue::from_rule(
+            rules::ENV_REQUIRED_VAR_MISSING,
+            Severity::Error,
+            "DATABASE_URL is missing",
+            "add it",
+        );
+        let issues = [secret.clone(), missing.clone()];
+
+        let prefix = &secret.fingerprint()[..8];
+        assert_eq!(find(&issues, prefix).expect("found").code, secret.code);
+        assert!(find(&issues, &missing.fingerprint()).is_err());
+        assert!(find(&issues, "ffff0000ffff0000").is_err());
+        assert!(find(&issues, "ab").is_err());
+    }
+
+    #[test]
+    fn inline_secrets_get_replace_text_and_files_get_removed() {
+        let root = std::env::temp_dir().join(format!("devguard-remediate-{}", std::process::id()));
+        fs::create_dir_all(root.join("config")).expect("dir created");
+        fs::write(
+            root.join("config/app.js"),
+            "// keys\nconst aws = \"AKIA1234567890ABCDEF\";\n",
+        )
+        .expect("file written");
+        let plan = render_plan(&aws_issue(), &root);
+        fs::remove_dir_all(&root).expect("cleanup");
+
+        assert!(plan.contains("AKIA1234567890ABCDEF==>***REMOVED***"));
+        assert!(plan.contains("git filter-repo --replace-text ../replacements.txt"));
+        assert!(plan.contains("aws iam update-access-key"));
+
+        let env_file = Issue::from_rule(
+            rules::ENV_FORBIDDEN_FILE_TRACKED,
+            Severity::Error,
+            "forbidden env file appears tracked",
+            "remove it",
+        )
+        .with_file("apps/web/.env.local");
+        let plan = render_plan(&env_file, &root);
+        assert!(plan.contains("git filter-repo --invert-paths --path apps/web/.env.local"));
+        assert!(plan.contains("bfg --delete-files .env.local"));
+    }
+}
@@ -0,0 +1,450 @@
+use crate::config::{Config, CustomPattern, RuleSetting};
+use crate::core::{Issue, Severity, Span, rules};
+#[cfg(not(feature = "wasm"))]
+use crate::core::{RepoContext, generated};
+#[cfg(not(feature = "wasm"))]
+use crate::utils::fs::{decode_text, relative_path};
+use crate::utils::{glob, sandbox};
+use anyhow::{Context, Result, bail};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_RULES_DIR: &str = "devguard-rules";
+
+struct CompiledPattern<'a> {
+    spec: &'a CustomPattern,
+    regex: Regex,
+    severity: Severity,
+    paths: Vec<Regex>,
+}
+
+impl CompiledPattern<'_> {
+    fn applies_to(&self, rel: &str) -> bool {
+        self.paths.is_empty() || self.paths.iter().any(|glob| glob.is_match(rel))
+    }
+
+    fn issue(&self, content: &str, rel: &str, start: usize, end: usize) -> Issue {
+        let spec = self.spec;
+        let message = spec.message.as_deref().unwrap_or("custom pattern matched");
+        let mut issue = Issue::from_rule(
+            rules::PACK_CUSTOM_PATTERN,
+            self.severity,
+            format!("{}: {}", spec.id, message),
+            spec.remediation
+                .clone()
+                .unwrap_or_else(|| "remove the match or suppress it if it is expected".to_string()),
+        )
+        .with_file(rel.to_string())
+        .with_span(Span::from_offsets(content, start, end));
+        issue.category = spec.scored_category();
+        issue.custom_category = spec.custom_category().map(str::to_string);
+        if let Some(pack) = &spec.pack {
+            issue = issue.with_description(format!("from rule pack {}", pack));
+        }
+        issue
+    }
+}
+
+fn severity(setting: RuleSetting) -> Option<Severity> {
+    match setting {
+        RuleSetting::Off => None,
+        RuleSetting::Error => Some(Severity::Error),
+        RuleSetting::Warning => Some(Severity::Warning),
+        RuleSetting::Info => Some(Severity::Info),
+    }
+}
+
+// config validation has already compiled every regex, so failures here cannot happen
+fn compile(cfg: &Config) -> Vec<CompiledPattern<'_>> {
+    cfg.custom_patterns
+        .iter()
+        .filter(|spec| cfg.category_enabled(spec.scored_category()))
+        .filter_map(|spec| {
+            Some(CompiledPattern {
+                spec,
+                regex: Regex::new(&spec.regex).ok()?,
+                severity: severity(spec.severity)?,
+                paths: spec
+                    .paths
+                    .iter()
+                    .filter_map(|pattern| glob::path_glob(pattern))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn scan_custom_patterns(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let patterns = compile(cfg);
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "custom patterns") {
+        let rel = relative_path(&ctx.repo_root, entry.path());
+        let wanted = patterns
+            .iter()
+            .filter(|pattern| pattern.applies_to(&rel))
+            .collect::<Vec<_>>();
+        if wanted.is_empty() {
+            continue;
+        }
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = decode_text(&bytes);
+        let found = wanted
+            .into_iter()
+            .flat_map(|pattern| {
+                pattern
+                    .regex
+                    .find_iter(&content)
+                    .map(|found| pattern.issue(&content, &rel, found.start(), found.end()))
+            })
+            .collect();
+        issues.extend(generated::apply(
+            cfg.scan.generated_files,
+            generated::generated_reason(&rel, &content),
+            found,
+        ));
+    }
+
+    issues
+}
+
+// like `scan_custom_patterns`, for text that is not a worktree file
+pub fn scan_text(cfg: &Config, rel: &str, content: &str) -> Vec<Issue> {
+    compile(cfg)
+        .iter()
+        .filter(|pattern| pattern.applies_to(rel))
+        .flat_map(|pattern| {
+            pattern
+                .regex
+                .find_iter(content)
+                .map(|found| pattern.issue(content, rel, found.start(), found.end()))
+        })
+        .collect()
+}
+
+// plain entries are checked directly; glob entries pass when any file matches
+#[cfg(not(feature = "wasm"))]
+pub fn check_required_files(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let globs = cfg
+        .required_files
+        .iter()
+        .filter(|file| glob::is_glob(file))
+        .filter_map(|file| Some((file, glob::path_glob(file)?)))
+        .collect::<Vec<_>>();
+    let mut found_globs = vec![false; globs.len()];
+    if !globs.is_empty() {
+        for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "required files") {
+            let rel = relative_path(&ctx.repo_root, entry.path());
+            for (found, (_, glob)) in found_globs.iter_mut().zip(&globs) {
+                *found = *found || glob.is_match(&rel);
+            }
+        }
+    }
+
+    let mut issues = Vec::new();
+    for file in &cfg.required_files {
+        let present = match globs.iter().position(|(pattern, _)| *pattern == file) {
+            Some(idx) => found_globs[idx],
+            None => ctx.repo_root.join(file.trim_start_matches("./")).exists(),
+        };
+        if !present {
+            issues.push(
+                Issue::from_rule(
+                    rules::PACK_REQUIRED_FILE_MISSING,
+                    Severity::Warning,
+                    format!("required file {} is missing", file),
+                    format!("add {} to the repository", file),
+                )
+                .with_file(file.clone()),
+            );
+        }
+    }
+    issues
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureResult {
+    pub path: String,
+    pub expectations: usize,
+    pub failures: Vec<FixtureFailure>,
+}
+
+impl FixtureResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FixtureFailure {
+    pub line: usize,
+    pub pattern: String,
+    pub reason: String,
+}
+
+// `# expect: id` (also `//`, `--`, `;`, `/*`, `<!--`) marks the line it trails, or the next
+// line when it stands alone. several ids may be listed, separated by commas
+static EXPECT_ANNOTATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:#|//|--|;|/\*|<!--)\s*expect:\s*([A-Za-z0-9_.-]+(?:\s*,\s*[A-Za-z0-9_.-]+)*)")
+        .expect("valid regex")
+});
+
+// `devguard rules test`: every pattern runs against every fixture regardless of its path
+// globs or severity, and any match without a matching annotation is a failure too
+pub fn test_fixture(cfg: &Config, path: &str, content: &str) -> FixtureResult {
+    let mut expected = BTreeSet::new();
+    let mut pending = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let Some(caps) = EXPECT_ANNOTATION.captures(line) else {
+            expected.extend(pending.drain(..).map(|id| (line_no, id)));
+            continue;
+        };
+        let ids = caps[1]
+            .split(',')
+            .map(|id| id.trim().to_string())
+            .collect::<Vec<_>>();
+        if line[..caps.get(0).expect("whole match").start()]
+            .trim()
+            .is_empty()
+        {
+            pending.extend(ids);
+        } else {
+            expected.extend(pending.drain(..).map(|id| (line_no, id)));
+            expected.extend(ids.into_iter().map(|id| (line_no, id)));
+        }
+    }
+
+    let mut matched = BTreeSet::new();
+    for spec in &cfg.custom_patterns {
+        let Ok(regex) = Regex::new(&spec.regex) else {
+            continue;
+        };
+        for found in regex.find_iter(content) {
+            let line_no = content[..found.start()].matches('\n').count() + 1;
+            matched.insert((line_no, spec.id.clone()));
+        }
+    }
+
+    let known = cfg
+        .custom_patterns
+        .iter()
+        .map(|spec| spec.id.as_str())
+        .collect::<BTreeSet<_>>();
+    let mut failures = Vec::new();
+    for (line, id) in &expected {
+        let reason = if !known.contains(id.as_str()) {
+            "no custom pattern has this id"
+        } else if !matched.contains(&(*line, id.clone())) {
+            "expected a match, found none"
+        } else {
+            continue;
+        };
+        failures.push(FixtureFailure {
+            line: *line,
+            pattern: id.clone(),
+            reason: reason.to_string(),
+        });
+    }
+    for (line, id) in matched.difference(&expected) {
+        failures.push(FixtureFailure {
+            line: *line,
+            pattern: id.clone(),
+            reason: "matched without an expect annotation".to_string(),
+        });
+    }
+    failures.sort_by(|a, b| (a.line, &a.pattern).cmp(&(b.line, &b.pattern)));
+
+    FixtureResult {
+        path: path.to_string(),
+        expectations: expected.len(),
+        failures,
+    }
+}
+
+// fixture arguments may be files or directories; directories are walked with the scan excludes
+#[cfg(not(feature = "wasm"))]
+pub fn run_fixtures(cfg: &Config, paths: &[PathBuf]) -> Result<Vec<FixtureResult>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(
+                crate::utils::fs::walk_files(path, path, &cfg.scan).map(|entry| entry.into_path()),
+            );
+        } else if path.is_file() {
+            files.push(path.clone());
+        } else {
+            bail!("fixture not found: {}", path.display());
+        }
+    }
+    if files.is_empty() {
+        bail!("no fixture files found");
+    }
+
+    files
+        .iter()
+        .map(|file| {
+            let bytes =
+                fs::read(file).with_context(|| format!("failed reading {}", file.display()))?;
+            Ok(test_fixture(
+                cfg,
+                &file.display().to_string(),
+                &decode_text(&bytes),
+            ))
+        })
+        .collect()
+}
+
+// `devguard rules new <name>`: a commented starting point for a pack
+pub fn scaffold(dir: &Path, name: &str) -> Result<PathBuf> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+    {
+        bail!(
+            "rule pack name {:?} may only contain letters, digits, '-', and '_'",
+            name
+        );
+    }
+    let path = dir.join(format!("{}.toml", name));
+    sandbox::ensure_allowed(&format!("writing {}", path.display()))?;
+    if path.exists() {
+        bail!(
+            "refusing to overwrite existing rule pack: {}",
+            path.display()
+        );
+    }
+    fs::create_dir_all(dir).with_context(|| format!("failed creating {}", dir.display()))?;
+    fs::write(&path, pack_template(name))
+        .with_context(|| format!("failed writing {}", path.display()))?;
+    Ok(path)
+}
+
+fn pack_template(name: &str) -> String {
+    format!(
+        r#"# devguard rule pack "{name}"
+# every *.toml file in rules_dir is merged into the config at load time
+
+# files every repository using this pack must have (DG_PACK_002); globs allowed
+required_files = [
+    # "SECURITY.md",
+]
+
+# severity overrides for any rule id (`devguard rules list`); the config file's
+# own [rules] table wins over packs
+[rules]
+# DG_GIT_002 = "off"
+
+# custom patterns report DG_PACK_001 findings wherever the regex matches
+[[custom_patterns]]
+id = "{name}-example-token"
+regex = '{name}_tok_[A-Za-z0-9]{{32}}'
+message = "example token committed"
+severity = "error"          # error, warning, info, or off
+category = "secrets"        # a built-in category, or your own label such as "Internal"
+paths = ["src/**/*.ts"]     # optional globs; every scanned file when empty
+remediation = "revoke the token and load it from the environment"
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Category;
+
+    #[test]
+    fn scaffolded_pack_parses_as_config() {
+        let cfg = toml::from_str::<Config>(&pack_template("acme")).expect("template parses");
+        assert_eq!(cfg.custom_patterns[0].id, "acme-example-token");
+        assert_eq!(cfg.custom_patterns[0].severity, RuleSetting::Error);
+
+        let patterns = compile(&cfg);
+        assert!(patterns[0].applies_to("src/lib/api.ts"));
+        assert!(!patterns[0].applies_to("docs/api.md"));
+        let content = format!("const t = \"acme_tok_{}\";\n", "a".repeat(32));
+        let found = patterns[0].regex.find(&content).expect("matches");
+        let issue = patterns[0].issue(&content, "src/lib/api.ts", found.start(), found.end());
+        assert_eq!(issue.code, "DG_PACK_001");
+        assert_eq!(issue.title, "acme-example-token: example token committed");
+        assert_eq!(issue.column, Some(12));
+    }
+
+    #[test]
+    fn fixtures_report_missed_and_unexpected_matches() {
+        let cfg = toml::from_str::<Config>(
+            "[[custom_patterns]]\nid = \"acme-token\"\nregex = 'acme_[a-z]{4}'\nseverity = \"off\"\n",
+        )
+        .expect("config parses");
+        let fixture = "\
+token = \"acme_abcd\"  # expect: acme-token
+# expect: acme-token
+token = \"acme_efgh\"
+token = \"acme_none\"
+// expect: acme-token
+token = \"clean\"
+x = 1 # expect: other-rule
+";
+        let result = test_fixture(&cfg, "fixture.py", fixture);
+        assert_eq!(result.expectations, 4);
+        assert!(!result.passed());
+        let failures = result
+            .failures
+            .iter()
+            .map(|failure| (failure.line, failure.reason.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            failures,
+            vec![
+                (4, "matched without an expect annotation"),
+                (6, "expected a match, found none"),
+                (7, "no custom pattern has this id"),
+            ]
+        );
+
+        let clean = test_fixture(&cfg, "ok.py", "a = \"acme_abcd\" # expect: acme-token\n");
+        assert!(clean.passed());
+    }
+
+    #[test]
+    fn custom_category_labels_keep_a_builtin_score_bucket() {
+        let cfg = toml::from_str::<Config>(
+            "[[custom_patterns]]\nid = \"a\"\nregex = 'aaa'\ncategory = \"Internal Tools\"\n\n[[custom_patterns]]\nid = \"b\"\nregex = 'bbb'\ncategory = \"Git\"\n\n[[custom_patterns]]\nid = \"c\"\nregex = 'ccc'\ncategory = \"env\"\n",
+        )
+        .expect("config parses");
+        let issues = scan_text(&cfg, "src/main.rs", "aaa bbb ccc");
+        let categories = issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.category,
+                    issue.category_label(),
+                    issue.category_slug(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            categories,
+            vec![
+                (
+                    Category::Secrets,
+                    "Internal Tools",
+                    "internal-tools".to_string()
+                ),
+                (Category::Git, "Git", "git".to_string()),
+                (Category::Env, "Env", "env".to_string()),
+            ]
+        );
+    }
+}
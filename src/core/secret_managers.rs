@@ -0,0 +1,171 @@
+use crate::config::{Config, SecretManager};
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs::{decode_text, relative_path};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+const DOPPLER_FILES: &[&str] = &["doppler.yaml", ".doppler.yaml"];
+
+static VAULT_TEMPLATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\{\{-?\s*(with\s+)?secret\s+""#).expect("valid vault template regex")
+});
+static TERRAFORM_CLOUD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^\s*(cloud\s*\{|backend\s+"remote"\s*\{)"#)
+        .expect("valid terraform cloud regex")
+});
+
+// where each secret manager shows up in the repo, as a short piece of evidence per manager:
+// its config file, a script that runs under it, a reference in a dotenv file, or a template
+pub fn detect(ctx: &RepoContext, cfg: &Config) -> BTreeMap<SecretManager, String> {
+    let mut found = BTreeMap::new();
+
+    for name in DOPPLER_FILES {
+        if ctx.repo_root.join(name).is_file() {
+            found
+                .entry(SecretManager::Doppler)
+                .or_insert_with(|| name.to_string());
+        }
+    }
+    for package in &ctx.packages {
+        for (needle, manager) in [
+            ("doppler run", SecretManager::Doppler),
+            ("op run", SecretManager::OnePassword),
+            ("op inject", SecretManager::OnePassword),
+            ("vault agent", SecretManager::Vault),
+        ] {
+            if package.has_script_containing(needle) {
+                found
+                    .entry(manager)
+                    .or_insert_with(|| format!("a package.json script runs `{}`", needle));
+            }
+        }
+    }
+    // `op run --env-file` resolves op:// references, so the dotenv file holds no secret
+    if let Some(var) = ctx
+        .dotenv_vars
+        .iter()
+        .find(|var| var.value.starts_with("op://"))
+    {
+        found
+            .entry(SecretManager::OnePassword)
+            .or_insert_with(|| format!("{}:{} references op://", var.file, var.line));
+    }
+
+    let wanted = cfg
+        .env
+        .managed
+        .values()
+        .filter(|manager| {
+            matches!(
+                manager,
+                SecretManager::Vault | SecretManager::TerraformCloud
+            )
+        })
+        .any(|manager| !found.contains_key(manager));
+    if !wanted {
+        return found;
+    }
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "secret manager detection") {
+        let manager = match entry.path().extension().and_then(|ext| ext.to_str()) {
+            Some("ctmpl" | "tpl" | "hcl") => SecretManager::Vault,
+            Some("tf") => SecretManager::TerraformCloud,
+            _ => continue,
+        };
+        if found.contains_key(&manager) {
+            continue;
+        }
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = decode_text(&bytes);
+        let rel = relative_path(&ctx.repo_root, entry.path());
+        let evidence = match manager {
+            SecretManager::Vault if VAULT_TEMPLATE_RE.is_match(&content) => {
+                format!("{} reads Vault secrets", rel)
+            }
+            SecretManager::TerraformCloud if TERRAFORM_CLOUD_RE.is_match(&content) => {
+                format!("{} uses a Terraform Cloud workspace", rel)
+            }
+            _ => continue,
+        };
+        found.insert(manager, evidence);
+    }
+
+    found
+}
+
+// one finding per manager named in [env.managed] that the repo shows no sign of using
+pub fn check_managed_sources(
+    cfg: &Config,
+    detected: &BTreeMap<SecretManager, String>,
+) -> Vec<Issue> {
+    let mut by_manager: BTreeMap<SecretManager, Vec<&str>> = BTreeMap::new();
+    for (key, manager) in &cfg.env.managed {
+        by_manager.entry(*manager).or_default().push(key);
+    }
+
+    by_manager
+        .into_iter()
+        .filter(|(manager, _)| !detected.contains_key(manager))
+        .map(|(manager, keys)| {
+            Issue::from_rule(
+                rules::ENV_MANAGER_NOT_FOUND,
+                Severity::Info,
+                format!("{} not found for managed env vars", manager),
+                format!(
+                    "check that {} still provides {}, or update [env.managed]",
+                    manager,
+                    keys.join(", ")
+                ),
+            )
+            .with_description(format!(
+                "[env.managed] assigns {} to {}, but no {} config, script, or reference was found in the repository",
+                keys.join(", "),
+                manager,
+                manager
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_vault_templates_and_terraform_cloud_blocks() {
+        assert!(VAULT_TEMPLATE_RE.is_match(
+            "{{ with secret \"secret/data/app\" }}DATABASE_URL={{ .Data.data.url }}{{ end }}"
+        ));
+        assert!(VAULT_TEMPLATE_RE.is_match("{{- secret \"kv/app\" -}}"));
+        assert!(!VAULT_TEMPLATE_RE.is_match("{{ .Values.secretName }}"));
+
+        assert!(
+            TERRAFORM_CLOUD_RE.is_match("terraform {\n  cloud {\n    organization = \"acme\"\n")
+        );
+        assert!(TERRAFORM_CLOUD_RE.is_match("terraform {\n  backend \"remote\" {\n"));
+        assert!(!TERRAFORM_CLOUD_RE.is_match("terraform {\n  backend \"s3\" {\n"));
+    }
+
+    #[test]
+    fn reports_managers_with_no_trace_in_the_repo() {
+        let mut cfg = Config::default();
+        cfg.env.managed = BTreeMap::from([
+            ("DATABASE_URL".to_string(), SecretManager::Doppler),
+            ("STRIPE_SECRET_KEY".to_string(), SecretManager::Vault),
+            ("SENTRY_DSN".to_string(), SecretManager::Vault),
+        ]);
+        let detected = BTreeMap::from([(SecretManager::Doppler, "doppler.yaml".to_string())]);
+
+        let issues = check_managed_sources(&cfg, &detected);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "DG_ENV_016");
+        assert_eq!(issues[0].title, "Vault not found for managed env vars");
+        assert!(
+            issues[0]
+                .remediation
+                .contains("SENTRY_DSN, STRIPE_SECRET_KEY")
+        );
+    }
+}
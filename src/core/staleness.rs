@@ -0,0 +1,222 @@
+use crate::config::Config;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs::{self as fs_utils, relative_path};
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAY: u64 = 24 * 60 * 60;
+
+pub fn check_staleness(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let mut issues = check_example_files(ctx, cfg, now);
+    issues.extend(check_migrations(ctx, cfg));
+    issues
+}
+
+// tracked paths use the last commit that touched them, so a fresh clone does not look
+// brand new; untracked ones (dotenv files, usually) fall back to modification times
+fn last_changed(ctx: &RepoContext, cfg: &Config, paths: &[PathBuf]) -> Vec<Option<u64>> {
+    ctx.last_commit_times(paths)
+        .into_iter()
+        .zip(paths)
+        .map(|(committed, path)| committed.or_else(|| modified(ctx, cfg, path)))
+        .collect()
+}
+
+fn modified(ctx: &RepoContext, cfg: &Config, path: &Path) -> Option<u64> {
+    if path.is_dir() {
+        return ctx
+            .walk_files(path, &cfg.scan, "staleness checks")
+            .filter_map(|entry| mtime(entry.path()))
+            .max();
+    }
+    mtime(path)
+}
+
+fn mtime(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs())
+}
+
+// an old example file is only a problem once the dotenv files moved on without it
+fn check_example_files(ctx: &RepoContext, cfg: &Config, now: u64) -> Vec<Issue> {
+    let dotenv_files = ctx
+        .dotenv_vars
+        .iter()
+        .map(|var| var.file.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    if dotenv_files.is_empty() {
+        return Vec::new();
+    }
+    let dotenv_paths = dotenv_files
+        .iter()
+        .map(|file| ctx.repo_root.join(file))
+        .collect::<Vec<_>>();
+    let Some((newest_dotenv, dotenv_file)) = last_changed(ctx, cfg, &dotenv_paths)
+        .into_iter()
+        .zip(&dotenv_files)
+        .filter_map(|(time, file)| Some((time?, file)))
+        .max()
+    else {
+        return Vec::new();
+    };
+
+    let examples = cfg
+        .env
+        .example_files
+        .iter()
+        .map(|file| ctx.repo_root.join(file))
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    let mut issues = Vec::new();
+    for (path, changed) in examples.iter().zip(last_changed(ctx, cfg, &examples)) {
+        let Some(changed) = changed else {
+            continue;
+        };
+        let age_days = now.saturating_sub(changed) / DAY;
+        if age_days < cfg.staleness.example_max_age_days || newest_dotenv <= changed {
+            continue;
+        }
+        let differing = differing_keys(path, &ctx.dotenv_keys);
+        if differing == 0 {
+            continue;
+        }
+        let rel = relative_path(&ctx.repo_root, path);
+        issues.push(
+            Issue::from_rule(
+                rules::ENV_EXAMPLE_OUTDATED,
+                Severity::Warning,
+                format!(
+                    "{} not updated in {} days while {} changed since",
+                    rel, age_days, dotenv_file
+                ),
+                format!(
+                    "bring {} in line with the current dotenv keys, or raise staleness.example_max_age_days",
+                    rel
+                ),
+            )
+            .with_file(rel)
+            .with_description(format!(
+                "{} key(s) differ between the example and the dotenv files",
+                differing
+            )),
+        );
+    }
+    issues
+}
+
+fn differing_keys(example: &Path, dotenv_keys: &HashSet<String>) -> usize {
+    let Ok(content) = fs_utils::read_text(example) else {
+        return 0;
+    };
+    let example_keys = fs_utils::parse_dotenv(&content)
+        .into_iter()
+        .map(|entry| entry.key)
+        .collect::<HashSet<_>>();
+    example_keys.symmetric_difference(dotenv_keys).count()
+}
+
+// compares the newest change under any migrations directory with the newest change to
+// any schema source; both sides only count when they exist
+fn check_migrations(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let staleness = &cfg.staleness;
+    let existing = |entries: &[String], dirs_only: bool| {
+        entries
+            .iter()
+            .map(|entry| ctx.repo_root.join(entry.trim_end_matches('/')))
+            .filter(|path| {
+                if dirs_only {
+                    path.is_dir()
+                } else {
+                    path.exists()
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let migrations = existing(&staleness.migrations_dirs, true);
+    let schemas = existing(&staleness.schema_paths, false);
+    if migrations.is_empty() || schemas.is_empty() {
+        return Vec::new();
+    }
+
+    let newest = |paths: &[PathBuf]| {
+        last_changed(ctx, cfg, paths)
+            .into_iter()
+            .zip(paths)
+            .filter_map(|(time, path)| Some((time?, relative_path(&ctx.repo_root, path))))
+            .max()
+    };
+    let (Some((migrated, migrations_dir)), Some((schema_changed, schema))) =
+        (newest(&migrations), newest(&schemas))
+    else {
+        return Vec::new();
+    };
+    let grace = staleness.migrations_grace_days * DAY;
+    if schema_changed <= migrated + grace {
+        return Vec::new();
+    }
+
+    vec![
+        Issue::from_rule(
+            rules::GIT_MIGRATIONS_BEHIND_SCHEMA,
+            Severity::Warning,
+            format!(
+                "{} changed {} days after the newest migration in {}",
+                schema,
+                (schema_changed - migrated) / DAY,
+                migrations_dir
+            ),
+            "generate a migration for the schema change, or raise staleness.migrations_grace_days",
+        )
+        .with_file(schema),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn touch(path: &Path, content: &str, days_ago: u64) {
+        fs::create_dir_all(path.parent().expect("parent")).expect("dir created");
+        fs::write(path, content).expect("file written");
+        let time = SystemTime::now() - Duration::from_secs(days_ago * DAY);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(time))
+            .expect("mtime set");
+    }
+
+    #[test]
+    fn stale_example_and_lagging_migrations_are_reported() {
+        let root = std::env::temp_dir().join(format!("devguard-stale-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        touch(&root.join(".env.example"), "DATABASE_URL=\n", 400);
+        touch(&root.join(".env"), "DATABASE_URL=x\nSTRIPE_KEY=y\n", 3);
+        touch(&root.join("prisma/migrations/001/migration.sql"), "", 90);
+        touch(&root.join("prisma/schema.prisma"), "model User {}\n", 2);
+
+        let cfg = Config::default();
+        let ctx = RepoContext::build(&root, &cfg).expect("context");
+        let issues = check_staleness(&ctx, &cfg);
+        fs::remove_dir_all(&root).expect("cleanup");
+
+        let codes = issues.iter().map(|issue| issue.code).collect::<Vec<_>>();
+        assert_eq!(codes, ["DG_ENV_007", "DG_GIT_013"]);
+        assert_eq!(
+            issues[0].title,
+            ".env.example not updated in 400 days while .env changed since"
+        );
+        assert_eq!(
+            issues[1].title,
+            "prisma/schema.prisma changed 88 days after the newest migration in prisma/migrations"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use crate::core::Span;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::path::Path;
+
+// lockfiles are machine-written and full of `integrity`/`resolved` noise
+const SKIPPED_FILES: [&str; 3] = ["package-lock.json", "pnpm-lock.yaml", "composer.lock"];
+
+static SECRET_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(password|passwd|pwd|secret|token|api[_-]?key|access[_-]?key|private[_-]?key)")
+        .expect("valid secret key regex")
+});
+// keys that name something about a secret rather than holding it: `token_url`, `password_min_length`
+static DESCRIPTIVE_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)[_.-]?(url|uri|endpoint|path|file|name|id|env|var|type|length|len|policy|ttl|expiry|expires|expiration|header|prefix|field|count|version|enabled|required)$")
+        .expect("valid descriptive key regex")
+});
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(\$\{?[A-Za-z0-9_]+\}?|\{\{.*\}\}|<[^>]*>|%\(?[A-Za-z0-9_]+\)?s?|(env|vault|ssm|secretsmanager|op|arn|ref|secret)[:+].*|x{3,}|\*{3,}|\.{3,}|(change|replace)[_-]?me.*|your[_-].*|.*(example|placeholder|dummy|sample|redacted|fixme|todo).*|none|null|nil|undefined|true|false)$")
+        .expect("valid placeholder regex")
+});
+// enum-like values such as `bearer` or `client_credentials`
+static WORD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z]+([_-][a-z]+)*$").expect("valid word regex"));
+static FILE_PATH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\.{1,2}/|~/|/)?[\w.-]+(/[\w.-]+)*\.[A-Za-z0-9]{1,5}$|^(\.{1,2}|~)?/[\w./-]+$")
+        .expect("valid file path regex")
+});
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z][a-z0-9+.-]*://").expect("valid url regex"));
+static URL_CREDENTIALS_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z][a-z0-9+.-]*://[^/@\s]+:[^/@\s]+@").expect("valid url regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+    Toml,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuredHit {
+    pub key_path: String,
+    pub value: String,
+    pub span: Option<Span>,
+}
+
+fn format_of(path: &Path) -> Option<Format> {
+    let name = path.file_name()?.to_str()?;
+    if SKIPPED_FILES.contains(&name) {
+        return None;
+    }
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "json" => Some(Format::Json),
+        "yaml" | "yml" => Some(Format::Yaml),
+        "toml" => Some(Format::Toml),
+        _ => None,
+    }
+}
+
+// values under secret-looking keys in json, yaml, and toml files; files that do not parse
+// are left to the regex scan
+pub fn scan(path: &Path, content: &str) -> Vec<StructuredHit> {
+    let Some(format) = format_of(path) else {
+        return Vec::new();
+    };
+    let documents = match format {
+        Format::Json => serde_json::from_str::<Value>(content).into_iter().collect(),
+        Format::Toml => toml::from_str::<Value>(content).into_iter().collect(),
+        Format::Yaml => serde_yaml::Deserializer::from_str(content)
+            .map_while(|document| {
+                <serde_yaml::Value as serde::Deserialize>::deserialize(document).ok()
+            })
+            .map(yaml_to_json)
+            .collect::<Vec<_>>(),
+    };
+
+    let mut found = Vec::new();
+    for document in &documents {
+        collect(document, &mut String::new(), &mut found);
+    }
+    let mut searched_from = 0;
+    found
+        .into_iter()
+        .map(|(key_path, key, value)| {
+            let span = locate(content, &key, &value, &mut searched_from);
+            StructuredHit {
+                key_path,
+                value,
+                span,
+            }
+        })
+        .collect()
+}
+
+fn collect(value: &Value, path: &mut String, found: &mut Vec<(String, String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                if let Value::String(text) = child
+                    && is_secret_key(key)
+                    && looks_like_secret(text)
+                {
+                    found.push((path.clone(), key.clone(), text.clone()));
+                }
+                collect(child, path, found);
+                path.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (idx, child) in items.iter().enumerate() {
+                let len = path.len();
+                path.push_str(&format!("[{}]", idx));
+                collect(child, path, found);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn is_secret_key(key: &str) -> bool {
+    SECRET_KEY_RE.is_match(key) && !DESCRIPTIVE_KEY_RE.is_match(key)
+}
+
+pub fn is_placeholder(value: &str) -> bool {
+    PLACEHOLDER_RE.is_match(value.trim())
+}
+
+pub fn looks_like_secret(value: &str) -> bool {
+    let value = value.trim();
+    if value.chars().count() < 6 || value.chars().any(char::is_whitespace) {
+        return false;
+    }
+    if URL_RE.is_match(value) {
+        return URL_CREDENTIALS_RE.is_match(value);
+    }
+    !(PLACEHOLDER_RE.is_match(value) || WORD_RE.is_match(value) || FILE_PATH_RE.is_match(value))
+}
+
+// the value's first occurrence, after the previous hit, on a line that also names the key;
+// values that only appear escaped or folded stay unlocated
+fn locate(content: &str, key: &str, value: &str, searched_from: &mut usize) -> Option<Span> {
+    for (offset, _) in content[*searched_from..].match_indices(value) {
+        let start = *searched_from + offset;
+        let line_start = content[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = content[start..]
+            .find('\n')
+            .map_or(content.len(), |idx| start + idx);
+        if content[line_start..line_end].contains(key) {
+            *searched_from = start + value.len();
+            return Some(Span::from_offsets(content, start, start + value.len()));
+        }
+    }
+    None
+}
+
+// yaml allows non-string keys (`8080: web`); they are printed as they would be written
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut map = Map::new();
+            for (key, child) in mapping {
+                let key = match key {
+                    serde_yaml::Value::String(key) => key,
+                    other => serde_yaml::to_string(&other)
+                        .map(|text| text.trim_end().to_string())
+                        .unwrap_or_default(),
+                };
+                map.insert(key, yaml_to_json(child));
+            }
+            Value::Object(map)
+        }
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+        serde_yaml::Value::String(text) => Value::String(text),
+        // only strings can hold a secret here, so other scalars need no exact conversion
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(file: &str, content: &str) -> Vec<(String, Option<usize>)> {
+        scan(Path::new(file), content)
+            .into_iter()
+            .map(|hit| (hit.key_path, hit.span.map(|span| span.line)))
+            .collect()
+    }
+
+    #[test]
+    fn reports_key_paths_in_each_format() {
+        let yaml = "database:\n  host: db.internal\n  credentials:\n    user: app\n    password: \"s3cr3t-Pa55\"\n---\nservers:\n  - name: a\n    api_key: 9f8e7d6c5b4a\n";
+        assert_eq!(
+            paths("config/app.yaml", yaml),
+            [
+                ("database.credentials.password".to_string(), Some(5)),
+                ("servers[0].api_key".to_string(), Some(9)),
+            ]
+        );
+
+        let json = "{\n  \"stripe\": { \"webhookSecret\": \"whsec_8hF2kQ9x\" }\n}\n";
+        assert_eq!(
+            paths("settings.json", json),
+            [("stripe.webhookSecret".to_string(), Some(2))]
+        );
+
+        let toml = "[smtp]\nhost = \"mail\"\nPASSWORD = \"hunter2!x\"\n";
+        assert_eq!(
+            paths("deploy.toml", toml),
+            [("smtp.PASSWORD".to_string(), Some(3))]
+        );
+    }
+
+    #[test]
+    fn skips_placeholders_references_and_descriptive_keys() {
+        let yaml = "password: ${DB_PASSWORD}\ntoken: changeme\napi_key: <your-api-key>\nsecret: vault:kv/app#secret\ntoken_url: https://auth.example.com/token\nclient_secret_file: ./secrets/client.json\ngrant: client_credentials\npassword_min_length: \"12345678\"\ntoken_type: bearer_token\nlabel_password: Enter your password\n";
+        assert!(paths("app.yml", yaml).is_empty());
+        assert!(paths("package-lock.json", "{\"token\": \"abc123def456\"}").is_empty());
+        assert_eq!(
+            paths("db.yml", "url_secret: postgres://app:pw1234@db/app\n"),
+            [("url_secret".to_string(), Some(1))]
+        );
+    }
+}
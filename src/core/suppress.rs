@@ -0,0 +1,472 @@
+use crate::config::{Config, Suppression};
+use crate::core::{Issue, Severity, rules};
+use crate::utils::date::Date;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, Value, value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mechanism {
+    Suppression,
+    RuleOff,
+    Allowlist,
+}
+
+impl Mechanism {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Suppression => "suppression",
+            Self::RuleOff => "rule off",
+            Self::Allowlist => "allowlist",
+        }
+    }
+}
+
+// a finding the run made and then hid; the report lists these so an audit can see what
+// was left out and on whose say-so
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressedFinding {
+    pub code: String,
+    pub title: String,
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub mechanism: Mechanism,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
+impl SuppressedFinding {
+    pub fn new(issue: &Issue, mechanism: Mechanism, reason: impl Into<String>) -> Self {
+        Self {
+            code: issue.code.to_string(),
+            title: issue.title.clone(),
+            severity: issue.severity,
+            file: issue.file.clone(),
+            line: issue.line,
+            mechanism,
+            owner: None,
+            reason: Some(reason.into()),
+            expires: None,
+        }
+    }
+
+    pub fn location(&self) -> Option<String> {
+        let file = self.file.as_deref()?;
+        Some(match self.line {
+            Some(line) => format!("{}:{}", file, line),
+            None => file.to_string(),
+        })
+    }
+}
+
+// returns the issues still reported and the ones an active suppression hid
+pub fn apply_suppressions(
+    issues: Vec<Issue>,
+    suppressions: &[Suppression],
+    today: Date,
+) -> (Vec<Issue>, Vec<SuppressedFinding>) {
+    if suppressions.is_empty() {
+        return (issues, Vec::new());
+    }
+
+    let mut kept = Vec::with_capacity(issues.len());
+    let mut hidden = Vec::new();
+    let mut expired_notices = Vec::new();
+
+    for issue in issues {
+        let Some(suppression) = suppressions
+            .iter()
+            .find(|suppression| matches_issue(suppression, &issue))
+        else {
+            kept.push(issue);
+            continue;
+        };
+
+        let expired_on = suppression
+            .expires
+            .as_deref()
+            .and_then(Date::parse)
+            .filter(|expires| *expires < today);
+        let Some(expired_on) = expired_on else {
+            hidden.push(SuppressedFinding {
+                owner: suppression.owner.clone(),
+                reason: suppression.reason.clone(),
+                expires: suppression.expires.clone(),
+                ..SuppressedFinding::new(&issue, Mechanism::Suppression, "")
+            });
+            continue;
+        };
+
+        expired_notices.push(expired_notice(suppression, &issue, expired_on));
+        kept.push(issue);
+    }
+
+    kept.extend(expired_notices);
+    (kept, hidden)
+}
+
+fn matches_issue(suppression: &Suppression, issue: &Issue) -> bool {
+    if !suppression.rule.eq_ignore_ascii_case(issue.code) {
+        return false;
+    }
+    if let Some(fingerprint) = &suppression.fingerprint
+        && !issue
+            .fingerprint()
+            .starts_with(&fingerprint.trim().to_ascii_lowercase())
+    {
+        return false;
+    }
+
+    let Some(path) = suppression.path.as_deref() else {
+        return true;
+    };
+    let Some(file) = issue.file.as_deref() else {
+        return false;
+    };
+
+    let path = path.trim_start_matches("./").trim_end_matches('/');
+    file == path || file.starts_with(&format!("{}/", path))
+}
+
+fn expired_notice(suppression: &Suppression, issue: &Issue, expired_on: Date) -> Issue {
+    let mut details = vec![format!("suppressed issue: {}", issue.title)];
+    if let Some(owner) = &suppression.owner {
+        details.push(format!("owner: {}", owner));
+    }
+    if let Some(reason) = &suppression.reason {
+        details.push(format!("reason: {}", reason));
+    }
+
+    let mut notice = Issue::from_rule(
+        rules::SUPPRESSION_EXPIRED,
+        Severity::Warning,
+        format!("suppression for {} expired on {}", issue.code, expired_on),
+        "fix the underlying issue or renew the suppression after review",
+    )
+    .with_description(details.join("; "));
+    if let Some(file) = &issue.file {
+        notice = notice.with_file(file.clone());
+    }
+    if let Some(line) = issue.line {
+        notice = notice.with_line(line);
+    }
+    notice
+}
+
+// the entry `devguard ignore add` writes: this one finding, in this file
+pub fn for_issue(
+    issue: &Issue,
+    reason: String,
+    owner: Option<String>,
+    expires: Option<Date>,
+) -> Suppression {
+    Suppression {
+        rule: issue.code.to_string(),
+        path: issue.file.clone(),
+        fingerprint: Some(issue.fingerprint()),
+        reason: Some(reason),
+        owner,
+        expires: expires.map(|date| date.to_string()),
+    }
+}
+
+// `30d`, `6w`, or a YYYY-MM-DD date
+pub fn parse_expires(raw: &str, today: Date) -> Result<Date> {
+    let raw = raw.trim();
+    if let Some(date) = Date::parse(raw) {
+        return Ok(date);
+    }
+    let days = match (raw.strip_suffix('d'), raw.strip_suffix('w')) {
+        (Some(days), _) => days.parse::<i64>().ok(),
+        (_, Some(weeks)) => weeks.parse::<i64>().ok().map(|weeks| weeks * 7),
+        _ => None,
+    };
+    match days {
+        Some(days) if days > 0 => Ok(today.add_days(days)),
+        _ => bail!(
+            "invalid expiry {:?} (use a number of days like 30d, weeks like 6w, or YYYY-MM-DD)",
+            raw
+        ),
+    }
+}
+
+// appends a `[[suppressions]]` entry and leaves the rest of the file as written
+pub fn append_suppression(content: &str, suppression: &Suppression) -> Result<String> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .context("failed parsing config")?;
+    let mut entry = Table::new();
+    entry.insert("rule", value(suppression.rule.as_str()));
+    for (key, field) in [
+        ("path", &suppression.path),
+        ("fingerprint", &suppression.fingerprint),
+        ("reason", &suppression.reason),
+        ("owner", &suppression.owner),
+        ("expires", &suppression.expires),
+    ] {
+        if let Some(field) = field {
+            entry.insert(key, value(field.as_str()));
+        }
+    }
+    match doc
+        .entry("suppressions")
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+    {
+        Item::ArrayOfTables(entries) => entries.push(entry),
+        Item::Value(Value::Array(entries)) => entries.push(entry.into_inline_table()),
+        _ => bail!("`suppressions` in the config is not an array"),
+    }
+    let content = doc.to_string();
+    toml::from_str::<Config>(&content).context("the updated config does not parse")?;
+    Ok(content)
+}
+
+// `id` is a position from `devguard ignore list` or a fingerprint prefix of at least four
+// characters; returns the new file and the entry taken out
+pub fn remove_suppression(content: &str, id: &str) -> Result<(String, Suppression)> {
+    let suppressions = toml::from_str::<Config>(content)
+        .context("failed parsing config")?
+        .suppressions;
+    let id = id.trim().trim_start_matches('#');
+    let index = match id.parse::<usize>() {
+        Ok(position) if id.len() < 4 => position
+            .checked_sub(1)
+            .filter(|index| *index < suppressions.len())
+            .with_context(|| format!("there is no suppression #{}", id))?,
+        _ => {
+            let wanted = id.to_ascii_lowercase();
+            if wanted.len() < 4 {
+                bail!("{:?} is neither a list position nor a fingerprint", id);
+            }
+            let mut found = suppressions.iter().enumerate().filter(|(_, suppression)| {
+                suppression
+                    .fingerprint
+                    .as_deref()
+                    .is_some_and(|fingerprint| fingerprint.starts_with(&wanted))
+            });
+            let Some((index, _)) = found.next() else {
+                bail!("no suppression has fingerprint {}", id);
+            };
+            if found.next().is_some() {
+                bail!(
+                    "fingerprint prefix {} matches more than one suppression",
+                    id
+                );
+            }
+            index
+        }
+    };
+
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .context("failed parsing config")?;
+    let now_empty = match doc.get_mut("suppressions") {
+        Some(Item::ArrayOfTables(entries)) => {
+            entries.remove(index);
+            entries.is_empty()
+        }
+        Some(Item::Value(Value::Array(entries))) => {
+            entries.remove(index);
+            entries.is_empty()
+        }
+        _ => bail!("`suppressions` in the config is not an array"),
+    };
+    if now_empty {
+        doc.remove("suppressions");
+    }
+    Ok((doc.to_string(), suppressions[index].clone()))
+}
+
+pub fn render_suppressions(suppressions: &[Suppression], today: Date) -> String {
+    if suppressions.is_empty() {
+        return "no suppressions\n".to_string();
+    }
+    let mut lines = Vec::new();
+    for (idx, suppression) in suppressions.iter().enumerate() {
+        let mut head = format!(
+            "#{}  {}  {}",
+            idx + 1,
+            suppression.rule,
+            suppression.path.as_deref().unwrap_or("(any file)")
+        );
+        if let Some(fingerprint) = &suppression.fingerprint {
+            head.push_str(&format!("  {}", fingerprint));
+        }
+        lines.push(head);
+        if let Some(reason) = &suppression.reason {
+            lines.push(format!("    reason: {}", reason));
+        }
+        if let Some(owner) = &suppression.owner {
+            lines.push(format!("    owner: {}", owner));
+        }
+        if let Some(expires) = &suppression.expires {
+            let expired = Date::parse(expires).is_some_and(|expires| expires < today);
+            lines.push(format!(
+                "    expires: {}{}",
+                expires,
+                if expired { " (expired)" } else { "" }
+            ));
+        }
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+pub fn render_findings(issues: &[Issue]) -> String {
+    let mut seen = HashSet::new();
+    let lines = issues
+        .iter()
+        .filter(|issue| issue.severity != Severity::Pass && seen.insert(issue.fingerprint()))
+        .map(|issue| {
+            format!(
+                "{}  {}  {}  {}",
+                issue.fingerprint(),
+                issue.code,
+                issue.location().unwrap_or_default(),
+                issue.title
+            )
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return "no findings to suppress\n".to_string();
+    }
+    format!(
+        "{}\n\nrun `devguard ignore add <fingerprint> --reason \"...\" [--expires 30d]` to suppress one\n",
+        lines.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stripe_test_issue() -> Issue {
+        Issue::from_rule(
+            rules::SECRET_STRIPE_TEST_PATTERN,
+            Severity::Warning,
+            "Stripe test key pattern detected",
+            "move it",
+        )
+        .with_file("fixtures/stripe.env")
+        .with_line(3)
+    }
+
+    fn suppression(expires: Option<&str>) -> Suppression {
+        Suppression {
+            rule: "DG_SEC_002".to_string(),
+            path: Some("fixtures".to_string()),
+            reason: Some("fake fixture key".to_string()),
+            fingerprint: None,
+            owner: Some("payments-team".to_string()),
+            expires: expires.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn active_suppression_hides_matching_issue() {
+        let today = Date::parse("2025-01-01").expect("valid date");
+        let (issues, hidden) = apply_suppressions(
+            vec![stripe_test_issue()],
+            &[suppression(Some("2025-06-30"))],
+            today,
+        );
+        assert!(issues.is_empty());
+        assert_eq!(hidden.len(), 1);
+        assert_eq!(hidden[0].mechanism, Mechanism::Suppression);
+        assert_eq!(hidden[0].owner.as_deref(), Some("payments-team"));
+        assert_eq!(hidden[0].reason.as_deref(), Some("fake fixture key"));
+        assert_eq!(
+            hidden[0].location().as_deref(),
+            Some("fixtures/stripe.env:3")
+        );
+    }
+
+    #[test]
+    fn expired_suppression_resurfaces_issue_with_warning() {
+        let today = Date::parse("2025-07-01").expect("valid date");
+        let (issues, hidden) = apply_suppressions(
+            vec![stripe_test_issue()],
+            &[suppression(Some("2025-06-30"))],
+            today,
+        );
+
+        assert!(hidden.is_empty());
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].code, "DG_SEC_002");
+        assert_eq!(issues[1].code, "DG_SUPPRESS_001");
+        assert!(
+            issues[1]
+                .description
+                .as_deref()
+                .is_some_and(|details| details.contains("owner: payments-team"))
+        );
+    }
+
+    #[test]
+    fn ignore_entries_round_trip_through_the_config() {
+        let today = Date::parse("2025-01-31").expect("valid date");
+        assert_eq!(
+            parse_expires("30d", today).expect("days").to_string(),
+            "2025-03-02"
+        );
+        assert_eq!(
+            parse_expires("2w", today).expect("weeks").to_string(),
+            "2025-02-14"
+        );
+        assert!(parse_expires("0d", today).is_err());
+        assert!(parse_expires("soon", today).is_err());
+
+        let issue = stripe_test_issue();
+        let entry = for_issue(
+            &issue,
+            "demo key".to_string(),
+            None,
+            Some(Date::parse("2025-03-02").expect("valid date")),
+        );
+        let content =
+            "# team config\n[general]\nmin_score = 80\n\n[[suppressions]]\nrule = \"DG_GIT_001\"\n";
+        let added = append_suppression(content, &entry).expect("appended");
+        assert!(added.starts_with(content));
+        let config = toml::from_str::<Config>(&added).expect("parses");
+        assert_eq!(config.suppressions.len(), 2);
+        assert_eq!(
+            config.suppressions[1].fingerprint.as_deref(),
+            Some(issue.fingerprint().as_str())
+        );
+
+        let (issues, hidden) = apply_suppressions(vec![issue.clone()], &config.suppressions, today);
+        assert!(issues.is_empty());
+        assert_eq!(hidden.len(), 1);
+        let (issues, _) = apply_suppressions(
+            vec![issue.clone().with_file("fixtures/other.env")],
+            &config.suppressions,
+            today,
+        );
+        assert_eq!(issues.len(), 1);
+
+        let (removed, entry) =
+            remove_suppression(&added, &issue.fingerprint()[..6]).expect("removed");
+        assert_eq!(removed, content);
+        assert_eq!(entry.reason.as_deref(), Some("demo key"));
+        let (removed, entry) = remove_suppression(&added, "1").expect("removed");
+        assert_eq!(entry.rule, "DG_GIT_001");
+        assert!(!removed.contains("DG_GIT_001"));
+        assert!(remove_suppression(&added, "3").is_err());
+    }
+
+    #[test]
+    fn path_scope_limits_suppression() {
+        let today = Date::parse("2025-01-01").expect("valid date");
+        let other = stripe_test_issue().with_file("src/billing.ts");
+        let (issues, _) = apply_suppressions(vec![other], &[suppression(None)], today);
+        assert_eq!(issues.len(), 1);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::utils::git as git_utils;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+// a long-running `devguard daemon` keeps each repository's index snapshot between runs;
+// one-shot runs never touch the cache
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SNAPSHOTS: Lazy<Mutex<HashMap<PathBuf, Snapshot>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct Snapshot {
+    stamp: IndexStamp,
+    tracked: Arc<git_utils::TrackedSet>,
+}
+
+// git rewrites .git/index on every add, commit and checkout, so its size and mtime
+// change whenever the set of tracked paths can
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IndexStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl IndexStamp {
+    fn read(index: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(index).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn tracked_set(
+    repo: &git_utils::Repository,
+    repo_root: &Path,
+) -> Option<Arc<git_utils::TrackedSet>> {
+    let load = || {
+        git_utils::TrackedSet::load(repo, repo_root)
+            .ok()
+            .map(Arc::new)
+    };
+    if !ENABLED.load(Ordering::Relaxed) {
+        return load();
+    }
+    // a repository without an index yet is cheap to read and not worth remembering
+    let Some(stamp) = IndexStamp::read(&git_utils::index_path(repo)) else {
+        return load();
+    };
+
+    let mut snapshots = SNAPSHOTS.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(snapshot) = snapshots.get(repo_root)
+        && snapshot.stamp == stamp
+    {
+        return Some(snapshot.tracked.clone());
+    }
+    let tracked = load()?;
+    snapshots.insert(
+        repo_root.to_path_buf(),
+        Snapshot {
+            stamp,
+            tracked: tracked.clone(),
+        },
+    );
+    Some(tracked)
+}
@@ -0,0 +1,203 @@
+use crate::utils::{net, process, sandbox};
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+const CLONE_TIMEOUT: Duration = Duration::from_secs(300);
+// how often a running clone's checkout is measured against the budget
+const CLONE_SIZE_POLL: Duration = Duration::from_millis(250);
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+// a private temp directory for checkouts. every workspace gets its own directory, its
+// content counts against a byte budget, and the tree is removed on drop. only `--remote`
+// needs one: history scans stream git's output and archives are unpacked in memory, so
+// neither puts anything on disk
+pub struct Workspace {
+    root: PathBuf,
+    max_bytes: u64,
+    used: AtomicU64,
+}
+
+impl Workspace {
+    pub fn create(label: &str, max_bytes: u64) -> Result<Self> {
+        sandbox::ensure_allowed("creating a temp workspace")?;
+        let base = std::env::temp_dir();
+        loop {
+            let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+            let root = base.join(format!("devguard-{}-{}-{}", label, std::process::id(), id));
+            match fs::create_dir(&root) {
+                Ok(()) => {
+                    return Ok(Self {
+                        root,
+                        max_bytes,
+                        used: AtomicU64::new(0),
+                    });
+                }
+                // left behind by an earlier process with a recycled pid
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("failed to create workspace in {}", base.display())
+                    });
+                }
+            }
+        }
+    }
+
+    // shallow clone through the git CLI, which brings its own credential helpers and
+    // transports
+    pub fn clone_repo(&self, url: &str) -> Result<PathBuf> {
+        if net::is_offline() {
+            bail!("cannot clone {}: devguard is running with --offline", url);
+        }
+        let checkout = self.root.join("checkout");
+        self.clone_into(url, &checkout)?;
+        Ok(checkout)
+    }
+
+    // the checkout is measured while git writes it, so a repository over budget is
+    // stopped early instead of filling the disk first
+    fn clone_into(&self, url: &str, checkout: &Path) -> Result<()> {
+        let target = checkout.to_string_lossy();
+        let mut over_budget = false;
+        let mut measured_at = Instant::now();
+        let output = process::run_git_while(
+            &["clone", "--quiet", "--depth", "1", "--", url, &target],
+            CLONE_TIMEOUT,
+            || {
+                if measured_at.elapsed() >= CLONE_SIZE_POLL {
+                    measured_at = Instant::now();
+                    over_budget = !self.fits(dir_size(checkout));
+                }
+                !over_budget
+            },
+        );
+        if over_budget {
+            bail!(
+                "git clone of {} stopped: the checkout passed the workspace size limit of {} MB",
+                url,
+                self.max_bytes / (1024 * 1024)
+            );
+        }
+        let output = output.with_context(|| {
+            format!(
+                "git clone of {} did not finish (is git installed? the limit is {}s)",
+                url,
+                CLONE_TIMEOUT.as_secs()
+            )
+        })?;
+        if !output.success {
+            bail!("git clone of {} failed: {}", url, output.first_line());
+        }
+
+        self.reserve(dir_size(checkout))
+    }
+
+    fn fits(&self, bytes: u64) -> bool {
+        self.used
+            .load(Ordering::SeqCst)
+            .checked_add(bytes)
+            .is_some_and(|total| total <= self.max_bytes)
+    }
+
+    fn reserve(&self, bytes: u64) -> Result<()> {
+        let result = self
+            .used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                used.checked_add(bytes)
+                    .filter(|total| *total <= self.max_bytes)
+            });
+        if result.is_err() {
+            bail!(
+                "workspace size limit of {} MB exceeded",
+                self.max_bytes / (1024 * 1024)
+            );
+        }
+        Ok(())
+    }
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspaces_are_unique_and_removed_on_drop() {
+        let first = Workspace::create("test", 1024).expect("workspace created");
+        let second = Workspace::create("test", 1024).expect("workspace created");
+        assert_ne!(first.root, second.root);
+
+        fs::write(first.root.join("blob.txt"), b"hello").expect("file written");
+        let root = first.root.to_path_buf();
+        drop(first);
+        assert!(!root.exists());
+        assert!(second.root.is_dir());
+    }
+
+    #[test]
+    fn reservations_stop_at_the_size_limit() {
+        let workspace = Workspace::create("test", 8).expect("workspace created");
+        workspace.reserve(5).expect("within limit");
+        assert!(workspace.reserve(5).is_err());
+        workspace.reserve(3).expect("exactly at the limit");
+    }
+
+    #[test]
+    fn clones_past_the_size_limit_are_refused() {
+        let source = Workspace::create("test-source", u64::MAX).expect("workspace created");
+        let root = source.root.to_string_lossy().to_string();
+        fs::write(source.root.join("big.bin"), vec![b'x'; 64 * 1024]).expect("file written");
+        for args in [
+            vec!["init", "--quiet"],
+            vec!["add", "big.bin"],
+            vec![
+                "-c",
+                "user.name=dev",
+                "-c",
+                "user.email=dev@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "big",
+            ],
+        ] {
+            let output = process::run_with_timeout(
+                "git",
+                &[&["-C", root.as_str()], args.as_slice()].concat(),
+                Duration::from_secs(30),
+            )
+            .expect("git runs");
+            assert!(output.success, "{}", output.first_line());
+        }
+
+        let workspace = Workspace::create("test", 16 * 1024).expect("workspace created");
+        let err = workspace
+            .clone_into(
+                &format!("file://{}", root),
+                &workspace.root.join("checkout"),
+            )
+            .expect_err("clone over budget");
+        assert!(err.to_string().contains("size limit"), "{err:#}");
+        assert_eq!(workspace.used.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// one json line each way per connection: the client's argv, working directory, and
+// environment in, the rendered report and exit code out
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Request {
+    pub cwd: PathBuf,
+    pub argv: Vec<String>,
+    pub terminal: bool,
+    // the scan reads env vars from here rather than from the daemon's own environment;
+    // None, from an older client, falls back to the daemon's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Response {
+    pub stdout: String,
+    pub code: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// one socket per repository, in a per-user directory so another account cannot answer
+// in the daemon's place. without XDG_RUNTIME_DIR it lives in the shared temp directory,
+// so both sides check the directory is private to this user before using it
+pub fn socket_path(repo_root: &Path) -> PathBuf {
+    let root = repo_root
+        .canonicalize()
+        .unwrap_or_else(|_| repo_root.to_path_buf());
+    let dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("devguard"))
+        .unwrap_or_else(|| std::env::temp_dir().join(user_dir_name()));
+    let digest = devguard::utils::digest::sha256_hex(root.to_string_lossy().as_bytes());
+    dir.join(format!("daemon-{}.sock", &digest[..16]))
+}
+
+#[cfg(unix)]
+fn user_dir_name() -> String {
+    // SAFETY: getuid has no preconditions and cannot fail
+    format!("devguard-{}", unsafe { libc::getuid() })
+}
+
+#[cfg(not(unix))]
+fn user_dir_name() -> String {
+    "devguard".to_string()
+}
+
+#[cfg(unix)]
+pub use unix::{forward, serve};
+
+#[cfg(unix)]
+mod unix {
+    use super::{Request, Response, socket_path};
+    use anyhow::{Context, Result, bail};
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+
+    pub fn serve(repo_root: &Path, mut handle: impl FnMut(Request) -> Response) -> Result<()> {
+        let path = socket_path(repo_root);
+        if UnixStream::connect(&path).is_ok() {
+            bail!(
+                "a devguard daemon is already serving {} at {}",
+                repo_root.display(),
+                path.display()
+            );
+        }
+        devguard::utils::sandbox::ensure_allowed(&format!("listening on {}", path.display()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+            ensure_private(dir)?;
+        }
+        // left behind by a daemon that was killed
+        let _ = std::fs::remove_file(&path);
+        // the socket is created 0600 rather than narrowed after bind, so no other user can
+        // connect in between. nothing else runs yet, so changing the process umask is safe
+        // SAFETY: umask has no preconditions and cannot fail
+        let umask = unsafe { libc::umask(0o177) };
+        let bound = UnixListener::bind(&path);
+        // SAFETY: as above
+        unsafe { libc::umask(umask) };
+        let listener = bound.with_context(|| format!("failed to listen on {}", path.display()))?;
+        eprintln!(
+            "devguard daemon serving {} on {}",
+            repo_root.display(),
+            path.display()
+        );
+
+        // scans run one at a time; a second hook simply waits for the first
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            if let Err(err) = answer(stream, &mut handle) {
+                eprintln!("warning: {err:#}");
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn answer(
+        mut stream: UnixStream,
+        handle: &mut impl FnMut(Request) -> Response,
+    ) -> Result<()> {
+        let mut line = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut line)
+            .context("failed to read daemon request")?;
+        let request = serde_json::from_str(&line).context("malformed daemon request")?;
+        let mut reply = serde_json::to_vec(&handle(request))?;
+        reply.push(b'\n');
+        stream
+            .write_all(&reply)
+            .context("failed to send daemon response")
+    }
+
+    // None when no daemon is listening or it went away mid-request; the caller then
+    // scans in-process. a socket another user could have put there is never trusted
+    pub fn forward(repo_root: &Path, request: &Request) -> Option<Response> {
+        let path = socket_path(repo_root);
+        ensure_private(path.parent()?).ok()?;
+        if !owned_by_current_user(&std::fs::symlink_metadata(&path).ok()?) {
+            return None;
+        }
+        exchange(UnixStream::connect(&path).ok()?, request)
+    }
+
+    pub(super) fn ensure_private(dir: &Path) -> Result<()> {
+        let meta = std::fs::symlink_metadata(dir)
+            .with_context(|| format!("failed to read {}", dir.display()))?;
+        if !meta.is_dir() || !owned_by_current_user(&meta) || meta.mode() & 0o077 != 0 {
+            bail!(
+                "{} must be a directory only you can access (owned by you, mode 0700)",
+                dir.display()
+            );
+        }
+        Ok(())
+    }
+
+    fn owned_by_current_user(meta: &std::fs::Metadata) -> bool {
+        // SAFETY: getuid has no preconditions and cannot fail
+        meta.uid() == unsafe { libc::getuid() }
+    }
+
+    pub(super) fn exchange(mut stream: UnixStream, request: &Request) -> Option<Response> {
+        let mut line = serde_json::to_vec(request).ok()?;
+        line.push(b'\n');
+        stream.write_all(&line).ok()?;
+
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply).ok()?;
+        serde_json::from_str(&reply).ok()
+    }
+}
+
+#[cfg(not(unix))]
+pub fn serve(_repo_root: &Path, _handle: impl FnMut(Request) -> Response) -> anyhow::Result<()> {
+    anyhow::bail!("devguard daemon needs unix domain sockets and is not supported on this platform")
+}
+
+#[cfg(not(unix))]
+pub fn forward(_repo_root: &Path, _request: &Request) -> Option<Response> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+
+    #[test]
+    fn answers_a_forwarded_request_over_the_socket() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let worker = std::thread::spawn(move || {
+            unix::answer(server, &mut |request: Request| Response {
+                stdout: format!(
+                    "{} in {} with {:?}",
+                    request.argv.join(" "),
+                    request.cwd.display(),
+                    request.env.unwrap_or_default().keys().collect::<Vec<_>>()
+                ),
+                code: i32::from(request.terminal),
+                error: None,
+            })
+        });
+
+        let request = Request {
+            cwd: PathBuf::from("/work/app"),
+            argv: vec!["devguard".to_string(), "check".to_string()],
+            terminal: true,
+            env: Some(BTreeMap::from([("NO_COLOR".to_string(), "1".to_string())])),
+        };
+        let response = unix::exchange(client, &request).unwrap();
+        worker.join().unwrap().unwrap();
+
+        assert_eq!(
+            response.stdout,
+            "devguard check in /work/app with [\"NO_COLOR\"]"
+        );
+        assert_eq!(response.code, 1);
+        assert!(response.error.is_none());
+    }
+
+    #[test]
+    fn only_a_private_socket_directory_is_trusted() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("devguard-daemon-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let shared = unix::ensure_private(&dir);
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+        let private = unix::ensure_private(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(shared.is_err());
+        assert!(private.is_ok());
+    }
+}
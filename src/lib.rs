@@ -0,0 +1,21 @@
+// the `devguard` binary is a thin CLI over these modules; other tools drive the same
+// checks through `Checker`
+#[cfg(not(feature = "wasm"))]
+pub mod checker;
+pub mod config;
+pub mod core;
+pub mod policy;
+#[cfg(not(feature = "wasm"))]
+pub mod providers;
+#[cfg(not(feature = "wasm"))]
+pub mod report;
+#[cfg(not(feature = "wasm"))]
+pub mod score;
+pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(not(feature = "wasm"))]
+pub use checker::{CheckResults, Checker, CheckerBuilder, Score};
+pub use config::{Config, FailOn};
+pub use core::{Category, Issue, Severity};
@@ -0,0 +1,475 @@
+use crate::core::{Issue, Severity};
+use anyhow::{Result, bail};
+
+// `[policy.rules]` expressions, e.g.
+//   counts.error == 0 && score >= 85 && !issues[category == "supabase"]
+// `issues[...]` counts the non-pass issues matching the filter; numbers are truthy when
+// non-zero. string comparisons ignore case.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+    Path(Vec<String>),
+    Issues(Option<Box<Expr>>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Int,
+    Str,
+    Bool,
+}
+
+const REPORT_FIELDS: &[(&str, Kind)] = &[
+    ("score", Kind::Int),
+    ("min_score", Kind::Int),
+    ("counts.error", Kind::Int),
+    ("counts.critical", Kind::Int),
+    ("counts.warning", Kind::Int),
+    ("counts.info", Kind::Int),
+    ("counts.pass", Kind::Int),
+    ("counts.total", Kind::Int),
+];
+
+const ISSUE_FIELDS: &[(&str, Kind)] = &[
+    ("code", Kind::Str),
+    ("title", Kind::Str),
+    ("severity", Kind::Str),
+    ("category", Kind::Str),
+    ("file", Kind::Str),
+    ("line", Kind::Int),
+];
+
+fn field_kind(fields: &[(&str, Kind)], path: &[String]) -> Result<Kind> {
+    let name = path.join(".");
+    match fields.iter().find(|(field, _)| *field == name) {
+        Some((_, kind)) => Ok(*kind),
+        None => bail!("unknown field `{}` in policy expression", name),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+}
+
+impl Value {
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Int(value) => *value != 0,
+            Value::Str(value) => !value.is_empty(),
+            Value::Bool(value) => *value,
+        }
+    }
+}
+
+pub struct PolicyContext<'a> {
+    pub score: u8,
+    pub min_score: u8,
+    pub issues: &'a [Issue],
+}
+
+// parses and type-checks an expression so typos surface when the config is loaded
+pub fn validate(source: &str) -> Result<()> {
+    parse(source)?.kind(false)?;
+    Ok(())
+}
+
+pub fn parse(source: &str) -> Result<Expr> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.or()?;
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        bail!("unexpected {:?} in policy expression", token);
+    }
+    Ok(expr)
+}
+
+impl Expr {
+    pub fn evaluate(&self, ctx: &PolicyContext) -> Result<bool> {
+        Ok(self.eval(ctx, None)?.truthy())
+    }
+
+    // static type of the expression; `in_issue` is true inside an issues[...] filter
+    fn kind(&self, in_issue: bool) -> Result<Kind> {
+        Ok(match self {
+            Expr::Int(_) => Kind::Int,
+            Expr::Str(_) => Kind::Str,
+            Expr::Bool(_) | Expr::Not(_) | Expr::And(..) | Expr::Or(..) => {
+                for child in self.children() {
+                    child.kind(in_issue)?;
+                }
+                Kind::Bool
+            }
+            Expr::Path(path) if in_issue => field_kind(ISSUE_FIELDS, path)?,
+            Expr::Path(path) => field_kind(REPORT_FIELDS, path)?,
+            Expr::Issues(_) if in_issue => {
+                bail!("`issues` cannot be used inside an issues[...] filter")
+            }
+            Expr::Issues(filter) => {
+                if let Some(filter) = filter {
+                    filter.kind(true)?;
+                }
+                Kind::Int
+            }
+            Expr::Compare(left, op, right) => {
+                let (left, right) = (left.kind(in_issue)?, right.kind(in_issue)?);
+                if left != right
+                    || (left == Kind::Bool && !matches!(op, CompareOp::Eq | CompareOp::Ne))
+                {
+                    bail!(
+                        "cannot compare {:?} with {:?} in policy expression",
+                        left,
+                        right
+                    );
+                }
+                Kind::Bool
+            }
+        })
+    }
+
+    fn children(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Not(inner) => vec![inner],
+            Expr::And(left, right) | Expr::Or(left, right) => vec![left, right],
+            _ => Vec::new(),
+        }
+    }
+
+    fn eval(&self, ctx: &PolicyContext, issue: Option<&Issue>) -> Result<Value> {
+        Ok(match self {
+            Expr::Int(value) => Value::Int(*value),
+            Expr::Str(value) => Value::Str(value.clone()),
+            Expr::Bool(value) => Value::Bool(*value),
+            Expr::Path(path) => match issue {
+                Some(issue) => issue_field(issue, path)?,
+                None => report_field(ctx, path)?,
+            },
+            Expr::Issues(filter) => {
+                if issue.is_some() {
+                    bail!("`issues` cannot be used inside an issues[...] filter");
+                }
+                let mut count = 0;
+                for candidate in ctx.issues {
+                    if candidate.severity == Severity::Pass {
+                        continue;
+                    }
+                    let matched = match filter {
+                        Some(filter) => filter.eval(ctx, Some(candidate))?.truthy(),
+                        None => true,
+                    };
+                    count += i64::from(matched);
+                }
+                Value::Int(count)
+            }
+            Expr::Not(inner) => Value::Bool(!inner.eval(ctx, issue)?.truthy()),
+            Expr::And(left, right) => {
+                Value::Bool(left.eval(ctx, issue)?.truthy() && right.eval(ctx, issue)?.truthy())
+            }
+            Expr::Or(left, right) => {
+                Value::Bool(left.eval(ctx, issue)?.truthy() || right.eval(ctx, issue)?.truthy())
+            }
+            Expr::Compare(left, op, right) => {
+                let left = left.eval(ctx, issue)?;
+                let right = right.eval(ctx, issue)?;
+                Value::Bool(compare(&left, *op, &right)?)
+            }
+        })
+    }
+}
+
+fn compare(left: &Value, op: CompareOp, right: &Value) -> Result<bool> {
+    let ordering = match (left, right) {
+        (Value::Int(left), Value::Int(right)) => left.cmp(right),
+        (Value::Str(left), Value::Str(right)) => {
+            left.to_ascii_lowercase().cmp(&right.to_ascii_lowercase())
+        }
+        (Value::Bool(left), Value::Bool(right)) if matches!(op, CompareOp::Eq | CompareOp::Ne) => {
+            left.cmp(right)
+        }
+        _ => bail!("cannot compare {:?} with {:?}", left, right),
+    };
+    Ok(match op {
+        CompareOp::Eq => ordering.is_eq(),
+        CompareOp::Ne => ordering.is_ne(),
+        CompareOp::Lt => ordering.is_lt(),
+        CompareOp::Le => ordering.is_le(),
+        CompareOp::Gt => ordering.is_gt(),
+        CompareOp::Ge => ordering.is_ge(),
+    })
+}
+
+fn report_field(ctx: &PolicyContext, path: &[String]) -> Result<Value> {
+    let count = |severity: Severity| {
+        Value::Int(
+            ctx.issues
+                .iter()
+                .filter(|issue| issue.severity == severity)
+                .count() as i64,
+        )
+    };
+    let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+    Ok(match path.as_slice() {
+        ["score"] => Value::Int(i64::from(ctx.score)),
+        ["min_score"] => Value::Int(i64::from(ctx.min_score)),
+        ["counts", "error" | "critical"] => count(Severity::Error),
+        ["counts", "warning"] => count(Severity::Warning),
+        ["counts", "info"] => count(Severity::Info),
+        ["counts", "pass"] => count(Severity::Pass),
+        ["counts", "total"] => Value::Int(ctx.issues.len() as i64),
+        _ => bail!("unknown field `{}` in policy expression", path.join(".")),
+    })
+}
+
+fn issue_field(issue: &Issue, path: &[String]) -> Result<Value> {
+    let [field] = path else {
+        bail!("unknown issue field `{}`", path.join("."));
+    };
+    Ok(match field.as_str() {
+        "code" => Value::Str(issue.code.to_string()),
+        "title" => Value::Str(issue.title.clone()),
+        "severity" => Value::Str(issue.severity.slug().to_string()),
+        "category" => Value::Str(issue.category.slug().to_string()),
+        "file" => Value::Str(issue.file.clone().unwrap_or_default()),
+        "line" => Value::Int(issue.line.map_or(0, |line| line as i64)),
+        other => bail!("unknown issue field `{}`", other),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(i64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    const OPERATORS: [&str; 14] = [
+        "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "(", ")", "[", "]", ".",
+    ];
+
+    let mut tokens = Vec::new();
+    let chars = source.char_indices().collect::<Vec<_>>();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let (offset, ch) = chars[idx];
+        if ch.is_whitespace() {
+            idx += 1;
+        } else if ch == '"' || ch == '\'' {
+            let end = chars[idx + 1..]
+                .iter()
+                .position(|(_, c)| *c == ch)
+                .map(|pos| idx + 1 + pos);
+            let Some(end) = end else {
+                bail!("unterminated string in policy expression");
+            };
+            tokens.push(Token::Str(source[offset + 1..chars[end].0].to_string()));
+            idx = end + 1;
+        } else if ch.is_ascii_digit() {
+            let len = chars[idx..]
+                .iter()
+                .take_while(|(_, c)| c.is_ascii_digit())
+                .count();
+            let end = chars.get(idx + len).map_or(source.len(), |(pos, _)| *pos);
+            tokens.push(Token::Int(source[offset..end].parse()?));
+            idx += len;
+        } else if ch.is_ascii_alphabetic() || ch == '_' {
+            let len = chars[idx..]
+                .iter()
+                .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                .count();
+            let end = chars.get(idx + len).map_or(source.len(), |(pos, _)| *pos);
+            tokens.push(Token::Ident(source[offset..end].to_string()));
+            idx += len;
+        } else if let Some(op) = OPERATORS
+            .iter()
+            .find(|op| source[offset..].starts_with(**op))
+        {
+            tokens.push(Token::Op(op));
+            idx += op.len();
+        } else {
+            bail!("unexpected character {:?} in policy expression", ch);
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn eat(&mut self, op: &str) -> bool {
+        let matched = matches!(self.tokens.get(self.pos), Some(Token::Op(found)) if *found == op);
+        self.pos += usize::from(matched);
+        matched
+    }
+
+    fn expect(&mut self, op: &str) -> Result<()> {
+        if !self.eat(op) {
+            bail!("expected `{}` in policy expression", op);
+        }
+        Ok(())
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let left = self.primary()?;
+        let op = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+        ]
+        .into_iter()
+        .find(|(token, _)| self.eat(token));
+        match op {
+            Some((_, op)) => Ok(Expr::Compare(Box::new(left), op, Box::new(self.primary()?))),
+            None => Ok(left),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        if self.eat("(") {
+            let expr = self.or()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            bail!("policy expression ended unexpectedly");
+        };
+        self.pos += 1;
+        match token {
+            Token::Int(value) => Ok(Expr::Int(value)),
+            Token::Str(value) => Ok(Expr::Str(value)),
+            Token::Ident(name) if name == "true" || name == "false" => {
+                Ok(Expr::Bool(name == "true"))
+            }
+            Token::Ident(name) if name == "issues" => {
+                if !self.eat("[") {
+                    return Ok(Expr::Issues(None));
+                }
+                let filter = self.or()?;
+                self.expect("]")?;
+                Ok(Expr::Issues(Some(Box::new(filter))))
+            }
+            Token::Ident(name) => {
+                let mut path = vec![name];
+                while self.eat(".") {
+                    match self.tokens.get(self.pos).cloned() {
+                        Some(Token::Ident(segment)) => {
+                            path.push(segment);
+                            self.pos += 1;
+                        }
+                        _ => bail!("expected a field name after `.` in policy expression"),
+                    }
+                }
+                Ok(Expr::Path(path))
+            }
+            Token::Op(op) => bail!("unexpected `{}` in policy expression", op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules;
+
+    fn issues() -> Vec<Issue> {
+        vec![
+            Issue::from_rule(
+                rules::SUPABASE_SERVICE_ROLE_IN_CLIENT,
+                Severity::Error,
+                "service role key in client code",
+                "move it server-side",
+            ),
+            Issue::from_rule(
+                rules::GIT_CLEAN_TREE,
+                Severity::Pass,
+                "working tree is clean",
+                "no action needed",
+            ),
+        ]
+    }
+
+    fn eval(source: &str, score: u8) -> bool {
+        let issues = issues();
+        parse(source)
+            .expect("expression parses")
+            .evaluate(&PolicyContext {
+                score,
+                min_score: 80,
+                issues: &issues,
+            })
+            .expect("expression evaluates")
+    }
+
+    #[test]
+    fn release_gate_style_expressions() {
+        let gate = r#"counts.critical == 0 && score >= 85 && !issues[category == "Supabase"]"#;
+        assert!(!eval(gate, 90));
+        assert!(eval("counts.error == 1 && score >= min_score", 85));
+        assert!(eval(
+            r#"issues[severity == "error" && code != "DG_X"] == 1"#,
+            0
+        ));
+        assert!(eval(r#"!issues[category == "git"] || score < 10"#, 50));
+        assert!(eval("(counts.pass > 0) == true", 0));
+    }
+
+    #[test]
+    fn mistakes_are_reported_before_the_run() {
+        assert!(validate("score >= 85 && counts.error == 0").is_ok());
+        assert!(validate("scroe >= 85").is_err());
+        assert!(validate("score >= ").is_err());
+        assert!(validate(r#"score == "high""#).is_err());
+        assert!(validate("issues[color == 1]").is_err());
+        assert!(validate(r#"issues[file == "src/app.ts"] == 0"#).is_ok());
+        assert!(validate("counts.error = 0").is_err());
+    }
+}
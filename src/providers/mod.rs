@@ -1,4 +1,5 @@
-use crate::config::Config;
+use crate::config::{Config, DetectMarkers};
+use crate::core::issue::RuleSpec;
 use crate::core::{Issue, RepoContext};
 
 pub mod stripe;
@@ -9,7 +10,25 @@ pub trait Provider {
     fn name(&self) -> &'static str;
     fn is_enabled(&self, cfg: &Config) -> bool;
     fn detect(&self, ctx: &RepoContext) -> bool;
+    fn markers<'a>(&self, cfg: &'a Config) -> &'a DetectMarkers;
     fn run_checks(&self, ctx: &RepoContext, cfg: &Config) -> Vec<Issue>;
+    // what a run that names this provider reports when it is disabled, and when it is
+    // not detected
+    fn skip_rules(&self) -> (RuleSpec, RuleSpec);
+
+    // built-in heuristics plus any `detect_paths` / `detect_packages` from config
+    fn detected(&self, ctx: &RepoContext, cfg: &Config) -> bool {
+        let markers = self.markers(cfg);
+        markers
+            .detect_paths
+            .iter()
+            .any(|path| ctx.repo_root.join(path.trim_start_matches("./")).exists())
+            || markers
+                .detect_packages
+                .iter()
+                .any(|name| ctx.has_any_dependency(name))
+            || self.detect(ctx)
+    }
 }
 
 pub fn all_providers() -> Vec<Box<dyn Provider>> {
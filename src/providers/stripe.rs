@@ -1,17 +1,73 @@
-use crate::config::Config;
-use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::config::{Config, DetectMarkers};
+use crate::core::issue::RuleSpec;
+use crate::core::manifests::Ecosystem;
+use crate::core::{Issue, RemediationAction, RepoContext, Severity, rules};
 use crate::providers::Provider;
+use crate::utils::fs::{decode_text, relative_path};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 pub struct StripeProvider;
 
+const SDK_PACKAGES: [(Ecosystem, &str); 5] = [
+    (Ecosystem::Python, "stripe"),
+    (Ecosystem::Ruby, "stripe"),
+    (Ecosystem::Go, "github.com/stripe/stripe-go"),
+    (Ecosystem::Rust, "async-stripe"),
+    (Ecosystem::Rust, "stripe-rust"),
+];
+
 static STRIPE_LIVE_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"sk_live_[0-9A-Za-z]{16,}").expect("valid stripe live regex"));
 static STRIPE_TEST_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"sk_test_[0-9A-Za-z]{16,}").expect("valid stripe test regex"));
 
+const CODE_EXTENSIONS: [&str; 10] = [
+    "ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "py", "rb", "go",
+];
+const TEST_DIRS: [&str; 7] = [
+    "test",
+    "tests",
+    "__tests__",
+    "__mocks__",
+    "spec",
+    "e2e",
+    "fixtures",
+];
+
+// stripe-node, stripe-python, stripe-ruby, and stripe-go spell the verifier differently
+static CONSTRUCT_EVENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:webhooks|Webhook|webhook)\s*(?:\.|::)\s*(?:constructEvent(?:Async)?|construct_event|ConstructEvent(?:WithOptions)?)\s*\(",
+    )
+    .expect("valid construct event regex")
+});
+static ENV_NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?:process\.env\.|env\[\s*['"]|environ\[\s*['"]|environ\.get\(\s*['"]|getenv\(\s*['"]|Getenv\(\s*"|ENV\.fetch\(\s*['"]|env\.get\(\s*['"])([A-Za-z_][A-Za-z0-9_]*)"#,
+    )
+    .expect("valid env name regex")
+});
+static API_VERSION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:apiVersion|api_version|APIVersion)\s*[:=]\s*['"](\d{4}-\d{2}-\d{2})"#)
+        .expect("valid api version regex")
+});
+static TEST_CLOCK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"testHelpers\.testClocks\.|test_helpers\.TestClock\.|TestHelpers::TestClock\.|\btest_clock['"]?\s*[:=]|\bTestClock\s*:"#,
+    )
+    .expect("valid test clock regex")
+});
+
+// the signing secret handed to a webhook verifier: a literal in the source, or the env var
+// (or failing that, the expression) it comes from
+#[derive(Debug, PartialEq, Eq)]
+enum WebhookSecret {
+    Literal,
+    Named(String),
+}
+
 impl Provider for StripeProvider {
     fn name(&self) -> &'static str {
         "stripe"
@@ -21,8 +77,19 @@ impl Provider for StripeProvider {
         cfg.providers.stripe.enabled
     }
 
+    fn markers<'a>(&self, cfg: &'a Config) -> &'a DetectMarkers {
+        &cfg.providers.stripe.markers
+    }
+
+    fn skip_rules(&self) -> (RuleSpec, RuleSpec) {
+        (rules::STRIPE_PROVIDER_DISABLED, rules::STRIPE_NOT_DETECTED)
+    }
+
     fn detect(&self, ctx: &RepoContext) -> bool {
-        ctx.package_json_contains("\"stripe\"")
+        ctx.has_dependency("stripe")
+            || SDK_PACKAGES
+                .iter()
+                .any(|(ecosystem, name)| ctx.has_manifest_dependency(*ecosystem, name))
             || ctx.has_env_key("STRIPE_SECRET_KEY")
             || ctx.has_env_key("STRIPE_PUBLISHABLE_KEY")
     }
@@ -44,7 +111,8 @@ impl Provider for StripeProvider {
                             "move live keys to deployment secrets and rotate exposed values",
                         )
                         .with_file(variable.file.clone())
-                        .with_line(variable.line),
+                        .with_line(variable.line)
+                        .with_action(RemediationAction::rotate_key("stripe")),
                     );
                 }
             }
@@ -59,7 +127,8 @@ impl Provider for StripeProvider {
                         "keep test keys in local-only env files and out of source control",
                     )
                     .with_file(variable.file.clone())
-                    .with_line(variable.line),
+                    .with_line(variable.line)
+                    .with_action(RemediationAction::rotate_key("stripe")),
                 );
             }
         }
@@ -76,6 +145,285 @@ impl Provider for StripeProvider {
             );
         }
 
+        if cfg.providers.stripe.check_code {
+            issues.extend(scan_code(ctx, cfg));
+        }
+
         issues
     }
 }
+
+fn scan_code(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut handlers: Vec<(String, usize, Option<String>)> = Vec::new();
+    let min_version = &cfg.providers.stripe.min_api_version;
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "stripe code scan") {
+        let relative_file = relative_path(&ctx.repo_root, entry.path());
+        if !is_application_source(&relative_file) {
+            continue;
+        }
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = decode_text(&bytes);
+
+        for call in CONSTRUCT_EVENT_RE.find_iter(&content) {
+            let line = line_number(&content, call.start());
+            let secret = call_argument(&content[call.end()..], 2)
+                .and_then(|argument| webhook_secret(&content, argument));
+            if secret == Some(WebhookSecret::Literal) {
+                issues.push(
+                    Issue::from_rule(
+                        rules::STRIPE_HARDCODED_WEBHOOK_SECRET,
+                        Severity::Error,
+                        "Stripe webhook signing secret is hard-coded",
+                        "roll the endpoint secret in the Stripe dashboard and read it from STRIPE_WEBHOOK_SECRET",
+                    )
+                    .with_file(relative_file.clone())
+                    .with_line(line)
+                    .with_action(RemediationAction::rotate_key("stripe")),
+                );
+            }
+            let name = match secret {
+                Some(WebhookSecret::Named(name)) => Some(name),
+                _ => None,
+            };
+            handlers.push((relative_file.clone(), line, name));
+        }
+
+        for captures in API_VERSION_RE.captures_iter(&content) {
+            let version = &captures[1];
+            if version >= min_version.as_str() {
+                continue;
+            }
+            issues.push(
+                Issue::from_rule(
+                    rules::STRIPE_OLD_API_VERSION,
+                    Severity::Warning,
+                    format!("Stripe API version {} is pinned", version),
+                    "upgrade the pinned version following Stripe's upgrade guide",
+                )
+                .with_file(relative_file.clone())
+                .with_line(line_number(
+                    &content,
+                    captures.get(1).expect("group").start(),
+                ))
+                .with_description(format!(
+                    "{} is older than providers.stripe.min_api_version {}",
+                    version, min_version
+                )),
+            );
+        }
+
+        if let Some(hit) = TEST_CLOCK_RE.find(&content) {
+            issues.push(
+                Issue::from_rule(
+                    rules::STRIPE_TEST_CLOCK_IN_CODE,
+                    Severity::Warning,
+                    "Stripe test clock used outside tests",
+                    "move test clock setup into test fixtures or scripts",
+                )
+                .with_file(relative_file.clone())
+                .with_line(line_number(&content, hit.start()))
+                .with_description(
+                    "test clocks only exist in test mode, so this fails against live keys",
+                ),
+            );
+        }
+    }
+
+    let files = handlers
+        .iter()
+        .map(|(file, _, _)| file.as_str())
+        .collect::<BTreeSet<_>>();
+    if files.len() > 1 {
+        let secrets = handlers
+            .iter()
+            .filter_map(|(_, _, secret)| secret.as_deref())
+            .collect::<BTreeSet<_>>();
+        let (file, line, _) = &handlers[0];
+        let title = if secrets.len() > 1 {
+            "Stripe webhook handlers verify with different secrets".to_string()
+        } else {
+            format!(
+                "Stripe webhook events are verified in {} files",
+                files.len()
+            )
+        };
+        issues.push(
+            Issue::from_rule(
+                rules::STRIPE_DUPLICATE_WEBHOOK_HANDLERS,
+                Severity::Warning,
+                title,
+                "keep one handler per registered Stripe endpoint and delete the leftover route",
+            )
+            .with_file(file.clone())
+            .with_line(*line)
+            .with_description(format!(
+                "webhook verification found at {}",
+                handlers
+                    .iter()
+                    .map(|(file, line, secret)| match secret {
+                        Some(secret) => format!("{}:{} ({})", file, line, secret),
+                        None => format!("{}:{}", file, line),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        );
+    }
+
+    issues
+}
+
+fn is_application_source(relative_file: &str) -> bool {
+    let Some((stem, ext)) = relative_file.rsplit_once('.') else {
+        return false;
+    };
+    if !CODE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()) {
+        return false;
+    }
+    let name = stem.rsplit('/').next().unwrap_or(stem);
+    let is_test_name = name.ends_with(".test")
+        || name.ends_with(".spec")
+        || name.starts_with("test_")
+        || name.ends_with("_test")
+        || name.ends_with("_spec");
+    let mut dirs = relative_file.split('/').rev().skip(1);
+    !is_test_name && !dirs.any(|dir| TEST_DIRS.contains(&dir))
+}
+
+// the `index`th top-level argument of a call, given the text right after its `(`
+fn call_argument(args: &str, index: usize) -> Option<&str> {
+    let (mut depth, mut quote, mut escaped) = (0usize, None, false);
+    let (mut current, mut start) = (0usize, 0usize);
+
+    for (at, ch) in args.char_indices() {
+        if let Some(open) = quote {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                _ if ch == open => quote = None,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            ',' | ')' if depth == 0 => {
+                if current == index {
+                    return Some(args[start..at].trim());
+                }
+                if ch == ')' {
+                    return None;
+                }
+                current += 1;
+                start = at + 1;
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// a plain identifier is followed to its assignment in the same file, which is how most
+// handlers read the secret: `const endpointSecret = process.env.STRIPE_WEBHOOK_SECRET`
+fn webhook_secret(content: &str, argument: &str) -> Option<WebhookSecret> {
+    let argument = argument.trim_end_matches('!');
+    if argument.is_empty() {
+        return None;
+    }
+    if argument.starts_with(['"', '\'', '`']) {
+        return Some(WebhookSecret::Literal);
+    }
+    if let Some(captures) = ENV_NAME_RE.captures(argument) {
+        return Some(WebhookSecret::Named(captures[1].to_string()));
+    }
+    if argument.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+        let assignment = Regex::new(&format!(
+            r"(?m)\b{}\s*(?::[^=\n]*)?=\s*(.+)$",
+            regex::escape(argument)
+        ))
+        .ok()?;
+        if let Some(value) = assignment
+            .captures(content)
+            .map(|captures| captures[1].trim().to_string())
+        {
+            if value.starts_with(['"', '\'', '`']) {
+                return Some(WebhookSecret::Literal);
+            }
+            if let Some(captures) = ENV_NAME_RE.captures(&value) {
+                return Some(WebhookSecret::Named(captures[1].to_string()));
+            }
+        }
+    }
+    Some(WebhookSecret::Named(argument.to_string()))
+}
+
+fn line_number(content: &str, byte_index: usize) -> usize {
+    content[..byte_index]
+        .bytes()
+        .filter(|byte| *byte == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_webhook_secrets_to_their_source() {
+        let content = "const endpointSecret: string = process.env.STRIPE_WEBHOOK_SECRET!;\n\
+event = stripe.webhooks.constructEvent(req.body, sig, endpointSecret);\n";
+        let call = CONSTRUCT_EVENT_RE.find(content).expect("verifier call");
+        let argument = call_argument(&content[call.end()..], 2).expect("third argument");
+        assert_eq!(argument, "endpointSecret");
+        assert_eq!(
+            webhook_secret(content, argument),
+            Some(WebhookSecret::Named("STRIPE_WEBHOOK_SECRET".to_string()))
+        );
+
+        let python = "event = stripe.Webhook.construct_event(\n    payload, request.headers.get(\"Stripe-Signature\"), \"whsec_abc, def\"\n)";
+        let call = CONSTRUCT_EVENT_RE.find(python).expect("verifier call");
+        let argument = call_argument(&python[call.end()..], 2).expect("third argument");
+        assert_eq!(
+            webhook_secret(python, argument),
+            Some(WebhookSecret::Literal)
+        );
+
+        let go = "event, err := webhook.ConstructEvent(payload, header, os.Getenv(\"STRIPE_WH_SECRET\"))";
+        let call = CONSTRUCT_EVENT_RE.find(go).expect("verifier call");
+        let argument = call_argument(&go[call.end()..], 2).expect("third argument");
+        assert_eq!(
+            webhook_secret(go, argument),
+            Some(WebhookSecret::Named("STRIPE_WH_SECRET".to_string()))
+        );
+    }
+
+    #[test]
+    fn skips_tests_and_recognizes_test_clocks() {
+        assert!(is_application_source("app/api/stripe/route.ts"));
+        assert!(is_application_source("billing/webhooks.py"));
+        assert!(!is_application_source("src/billing.test.ts"));
+        assert!(!is_application_source("tests/test_billing.py"));
+        assert!(!is_application_source("billing/webhook_test.go"));
+        assert!(!is_application_source("README.md"));
+
+        assert!(TEST_CLOCK_RE.is_match("await stripe.testHelpers.testClocks.advance(clock.id, {"));
+        assert!(TEST_CLOCK_RE.is_match("stripe.Customer.create(email=email, test_clock=clock.id)"));
+        assert!(
+            !TEST_CLOCK_RE.is_match("const subscription = await stripe.subscriptions.create({")
+        );
+        assert_eq!(
+            &API_VERSION_RE
+                .captures("new Stripe(key, { apiVersion: '2019-12-03' })")
+                .expect("pinned version")[1],
+            "2019-12-03"
+        );
+    }
+}
@@ -1,15 +1,65 @@
-use crate::config::Config;
+use crate::config::{Config, DetectMarkers};
+use crate::core::issue::RuleSpec;
+use crate::core::manifests::Ecosystem;
 use crate::core::{Issue, RepoContext, Severity, rules};
 use crate::providers::Provider;
-use crate::utils::fs::{is_likely_binary, relative_path};
+use crate::utils::fs::{decode_text, relative_path};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashSet;
-use std::fs;
-use walkdir::WalkDir;
 
 pub struct SupabaseProvider;
 
+const SDK_PACKAGES: [(Ecosystem, &str); 3] = [
+    (Ecosystem::Python, "supabase"),
+    (Ecosystem::Go, "github.com/supabase-community/supabase-go"),
+    (Ecosystem::Rust, "postgrest"),
+];
+
+// server code lives under a path segment with one of these names, or under supabase/functions
+const SERVER_SEGMENTS: [&str; 2] = ["api", "server"];
+const SERVER_EXTENSIONS: [&str; 7] = ["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts"];
+
+static ANON_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:NEXT_PUBLIC_|VITE_|PUBLIC_)?SUPABASE_(?:ANON|PUBLISHABLE)_KEY\b")
+        .expect("valid supabase anon key regex")
+});
+
+static PRIVILEGED_CALL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\.auth\.admin\.\w+|\.storage\.(?:createBucket|updateBucket|deleteBucket|emptyBucket)\b",
+    )
+    .expect("valid supabase privileged call regex")
+});
+
+static BUCKET_INSERT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)^insert\s+into\s+"?storage"?\."?buckets"?\s*\(([^)]*)\)\s*values\s*(.*)$"#)
+        .expect("valid bucket insert regex")
+});
+
+static BUCKET_UPDATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?is)^update\s+"?storage"?\."?buckets"?\s+set\s+.*\bpublic\s*=\s*true\b.*\bwhere\s+(?:id|name)\s*=\s*'([^']+)'"#,
+    )
+    .expect("valid bucket update regex")
+});
+
+static OBJECTS_POLICY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)^create\s+policy\s+("[^"]*"|\S+)\s+on\s+"?storage"?\."?objects"?\b(.*)$"#)
+        .expect("valid storage policy regex")
+});
+
+static POLICY_COMMAND_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bfor\s+(all|select|insert|update|delete)\b").expect("valid command regex")
+});
+
+static POLICY_ROLES_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)\bto\s+(.+?)(?:\busing\b|\bwith\s+check\b|$)").expect("valid roles regex")
+});
+
+static BUCKET_ID_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bbucket_id\s*=\s*'([^']+)'").expect("valid bucket id regex"));
+
 static SERVICE_ROLE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)\b(service_role|SUPABASE_SERVICE_ROLE_KEY|SUPABASE_SERVICE_ROLE)\b")
         .expect("valid supabase service role regex")
@@ -24,10 +74,24 @@ impl Provider for SupabaseProvider {
         cfg.providers.supabase.enabled
     }
 
+    fn markers<'a>(&self, cfg: &'a Config) -> &'a DetectMarkers {
+        &cfg.providers.supabase.markers
+    }
+
+    fn skip_rules(&self) -> (RuleSpec, RuleSpec) {
+        (
+            rules::SUPABASE_PROVIDER_DISABLED,
+            rules::SUPABASE_NOT_DETECTED,
+        )
+    }
+
     fn detect(&self, ctx: &RepoContext) -> bool {
         ctx.repo_root.join("supabase/config.toml").exists()
             || ctx.has_supabase_dir
-            || ctx.package_json_contains("@supabase/supabase-js")
+            || ctx.has_dependency("@supabase/supabase-js")
+            || SDK_PACKAGES
+                .iter()
+                .any(|(ecosystem, name)| ctx.has_manifest_dependency(*ecosystem, name))
     }
 
     fn run_checks(&self, ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
@@ -49,16 +113,14 @@ impl Provider for SupabaseProvider {
                     .with_description("this helps keep schema changes reproducible"),
                 );
             } else {
-                let has_sql_file = WalkDir::new(&migrations_dir)
-                    .into_iter()
-                    .filter_map(Result::ok)
+                let has_sql_file = ctx
+                    .walk_files(&migrations_dir, &cfg.scan, "supabase migrations check")
                     .any(|entry| {
-                        entry.file_type().is_file()
-                            && entry
-                                .path()
-                                .extension()
-                                .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("sql"))
-                                .unwrap_or(false)
+                        entry
+                            .path()
+                            .extension()
+                            .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("sql"))
+                            .unwrap_or(false)
                     });
 
                 if !has_sql_file {
@@ -79,6 +141,14 @@ impl Provider for SupabaseProvider {
             issues.extend(scan_frontend_for_service_role(ctx, cfg));
         }
 
+        if cfg.providers.supabase.check_server_anon_clients {
+            issues.extend(scan_server_for_anon_admin_calls(ctx, cfg));
+        }
+
+        if cfg.providers.supabase.review_storage_policies {
+            issues.extend(review_storage_policies(ctx, cfg));
+        }
+
         for key in ["SUPABASE_URL", "SUPABASE_ANON_KEY"] {
             if cfg.env.required.iter().any(|required| required == key) && !ctx.has_env_key(key) {
                 issues.push(
@@ -102,7 +172,6 @@ impl Provider for SupabaseProvider {
 fn scan_frontend_for_service_role(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
     let mut issues = Vec::new();
     let mut seen = HashSet::new();
-    let max_bytes = cfg.scan.max_file_size_kb * 1024;
 
     for root in ["src", "app", "pages"] {
         let path = ctx.repo_root.join(root);
@@ -110,28 +179,12 @@ fn scan_frontend_for_service_role(ctx: &RepoContext, cfg: &Config) -> Vec<Issue>
             continue;
         }
 
-        for entry in WalkDir::new(&path).into_iter().filter_map(Result::ok) {
-            if !entry.file_type().is_file() {
+        for entry in ctx.walk_files(&path, &cfg.scan, "supabase client scan") {
+            let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
                 continue;
-            }
-
-            let metadata = match entry.metadata() {
-                Ok(metadata) => metadata,
-                Err(_) => continue,
             };
-            if metadata.len() > max_bytes {
-                continue;
-            }
 
-            let bytes = match fs::read(entry.path()) {
-                Ok(bytes) => bytes,
-                Err(_) => continue,
-            };
-            if is_likely_binary(&bytes) {
-                continue;
-            }
-
-            let content = String::from_utf8_lossy(&bytes);
+            let content = decode_text(&bytes);
             for hit in SERVICE_ROLE_RE.find_iter(&content) {
                 let line = line_number(&content, hit.start());
                 let relative_file = relative_path(&ctx.repo_root, entry.path());
@@ -157,6 +210,314 @@ fn scan_frontend_for_service_role(ctx: &RepoContext, cfg: &Config) -> Vec<Issue>
     issues
 }
 
+// admin calls made through a client built from the anon key fail at runtime or, worse,
+// prompt someone to loosen RLS until they work
+fn scan_server_for_anon_admin_calls(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "supabase server client scan") {
+        let relative_file = relative_path(&ctx.repo_root, entry.path());
+        if !is_server_source(&relative_file) {
+            continue;
+        }
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = decode_text(&bytes);
+        if !content.contains("createClient")
+            || !ANON_KEY_RE.is_match(&content)
+            || SERVICE_ROLE_RE.is_match(&content)
+        {
+            continue;
+        }
+
+        if let Some(call) = PRIVILEGED_CALL_RE.find(&content) {
+            issues.push(
+                Issue::from_rule(
+                    rules::SUPABASE_ANON_CLIENT_PRIVILEGED,
+                    Severity::Warning,
+                    "server code calls a privileged Supabase API through an anon key client",
+                    "create a separate server-only client from SUPABASE_SERVICE_ROLE_KEY for admin calls",
+                )
+                .with_file(relative_file)
+                .with_line(line_number(&content, call.start()))
+                .with_description(format!(
+                    "{} needs the service role, but this file only builds clients from the anon key",
+                    call.as_str().trim_start_matches('.')
+                )),
+            );
+        }
+    }
+
+    issues
+}
+
+fn is_server_source(relative_file: &str) -> bool {
+    let has_extension = relative_file
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| SERVER_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+    let mut dirs = relative_file.split('/').rev().skip(1);
+    has_extension
+        && (relative_file.starts_with("supabase/functions/")
+            || dirs.any(|dir| SERVER_SEGMENTS.contains(&dir)))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum StorageFinding {
+    PublicBucket {
+        bucket: String,
+        line: usize,
+    },
+    AnonWritePolicy {
+        policy: String,
+        command: String,
+        bucket: Option<String>,
+        line: usize,
+    },
+}
+
+fn review_storage_policies(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let migrations_dir = ctx.repo_root.join(&cfg.providers.supabase.migrations_dir);
+    if !migrations_dir.is_dir() {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for entry in ctx.walk_files(&migrations_dir, &cfg.scan, "supabase storage policy review") {
+        let is_sql = entry
+            .path()
+            .extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("sql"));
+        if !is_sql {
+            continue;
+        }
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let relative_file = relative_path(&ctx.repo_root, entry.path());
+        findings.extend(
+            storage_findings(&decode_text(&bytes))
+                .into_iter()
+                .map(|finding| (relative_file.clone(), finding)),
+        );
+    }
+
+    let public_buckets = findings
+        .iter()
+        .filter_map(|(_, finding)| match finding {
+            StorageFinding::PublicBucket { bucket, .. } => Some(bucket.clone()),
+            StorageFinding::AnonWritePolicy { .. } => None,
+        })
+        .collect::<HashSet<_>>();
+
+    findings
+        .into_iter()
+        .map(|(file, finding)| match finding {
+            StorageFinding::PublicBucket { bucket, line } => Issue::from_rule(
+                rules::SUPABASE_PUBLIC_BUCKET,
+                Severity::Info,
+                format!("storage bucket {} is public", bucket),
+                "keep only files meant for anyone with the URL in public buckets",
+            )
+            .with_file(file)
+            .with_line(line)
+            .with_description("objects in a public bucket are readable without a policy"),
+            StorageFinding::AnonWritePolicy {
+                policy,
+                command,
+                bucket,
+                line,
+            } => {
+                let target = bucket.as_deref().unwrap_or("every bucket");
+                let description = if bucket
+                    .as_ref()
+                    .is_some_and(|bucket| public_buckets.contains(bucket))
+                {
+                    format!(
+                        "{} is also public, so anyone can upload files and serve them from your domain",
+                        target
+                    )
+                } else {
+                    format!("anonymous clients can {} objects in {}", command, target)
+                };
+                Issue::from_rule(
+                    rules::SUPABASE_ANON_STORAGE_WRITE,
+                    Severity::Error,
+                    format!("storage policy {} allows anonymous writes", policy),
+                    "restrict the policy to authenticated and check auth.uid() against the object owner or path",
+                )
+                .with_file(file)
+                .with_line(line)
+                .with_description(description)
+            }
+        })
+        .collect()
+}
+
+// statement-level review of storage DDL; anything generated through functions or dynamic
+// SQL is out of reach
+fn storage_findings(sql: &str) -> Vec<StorageFinding> {
+    let mut findings = Vec::new();
+
+    for (offset, statement) in sql_statements(sql) {
+        let line = line_number(sql, offset);
+
+        if let Some(captures) = BUCKET_INSERT_RE.captures(&statement) {
+            let columns = captures[1]
+                .split(',')
+                .map(|column| column.trim().trim_matches('"').to_ascii_lowercase())
+                .collect::<Vec<_>>();
+            let (Some(public_idx), Some(id_idx)) = (
+                columns.iter().position(|column| column == "public"),
+                columns
+                    .iter()
+                    .position(|column| column == "id")
+                    .or_else(|| columns.iter().position(|column| column == "name")),
+            ) else {
+                continue;
+            };
+            for row in value_rows(&captures[2]) {
+                let is_public = row
+                    .get(public_idx)
+                    .is_some_and(|value| value.trim_matches('\'').eq_ignore_ascii_case("true"));
+                if let (true, Some(bucket)) = (is_public, row.get(id_idx)) {
+                    findings.push(StorageFinding::PublicBucket {
+                        bucket: bucket.trim_matches('\'').to_string(),
+                        line,
+                    });
+                }
+            }
+            continue;
+        }
+
+        if let Some(captures) = BUCKET_UPDATE_RE.captures(&statement) {
+            findings.push(StorageFinding::PublicBucket {
+                bucket: captures[1].to_string(),
+                line,
+            });
+            continue;
+        }
+
+        let Some(captures) = OBJECTS_POLICY_RE.captures(&statement) else {
+            continue;
+        };
+        let rest = &captures[2];
+        let lowered = rest.to_ascii_lowercase();
+        // restrictive policies only narrow what permissive ones grant
+        if lowered.contains("restrictive") {
+            continue;
+        }
+        let command = POLICY_COMMAND_RE
+            .captures(rest)
+            .map_or("all".to_string(), |command| command[1].to_ascii_lowercase());
+        if command == "select" {
+            continue;
+        }
+        // no TO clause means PUBLIC, which includes anon
+        let anon = POLICY_ROLES_RE.captures(rest).is_none_or(|roles| {
+            roles[1].split(',').any(|role| {
+                let role = role.trim().trim_matches('"').to_ascii_lowercase();
+                role == "anon" || role == "public"
+            })
+        });
+        if !anon || lowered.contains("auth.") {
+            continue;
+        }
+        findings.push(StorageFinding::AnonWritePolicy {
+            policy: captures[1].trim_matches('"').to_string(),
+            command,
+            bucket: BUCKET_ID_RE
+                .captures(rest)
+                .map(|bucket| bucket[1].to_string()),
+            line,
+        });
+    }
+
+    findings
+}
+
+// statements with the byte offset of their first non-blank character; `--` comments are
+// dropped and semicolons inside quotes do not split
+fn sql_statements(sql: &str) -> Vec<(usize, String)> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut start = None;
+    let mut in_quote = false;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((idx, ch)) = chars.next() {
+        if !in_quote && ch == '-' && chars.peek().is_some_and(|(_, next)| *next == '-') {
+            for (_, skipped) in chars.by_ref() {
+                if skipped == '\n' {
+                    break;
+                }
+            }
+            current.push('\n');
+            continue;
+        }
+        if ch == '\'' {
+            in_quote = !in_quote;
+        }
+        if ch == ';' && !in_quote {
+            if let Some(start) = start.take() {
+                statements.push((start, current.trim().to_string()));
+            }
+            current.clear();
+            continue;
+        }
+        if start.is_none() && !ch.is_whitespace() {
+            start = Some(idx);
+        }
+        current.push(ch);
+    }
+    if let Some(start) = start {
+        statements.push((start, current.trim().to_string()));
+    }
+
+    statements
+}
+
+// the top-level parenthesized tuples of a VALUES list, split on commas outside quotes
+fn value_rows(values: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut depth = 0;
+    let mut in_quote = false;
+
+    for ch in values.chars() {
+        match ch {
+            '\'' => {
+                in_quote = !in_quote;
+                field.push(ch);
+            }
+            '(' if !in_quote => {
+                depth += 1;
+                if depth > 1 {
+                    field.push(ch);
+                }
+            }
+            ')' if !in_quote => {
+                depth -= 1;
+                if depth == 0 {
+                    row.push(field.trim().to_string());
+                    rows.push(std::mem::take(&mut row));
+                    field.clear();
+                } else {
+                    field.push(ch);
+                }
+            }
+            ',' if !in_quote && depth == 1 => {
+                row.push(std::mem::take(&mut field).trim().to_string())
+            }
+            _ if depth >= 1 => field.push(ch),
+            _ => {}
+        }
+    }
+
+    rows
+}
+
 fn line_number(content: &str, byte_index: usize) -> usize {
     content[..byte_index]
         .bytes()
@@ -164,3 +525,66 @@ fn line_number(content: &str, byte_index: usize) -> usize {
         .count()
         + 1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_sources_are_recognized_by_directory() {
+        assert!(is_server_source("supabase/functions/invite/index.ts"));
+        assert!(is_server_source("apps/web/pages/api/users.ts"));
+        assert!(is_server_source("src/server/admin.mjs"));
+        assert!(!is_server_source("src/components/api.tsx"));
+        assert!(!is_server_source("server/README.md"));
+        assert!(PRIVILEGED_CALL_RE.is_match("await supabase.auth.admin.deleteUser(id)"));
+        assert!(ANON_KEY_RE.is_match("process.env.NEXT_PUBLIC_SUPABASE_ANON_KEY!"));
+    }
+
+    #[test]
+    fn flags_public_buckets_and_anonymous_storage_writes() {
+        let sql = r#"
+-- buckets
+insert into storage.buckets (id, name, public)
+values ('avatars', 'avatars', true), ('invoices', 'invoices', false);
+
+update storage.buckets set public = true where id = 'uploads';
+
+create policy "Anyone can upload" on storage.objects
+  for insert to anon, authenticated
+  with check (bucket_id = 'avatars');
+
+create policy "Owners update" on storage.objects
+  for update to public using (auth.uid() = owner);
+
+create policy "Public read" on storage.objects for select using (bucket_id = 'avatars');
+
+create policy open_bucket on storage.objects with check (bucket_id = 'uploads');
+"#;
+        assert_eq!(
+            storage_findings(sql),
+            vec![
+                StorageFinding::PublicBucket {
+                    bucket: "avatars".to_string(),
+                    line: 3,
+                },
+                StorageFinding::PublicBucket {
+                    bucket: "uploads".to_string(),
+                    line: 6,
+                },
+                StorageFinding::AnonWritePolicy {
+                    policy: "Anyone can upload".to_string(),
+                    command: "insert".to_string(),
+                    bucket: Some("avatars".to_string()),
+                    line: 8,
+                },
+                StorageFinding::AnonWritePolicy {
+                    policy: "open_bucket".to_string(),
+                    command: "all".to_string(),
+                    bucket: Some("uploads".to_string()),
+                    line: 17,
+                },
+            ]
+        );
+    }
+}
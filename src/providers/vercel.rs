@@ -1,11 +1,43 @@
-use crate::config::Config;
-use crate::core::{Issue, RepoContext, Severity, rules};
+use crate::config::{Config, DetectMarkers};
+use crate::core::issue::RuleSpec;
+use crate::core::{Issue, RemediationAction, RepoContext, Severity, rules, structured};
 use crate::providers::Provider;
-use crate::utils::{fs as fs_utils, git as git_utils};
+use crate::utils::fs as fs_utils;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde_json::Value;
-use std::fs;
+use std::collections::HashSet;
 use std::path::Path;
 
+const MIDDLEWARE_FILES: [&str; 4] = [
+    "middleware.ts",
+    "middleware.js",
+    "src/middleware.ts",
+    "src/middleware.js",
+];
+
+static ROUTE_FILE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:src/)?(?:app/(?:.*/)?route|pages/api/.*)\.(?:ts|js|tsx|jsx|mjs)$")
+        .expect("valid route file regex")
+});
+
+static EDGE_RUNTIME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bruntime\s*[=:]\s*['"](?:experimental-)?edge['"]"#)
+        .expect("valid edge runtime regex")
+});
+
+static ENV_REFERENCE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"process\.env(?:\.([A-Za-z_][A-Za-z0-9_]*)|\[\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]\s*\])"#,
+    )
+    .expect("valid env reference regex")
+});
+
+static EDGE_CONFIG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"https://edge-config\.vercel\.com/ecfg_[A-Za-z0-9]+\?token=[A-Za-z0-9_-]+")
+        .expect("valid edge config regex")
+});
+
 pub struct VercelProvider;
 
 impl Provider for VercelProvider {
@@ -17,13 +49,23 @@ impl Provider for VercelProvider {
         cfg.providers.vercel.enabled
     }
 
+    fn markers<'a>(&self, cfg: &'a Config) -> &'a DetectMarkers {
+        &cfg.providers.vercel.markers
+    }
+
+    fn skip_rules(&self) -> (RuleSpec, RuleSpec) {
+        (rules::VERCEL_PROVIDER_DISABLED, rules::VERCEL_NOT_DETECTED)
+    }
+
     fn detect(&self, ctx: &RepoContext) -> bool {
         ctx.repo_root.join("vercel.json").is_file()
             || ctx.has_vercel_dir
-            || ctx.package_json_contains("\"vercel\"")
+            || ctx.has_dependency("vercel")
+            || ctx.has_script_containing("vercel ")
+            || ctx.get_field("scripts.vercel-build").is_some()
     }
 
-    fn run_checks(&self, ctx: &RepoContext, _cfg: &Config) -> Vec<Issue> {
+    fn run_checks(&self, ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
         let mut issues = Vec::new();
 
         let vercel_json = ctx.repo_root.join("vercel.json");
@@ -43,11 +85,8 @@ impl Provider for VercelProvider {
 
         let dot_vercel = ctx.repo_root.join(".vercel");
         if dot_vercel.exists() {
-            let tracked = if let Some(repo) = &ctx.git_repo {
-                git_utils::has_tracked_prefix(repo, ".vercel").ok()
-            } else {
-                None
-            };
+            let relative_dir = fs_utils::relative_path(&ctx.repo_root, &dot_vercel);
+            let tracked = ctx.tracked_set().map(|set| set.has_prefix(".vercel"));
 
             match tracked {
                 Some(true) => issues.push(
@@ -57,7 +96,11 @@ impl Provider for VercelProvider {
                         ".vercel directory appears tracked",
                         "remove .vercel from git and add it to .gitignore",
                     )
-                    .with_file(fs_utils::relative_path(&ctx.repo_root, &dot_vercel)),
+                    .with_file(relative_dir.clone())
+                    .with_action(RemediationAction::UntrackFile {
+                        path: relative_dir.clone(),
+                    })
+                    .with_action(RemediationAction::GitignoreAdd { path: relative_dir }),
                 ),
                 Some(false) => {}
                 None => issues.push(
@@ -67,15 +110,95 @@ impl Provider for VercelProvider {
                         ".vercel directory exists locally",
                         "confirm .vercel is gitignored to avoid leaking local metadata",
                     )
-                    .with_file(fs_utils::relative_path(&ctx.repo_root, &dot_vercel)),
+                    .with_file(relative_dir.clone())
+                    .with_action(RemediationAction::GitignoreAdd { path: relative_dir }),
                 ),
             }
         }
 
+        if cfg.providers.vercel.check_edge_runtime {
+            issues.extend(scan_edge_runtime(ctx, cfg));
+        }
+
         issues
     }
 }
 
+// edge functions are bundled at build time, so whatever they read from the environment
+// ends up in the deployed artifact; edge config connection strings carry a read token
+fn scan_edge_runtime(ctx: &RepoContext, cfg: &Config) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for entry in ctx.walk_files(&ctx.repo_root, &cfg.scan, "vercel edge runtime scan") {
+        let Some(bytes) = ctx.read_scannable(entry.path(), &cfg.scan) else {
+            continue;
+        };
+        let content = fs_utils::decode_text(&bytes);
+        let relative_file = fs_utils::relative_path(&ctx.repo_root, entry.path());
+
+        if ctx.tracked_status(entry.path()) != Some(false) {
+            for hit in EDGE_CONFIG_RE.find_iter(&content) {
+                issues.push(
+                    Issue::from_rule(
+                        rules::VERCEL_EDGE_CONFIG_COMMITTED,
+                        Severity::Error,
+                        "Edge Config connection string committed",
+                        "rotate the Edge Config token and read the connection string from the EDGE_CONFIG env var",
+                    )
+                    .with_file(relative_file.clone())
+                    .with_line(line_number(&content, hit.start()))
+                    .with_action(RemediationAction::rotate_key("vercel")),
+                );
+            }
+        }
+
+        let is_edge = MIDDLEWARE_FILES.contains(&relative_file.as_str())
+            || (ROUTE_FILE_RE.is_match(&relative_file) && EDGE_RUNTIME_RE.is_match(&content));
+        if !is_edge {
+            continue;
+        }
+        let mut reported = HashSet::new();
+        for captures in ENV_REFERENCE_RE.captures_iter(&content) {
+            let Some(name) = captures.get(1).or_else(|| captures.get(2)) else {
+                continue;
+            };
+            if !is_secret_env_name(name.as_str()) || !reported.insert(name.as_str()) {
+                continue;
+            }
+            issues.push(
+                Issue::from_rule(
+                    rules::VERCEL_EDGE_SECRET_ENV,
+                    Severity::Warning,
+                    format!("edge runtime code reads secret env var {}", name.as_str()),
+                    "move the secret-dependent logic to a Node.js serverless function or route handler",
+                )
+                .with_file(relative_file.clone())
+                .with_line(line_number(&content, name.start())),
+            );
+        }
+    }
+
+    issues
+}
+
+fn is_secret_env_name(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    let public = ["NEXT_PUBLIC_", "VITE_", "PUBLIC_"]
+        .iter()
+        .any(|prefix| upper.starts_with(prefix))
+        || upper.contains("ANON")
+        || upper.contains("PUBLISHABLE");
+    !public && (structured::is_secret_key(name) || upper.contains("SERVICE_ROLE"))
+}
+
+fn line_number(content: &str, byte_index: usize) -> usize {
+    content[..byte_index]
+        .bytes()
+        .filter(|byte| *byte == b'\n')
+        .count()
+        + 1
+}
+
 fn contains_key_recursive(value: &Value, key: &str) -> bool {
     match value {
         Value::Object(map) => {
@@ -94,10 +217,33 @@ fn parse_vercel_json(path: &Path) -> Option<Value> {
         return None;
     }
 
-    let raw = match fs::read_to_string(path) {
+    let raw = match fs_utils::read_text(path) {
         Ok(raw) => raw,
         Err(_) => return None,
     };
 
     serde_json::from_str::<Value>(&raw).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_edge_files_and_secret_env_names() {
+        assert!(ROUTE_FILE_RE.is_match("app/api/checkout/route.ts"));
+        assert!(ROUTE_FILE_RE.is_match("src/pages/api/hello.js"));
+        assert!(!ROUTE_FILE_RE.is_match("app/dashboard/page.tsx"));
+        assert!(EDGE_RUNTIME_RE.is_match("export const runtime = 'edge';"));
+        assert!(EDGE_RUNTIME_RE.is_match("export const config = { runtime: \"edge\" };"));
+
+        assert!(is_secret_env_name("STRIPE_SECRET_KEY"));
+        assert!(is_secret_env_name("SUPABASE_SERVICE_ROLE_KEY"));
+        assert!(!is_secret_env_name("NEXT_PUBLIC_SUPABASE_ANON_KEY"));
+        assert!(!is_secret_env_name("DATABASE_URL"));
+
+        let content =
+            "const ecfg = \"https://edge-config.vercel.com/ecfg_abc123?token=0f1e-2d3c\";";
+        assert!(EDGE_CONFIG_RE.is_match(content));
+    }
+}
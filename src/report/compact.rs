@@ -0,0 +1,63 @@
+use crate::core::{Issue, Severity};
+use crate::report::{FinalReport, RenderOptions};
+
+// one `severity:category:rule:file:line:title` line per issue, like compiler diagnostics.
+// nothing is wrapped or colored so log pipelines can split on the first five colons.
+pub fn render(report: &FinalReport, options: RenderOptions) -> String {
+    let show_passes = options.shows_passes(false);
+    let issues = report
+        .issues
+        .iter()
+        .filter(|issue| show_passes || issue.severity != Severity::Pass);
+    let limit = options.max_issues.unwrap_or(usize::MAX);
+
+    let mut out = String::new();
+    for issue in issues.take(limit) {
+        out.push_str(&render_issue(issue));
+        out.push('\n');
+    }
+    out
+}
+
+fn render_issue(issue: &Issue) -> String {
+    format!(
+        "{}:{}:{}:{}:{}:{}",
+        issue.severity.slug(),
+        issue.category_slug(),
+        issue.code,
+        issue.file.as_deref().unwrap_or_default(),
+        issue.line.map(|line| line.to_string()).unwrap_or_default(),
+        single_line(&issue.title)
+    )
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::sample_report;
+
+    #[test]
+    fn one_line_per_issue_without_passes() {
+        let rendered = render(&sample_report(), RenderOptions::default());
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "error:secrets:DG_SEC_004:config/secrets.env:7:AWS access key pattern detected",
+                "warning:env:DG_ENV_001:::missing required env var DATABASE_URL",
+                "info:git:DG_GIT_002:::working tree has changes",
+            ]
+        );
+
+        let options = RenderOptions {
+            passes: Some(true),
+            max_issues: Some(10),
+            ..RenderOptions::default()
+        };
+        assert_eq!(render(&sample_report(), options).lines().count(), 4);
+    }
+}
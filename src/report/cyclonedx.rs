@@ -0,0 +1,223 @@
+use crate::core::Issue;
+use crate::report::FinalReport;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+const SPEC_VERSION: &str = "1.5";
+const REPOSITORY_REF: &str = "repository";
+
+// a standalone CycloneDX BOM whose only component is the scanned repository; findings go
+// in `vulnerabilities` so SBOM tooling can merge or attach them
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: Metadata,
+    vulnerabilities: Vec<Vulnerability>,
+}
+
+#[derive(Debug, Serialize)]
+struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    tools: Tools,
+    component: Component,
+}
+
+#[derive(Debug, Serialize)]
+struct Tools {
+    components: Vec<Component>,
+}
+
+#[derive(Debug, Serialize)]
+struct Component {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    bom_ref: Option<&'static str>,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Vulnerability {
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    id: &'static str,
+    source: Source,
+    ratings: Vec<Rating>,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    recommendation: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    advisories: Vec<Advisory>,
+    affects: Vec<Affects>,
+    properties: Vec<Property>,
+}
+
+#[derive(Debug, Serialize)]
+struct Source {
+    name: &'static str,
+    url: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Advisory {
+    url: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Rating {
+    severity: &'static str,
+    method: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Affects {
+    #[serde(rename = "ref")]
+    target: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Property {
+    name: &'static str,
+    value: String,
+}
+
+pub fn render(report: &FinalReport) -> Result<String> {
+    let repository = Path::new(&report.repository_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| report.repository_path.clone());
+    let run = report.run.as_ref();
+
+    // the fingerprint ignores line numbers, so repeats in one file get a counter to keep
+    // bom-refs unique
+    let mut seen = HashMap::<String, usize>::new();
+    let vulnerabilities = report
+        .issues
+        .iter()
+        .filter_map(|issue| {
+            let fingerprint = issue.fingerprint();
+            let count = seen.entry(fingerprint.clone()).or_default();
+            *count += 1;
+            let bom_ref = match *count {
+                1 => format!("devguard:{}", fingerprint),
+                n => format!("devguard:{}:{}", fingerprint, n),
+            };
+            vulnerability(issue, fingerprint, bom_ref)
+        })
+        .collect();
+
+    let bom = Bom {
+        bom_format: "CycloneDX",
+        spec_version: SPEC_VERSION,
+        version: 1,
+        metadata: Metadata {
+            timestamp: run.and_then(|run| run.started_at.clone()),
+            tools: Tools {
+                components: vec![Component {
+                    kind: "application",
+                    bom_ref: None,
+                    name: report.tool.name.to_string(),
+                    version: Some(report.tool.version.to_string()),
+                }],
+            },
+            component: Component {
+                kind: "application",
+                bom_ref: Some(REPOSITORY_REF),
+                name: repository,
+                version: run.and_then(|run| run.git_commit.clone()),
+            },
+        },
+        vulnerabilities,
+    };
+
+    Ok(format!("{}\n", serde_json::to_string_pretty(&bom)?))
+}
+
+fn vulnerability(issue: &Issue, fingerprint: String, bom_ref: String) -> Option<Vulnerability> {
+    let severity = issue.severity.cyclonedx_severity()?;
+
+    let mut properties = vec![
+        Property {
+            name: "devguard:fingerprint",
+            value: fingerprint,
+        },
+        Property {
+            name: "devguard:severity",
+            value: issue.severity.slug().to_string(),
+        },
+        Property {
+            name: "devguard:category",
+            value: issue.category_slug(),
+        },
+    ];
+    if let Some(location) = issue.location() {
+        properties.push(Property {
+            name: "devguard:location",
+            value: location,
+        });
+    }
+
+    Some(Vulnerability {
+        bom_ref,
+        id: issue.code,
+        source: Source {
+            name: "devguard",
+            url: env!("CARGO_PKG_REPOSITORY"),
+        },
+        ratings: vec![Rating {
+            severity,
+            method: "other",
+        }],
+        description: issue.title.clone(),
+        detail: issue.description.clone(),
+        recommendation: issue.remediation.clone(),
+        advisories: issue.url.map(|url| Advisory { url }).into_iter().collect(),
+        affects: vec![Affects {
+            target: REPOSITORY_REF,
+        }],
+        properties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::sample_report;
+    use serde_json::Value;
+
+    #[test]
+    fn findings_become_vulnerabilities_keyed_by_fingerprint() {
+        let report = sample_report();
+        let parsed: Value =
+            serde_json::from_str(&render(&report).expect("renders")).expect("parses");
+
+        assert_eq!(parsed["bomFormat"], "CycloneDX");
+        assert_eq!(parsed["metadata"]["component"]["name"], "devguard-example");
+        let vulnerabilities = parsed["vulnerabilities"].as_array().expect("array");
+        // the pass finding is not a vulnerability
+        assert_eq!(vulnerabilities.len(), 3);
+
+        let aws = &vulnerabilities[0];
+        assert_eq!(aws["id"], "DG_SEC_004");
+        assert_eq!(aws["ratings"][0]["severity"], "high");
+        assert_eq!(aws["affects"][0]["ref"], "repository");
+        assert_eq!(
+            aws["bom-ref"],
+            format!("devguard:{}", report.issues[0].fingerprint())
+        );
+        assert_eq!(
+            aws["properties"][0]["value"],
+            report.issues[0].fingerprint()
+        );
+        assert_eq!(aws["properties"][3]["value"], "config/secrets.env:7");
+    }
+}
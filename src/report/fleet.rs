@@ -0,0 +1,358 @@
+use crate::core::Severity;
+use crate::report::REPORT_SCHEMA_VERSION;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+pub const FLEET_SCHEMA_VERSION: &str = "1";
+
+// the few fields of a saved json report a fleet summary needs; the saved score is used as is
+#[derive(Debug, Deserialize)]
+struct SavedReport {
+    schema_version: String,
+    repository_path: String,
+    score: u8,
+    passed: bool,
+    issues: Vec<SavedIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedIssue {
+    code: String,
+    severity: Severity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetSummary {
+    pub schema_version: String,
+    pub reports: usize,
+    pub average_score: f64,
+    pub median_score: f64,
+    pub passed: usize,
+    pub failed: usize,
+    // worst first
+    pub repositories: Vec<RepositoryScore>,
+    // rules by how many repositories they fire in, pass findings excluded
+    pub rules: Vec<RuleFrequency>,
+    // earlier summaries given with --previous, oldest first, then this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<HistoryPoint>,
+    // repositories whose score moved since the most recent earlier summary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<ScoreChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryScore {
+    pub repository: String,
+    pub score: u8,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleFrequency {
+    pub code: String,
+    pub repositories: usize,
+    pub findings: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    pub reports: usize,
+    pub average_score: f64,
+    pub median_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreChange {
+    pub repository: String,
+    pub previous: u8,
+    pub current: u8,
+    pub delta: i16,
+}
+
+pub fn summarize(
+    reports: &[impl AsRef<Path>],
+    previous: &[impl AsRef<Path>],
+) -> Result<FleetSummary> {
+    if reports.is_empty() {
+        bail!("pass at least one json report to summarize");
+    }
+    let saved = reports
+        .iter()
+        .map(|path| load_report(path.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    let previous = previous
+        .iter()
+        .map(|path| load_summary(path.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(build(saved, &previous))
+}
+
+fn load_report(path: &Path) -> Result<SavedReport> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed reading {}", path.display()))?;
+    let report = serde_json::from_str::<SavedReport>(&text)
+        .with_context(|| format!("{} is not a devguard json report", path.display()))?;
+    if report.schema_version != REPORT_SCHEMA_VERSION {
+        bail!(
+            "{} has unsupported report schema {}",
+            path.display(),
+            report.schema_version
+        );
+    }
+    Ok(report)
+}
+
+fn load_summary(path: &Path) -> Result<FleetSummary> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed reading {}", path.display()))?;
+    let summary = serde_json::from_str::<FleetSummary>(&text).with_context(|| {
+        format!(
+            "{} is not a fleet summary (write one with `devguard summarize --json`)",
+            path.display()
+        )
+    })?;
+    if summary.schema_version != FLEET_SCHEMA_VERSION {
+        bail!(
+            "{} has unsupported summary schema {}",
+            path.display(),
+            summary.schema_version
+        );
+    }
+    Ok(summary)
+}
+
+fn build(saved: Vec<SavedReport>, previous: &[FleetSummary]) -> FleetSummary {
+    let mut scores = saved.iter().map(|report| report.score).collect::<Vec<_>>();
+    scores.sort_unstable();
+    let average_score =
+        round(scores.iter().map(|score| f64::from(*score)).sum::<f64>() / scores.len() as f64);
+    let median_score = median(&scores);
+
+    let mut rules: BTreeMap<&str, (BTreeSet<&str>, usize)> = BTreeMap::new();
+    for report in &saved {
+        for issue in report
+            .issues
+            .iter()
+            .filter(|issue| issue.severity != Severity::Pass)
+        {
+            let entry = rules.entry(issue.code.as_str()).or_default();
+            entry.0.insert(report.repository_path.as_str());
+            entry.1 += 1;
+        }
+    }
+    let mut rules = rules
+        .into_iter()
+        .map(|(code, (repositories, findings))| RuleFrequency {
+            code: code.to_string(),
+            repositories: repositories.len(),
+            findings,
+        })
+        .collect::<Vec<_>>();
+    rules.sort_by(|a, b| {
+        b.repositories
+            .cmp(&a.repositories)
+            .then(b.findings.cmp(&a.findings))
+            .then(a.code.cmp(&b.code))
+    });
+
+    let mut repositories = saved
+        .iter()
+        .map(|report| RepositoryScore {
+            repository: report.repository_path.clone(),
+            score: report.score,
+            passed: report.passed,
+        })
+        .collect::<Vec<_>>();
+    repositories.sort_by(|a, b| a.score.cmp(&b.score).then(a.repository.cmp(&b.repository)));
+
+    let changes = previous
+        .last()
+        .map(|last| {
+            let before = last
+                .repositories
+                .iter()
+                .map(|repo| (repo.repository.as_str(), repo.score))
+                .collect::<BTreeMap<_, _>>();
+            let mut changes = repositories
+                .iter()
+                .filter_map(|repo| {
+                    let previous = *before.get(repo.repository.as_str())?;
+                    (previous != repo.score).then(|| ScoreChange {
+                        repository: repo.repository.clone(),
+                        previous,
+                        current: repo.score,
+                        delta: i16::from(repo.score) - i16::from(previous),
+                    })
+                })
+                .collect::<Vec<_>>();
+            changes.sort_by(|a, b| a.delta.cmp(&b.delta).then(a.repository.cmp(&b.repository)));
+            changes
+        })
+        .unwrap_or_default();
+
+    let current = HistoryPoint {
+        reports: saved.len(),
+        average_score,
+        median_score,
+    };
+    let history = if previous.is_empty() {
+        Vec::new()
+    } else {
+        previous
+            .iter()
+            .map(|summary| HistoryPoint {
+                reports: summary.reports,
+                average_score: summary.average_score,
+                median_score: summary.median_score,
+            })
+            .chain([current])
+            .collect()
+    };
+
+    let passed = saved.iter().filter(|report| report.passed).count();
+    FleetSummary {
+        schema_version: FLEET_SCHEMA_VERSION.to_string(),
+        reports: saved.len(),
+        average_score,
+        median_score,
+        passed,
+        failed: saved.len() - passed,
+        repositories,
+        rules,
+        history,
+        changes,
+    }
+}
+
+fn median(sorted: &[u8]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (f64::from(sorted[mid - 1]) + f64::from(sorted[mid])) / 2.0
+    } else {
+        f64::from(sorted[mid])
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+pub fn render(summary: &FleetSummary, json: bool, top: usize) -> Result<String> {
+    if json {
+        return Ok(format!("{}\n", serde_json::to_string_pretty(summary)?));
+    }
+
+    let mut lines = vec![
+        format!(
+            "Fleet: {} report(s) | passed {} | failed {}",
+            summary.reports, summary.passed, summary.failed
+        ),
+        format!(
+            "Score: average {:.1} | median {:.1}",
+            summary.average_score, summary.median_score
+        ),
+    ];
+    if let [.., before, _] = summary.history.as_slice() {
+        lines.push(format!(
+            "Trend: average {:+.1} | median {:+.1} since the previous summary",
+            summary.average_score - before.average_score,
+            summary.median_score - before.median_score
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("WORST REPOSITORIES".to_string());
+    for repo in summary.repositories.iter().take(top) {
+        lines.push(format!(
+            "- {:>3} {} {}",
+            repo.score,
+            if repo.passed { "pass" } else { "FAIL" },
+            repo.repository
+        ));
+    }
+
+    if !summary.rules.is_empty() {
+        lines.push(String::new());
+        lines.push("MOST COMMON RULES".to_string());
+        for rule in summary.rules.iter().take(top) {
+            lines.push(format!(
+                "- {} in {} repo(s), {} finding(s)",
+                rule.code, rule.repositories, rule.findings
+            ));
+        }
+    }
+
+    if !summary.changes.is_empty() {
+        lines.push(String::new());
+        lines.push("SCORE CHANGES".to_string());
+        for change in &summary.changes {
+            lines.push(format!(
+                "- {:+} {} ({} -> {})",
+                change.delta, change.repository, change.previous, change.current
+            ));
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(repository: &str, score: u8, codes: &[&str]) -> SavedReport {
+        SavedReport {
+            schema_version: REPORT_SCHEMA_VERSION.to_string(),
+            repository_path: repository.to_string(),
+            score,
+            passed: score >= 80,
+            issues: codes
+                .iter()
+                .map(|code| SavedIssue {
+                    code: code.to_string(),
+                    severity: Severity::Warning,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn aggregates_scores_rules_and_changes_since_the_last_summary() {
+        let first = build(vec![report("api", 60, &[]), report("web", 90, &[])], &[]);
+        assert!(first.history.is_empty());
+
+        let summary = build(
+            vec![
+                report("api", 70, &["DG_ENV_001", "DG_ENV_001"]),
+                report("web", 85, &["DG_ENV_001", "DG_GIT_010"]),
+                report("docs", 100, &[]),
+            ],
+            &[first],
+        );
+        assert_eq!(summary.reports, 3);
+        assert_eq!(summary.average_score, 85.0);
+        assert_eq!(summary.median_score, 85.0);
+        assert_eq!((summary.passed, summary.failed), (2, 1));
+        assert_eq!(summary.repositories[0].repository, "api");
+
+        assert_eq!(summary.rules[0].code, "DG_ENV_001");
+        assert_eq!(summary.rules[0].repositories, 2);
+        assert_eq!(summary.rules[0].findings, 3);
+
+        assert_eq!(summary.history.len(), 2);
+        assert_eq!(summary.history[0].average_score, 75.0);
+        let deltas = summary
+            .changes
+            .iter()
+            .map(|change| (change.repository.as_str(), change.delta))
+            .collect::<Vec<_>>();
+        assert_eq!(deltas, [("web", -5), ("api", 10)]);
+
+        let rendered = render(&summary, false, 5).expect("renders");
+        assert!(rendered.contains("Trend: average +10.0 | median +10.0"));
+    }
+}
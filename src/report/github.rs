@@ -0,0 +1,90 @@
+use crate::core::{Issue, Severity};
+use crate::report::{FinalReport, RenderOptions};
+
+// GitHub Actions workflow commands: each finding becomes an annotation on its file and
+// line in the run and the pull request diff, followed by a one-line score summary
+pub fn render(report: &FinalReport, options: RenderOptions) -> String {
+    let limit = options.max_issues.unwrap_or(usize::MAX);
+    let mut out = String::new();
+    for issue in report
+        .issues
+        .iter()
+        .filter(|issue| issue.severity != Severity::Pass)
+        .take(limit)
+    {
+        out.push_str(&render_issue(issue));
+        out.push('\n');
+    }
+    out.push_str(&format!(
+        "devguard: score {}/{} ({}) {}; error {} | warning {} | info {}\n",
+        report.score,
+        report.max_score,
+        report.label,
+        if report.passed { "pass" } else { "fail" },
+        report.counts.error,
+        report.counts.warning,
+        report.counts.info
+    ));
+    out
+}
+
+fn render_issue(issue: &Issue) -> String {
+    let level = match issue.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        _ => "notice",
+    };
+    let mut properties = Vec::new();
+    if let Some(file) = &issue.file {
+        properties.push(format!("file={}", escape_property(file)));
+        if let Some(line) = issue.line {
+            properties.push(format!("line={}", line));
+        }
+        if let Some(end_line) = issue.end_line {
+            properties.push(format!("endLine={}", end_line));
+        }
+    }
+    properties.push(format!(
+        "title={}",
+        escape_property(&format!("{} {}", issue.code, issue.title))
+    ));
+    format!(
+        "::{} {}::{}",
+        level,
+        properties.join(","),
+        escape_data(&issue.remediation)
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::sample_report;
+
+    #[test]
+    fn annotates_findings_and_ends_with_the_score() {
+        let rendered = render(&sample_report(), RenderOptions::default());
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(
+            "::error file=config/secrets.env,line=7,title=DG_SEC_004 AWS access key pattern detected::"
+        ));
+        assert!(
+            lines[1]
+                .starts_with("::warning title=DG_ENV_001 missing required env var DATABASE_URL::")
+        );
+        assert!(lines[2].starts_with("::notice title=DG_GIT_002 working tree has changes::"));
+        assert!(lines[3].starts_with("devguard: score "));
+    }
+}
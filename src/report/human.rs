@@ -1,6 +1,8 @@
-use crate::core::{Issue, Severity};
-use crate::report::{FinalReport, RenderOptions, issue_location};
-use crate::score::PenaltyProfile;
+use crate::core::suppress::{Mechanism, SuppressedFinding};
+use crate::core::{Category, Issue, Severity, remediate};
+use crate::report::{FinalReport, GroupBy, RenderOptions, issue_location};
+use crate::score::{PenaltyProfile, REPOSITORY_DIRECTORY};
+use std::collections::BTreeMap;
 
 pub fn render(report: &FinalReport, options: RenderOptions) -> String {
     let mut sections = Vec::new();
@@ -8,6 +10,31 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         "DevGuard {} | {}",
         report.tool.version, report.repository_path
     ));
+    if let Some(run) = &report.run {
+        let mut parts = run.started_at.iter().cloned().collect::<Vec<_>>();
+        if let Some(commit) = &run.git_commit {
+            parts.push(match &run.git_branch {
+                Some(branch) => format!("{} ({})", &commit[..commit.len().min(12)], branch),
+                None => commit[..commit.len().min(12)].to_string(),
+            });
+        }
+        parts.push(format!(
+            "{} files scanned, {} skipped",
+            run.files_scanned, run.files_skipped
+        ));
+        if let Some(duration_ms) = run.duration_ms {
+            parts.push(format!("{}ms", duration_ms));
+        }
+        sections.push(format!("Run: {}", parts.join(" | ")));
+    }
+    if !report.providers.is_empty() {
+        let providers = report
+            .providers
+            .iter()
+            .map(|provider| provider.summary())
+            .collect::<Vec<_>>();
+        sections.push(format!("Providers: {}", providers.join(" | ")));
+    }
     sections.push(format!(
         "Score: {}/{} ({})",
         report.score, report.max_score, report.label
@@ -31,12 +58,30 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         report.counts.pass,
         report.counts.total
     ));
+    if !report.suppressed.is_empty() {
+        sections.push(format!("Suppressed: {}", suppressed_counts(report)));
+    }
+    if !report.owners.is_empty() {
+        let owners = report
+            .owners
+            .iter()
+            .map(|summary| {
+                format!(
+                    "{} {}",
+                    summary.owner.as_deref().unwrap_or("unowned"),
+                    summary.counts.total - summary.counts.pass
+                )
+            })
+            .collect::<Vec<_>>();
+        sections.push(format!("Owners: {}", owners.join(" | ")));
+    }
     sections.push(format!(
-        "Penalty totals: error -{} | warning -{} | info -{} | total -{}",
+        "Penalty totals: error -{} | warning -{} | info -{} | total -{} | pass credit +{}",
         report.scoring.by_severity.error.penalty,
         report.scoring.by_severity.warning.penalty,
         report.scoring.by_severity.info.penalty,
-        report.scoring.total_deductions
+        report.scoring.total_deductions,
+        report.scoring.total_credits
     ));
 
     if options.summary_only {
@@ -44,38 +89,199 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         return sections.join("\n") + "\n";
     }
 
+    if options.summary {
+        return sections.join("\n") + "\n";
+    }
+
+    let groups = group_issues(report, options);
+    let total = groups.iter().map(|(_, issues)| issues.len()).sum::<usize>();
+    let limit = options.max_issues.unwrap_or(usize::MAX);
+    let mut shown = 0;
     let mut grouped = Vec::new();
-    for severity in Severity::ALL {
-        let issues = report
-            .issues
-            .iter()
-            .filter(|issue| issue.severity == severity)
-            .collect::<Vec<_>>();
-        if issues.is_empty() {
-            continue;
+    for (heading, issues) in groups {
+        if shown >= limit {
+            break;
         }
-
         grouped.push(String::new());
-        grouped.push(format!(
-            "{} ({})",
-            render_severity(severity, options.color),
-            issues.len()
-        ));
-
-        for issue in issues {
+        grouped.push(format!("{} ({})", heading, issues.len()));
+        for issue in issues.iter().take(limit - shown) {
             grouped.push(render_issue(issue));
+            shown += 1;
         }
     }
+    let hidden = total - shown;
+    if hidden > 0 {
+        grouped.push(String::new());
+        grouped.push(format!(
+            "... and {} more issue(s); raise --max-issues to see them",
+            hidden
+        ));
+    }
 
     if grouped.is_empty() {
         sections.push("No issues detected.".to_string());
     } else {
         sections.push(grouped.join("\n"));
     }
+    if !report.suppressed.is_empty() {
+        sections.push(String::new());
+        sections.push(format!("SUPPRESSED ({})", report.suppressed.len()));
+        sections.extend(report.suppressed.iter().map(render_suppressed));
+    }
 
     sections.join("\n") + "\n"
 }
 
+// `2 (suppression 1 | allowlist 1)`, mechanisms in a fixed order
+pub(crate) fn suppressed_counts(report: &FinalReport) -> String {
+    let parts = [
+        Mechanism::Suppression,
+        Mechanism::RuleOff,
+        Mechanism::Allowlist,
+    ]
+    .into_iter()
+    .filter_map(|mechanism| {
+        let count = report
+            .suppressed
+            .iter()
+            .filter(|finding| finding.mechanism == mechanism)
+            .count();
+        (count > 0).then(|| format!("{} {}", mechanism.label(), count))
+    })
+    .collect::<Vec<_>>();
+    let penalty = match report.scoring.suppressed_penalty {
+        0 => String::new(),
+        penalty => format!(", -{} score", penalty),
+    };
+    format!(
+        "{} ({}){}",
+        report.suppressed.len(),
+        parts.join(" | "),
+        penalty
+    )
+}
+
+fn render_suppressed(finding: &SuppressedFinding) -> String {
+    let mut line = format!(
+        "- [{}] [{}] {}",
+        finding.severity.label(),
+        finding.code,
+        finding.title
+    );
+    if let Some(location) = finding.location() {
+        line.push_str(&format!(" ({})", location));
+    }
+    line.push_str(&format!("\n  via {}", finding.mechanism.label()));
+    if let Some(owner) = &finding.owner {
+        line.push_str(&format!(" by {}", owner));
+    }
+    if let Some(reason) = finding
+        .reason
+        .as_deref()
+        .filter(|reason| !reason.is_empty())
+    {
+        line.push_str(&format!(": {}", reason));
+    }
+    if let Some(expires) = &finding.expires {
+        line.push_str(&format!(" (expires {})", expires));
+    }
+    line
+}
+
+fn group_issues(report: &FinalReport, options: RenderOptions) -> Vec<(String, Vec<&Issue>)> {
+    let show_passes = options.shows_passes(false);
+    let visible = report
+        .issues
+        .iter()
+        .filter(|issue| show_passes || issue.severity != Severity::Pass)
+        .collect::<Vec<_>>();
+    match options.group_by {
+        GroupBy::Severity => Severity::ALL
+            .iter()
+            .map(|severity| {
+                (
+                    render_severity(*severity, options.color),
+                    visible
+                        .iter()
+                        .copied()
+                        .filter(|issue| issue.severity == *severity)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(_, issues)| !issues.is_empty())
+            .collect(),
+        GroupBy::Category => {
+            // built-in categories in their usual order, then custom pattern labels
+            let mut custom = BTreeMap::<&str, Vec<&Issue>>::new();
+            for issue in &visible {
+                if let Some(label) = &issue.custom_category {
+                    custom.entry(label).or_default().push(issue);
+                }
+            }
+            Category::ALL
+                .iter()
+                .map(|category| {
+                    (
+                        category.label().to_string(),
+                        visible
+                            .iter()
+                            .copied()
+                            .filter(|issue| {
+                                issue.category == *category && issue.custom_category.is_none()
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .chain(
+                    custom
+                        .into_iter()
+                        .map(|(label, issues)| (label.to_string(), issues)),
+                )
+                .filter(|(_, issues)| !issues.is_empty())
+                .collect()
+        }
+        GroupBy::File => {
+            let mut by_file = BTreeMap::<&str, Vec<&Issue>>::new();
+            for issue in visible {
+                by_file
+                    .entry(issue.file.as_deref().unwrap_or(REPOSITORY_DIRECTORY))
+                    .or_default()
+                    .push(issue);
+            }
+            by_file
+                .into_iter()
+                .map(|(file, issues)| (file.to_string(), issues))
+                .collect()
+        }
+        GroupBy::Dir => {
+            let mut by_dir = BTreeMap::<&str, Vec<&Issue>>::new();
+            for issue in visible {
+                by_dir
+                    .entry(issue.directory().unwrap_or(REPOSITORY_DIRECTORY))
+                    .or_default()
+                    .push(issue);
+            }
+            // worst directories first, in the order the score breakdown ranks them
+            let mut groups = report
+                .scoring
+                .by_directory
+                .iter()
+                .filter_map(|dir| {
+                    by_dir
+                        .remove(dir.directory.as_str())
+                        .map(|issues| (format!("{} [-{}]", dir.directory, dir.penalty), issues))
+                })
+                .collect::<Vec<_>>();
+            groups.extend(
+                by_dir
+                    .into_iter()
+                    .map(|(dir, issues)| (dir.to_string(), issues)),
+            );
+            groups
+        }
+    }
+}
+
 fn render_summary_issues(report: &FinalReport) -> String {
     let mut lines = Vec::new();
     let visible = report
@@ -131,7 +337,7 @@ fn render_issue(issue: &Issue) -> String {
         "- [{}] [{}] ({}) {}{}{}",
         issue.severity.label(),
         issue.code,
-        issue.category,
+        issue.category_label(),
         issue.title,
         if penalty > 0 {
             format!(" [-{}]", penalty)
@@ -141,9 +347,28 @@ fn render_issue(issue: &Issue) -> String {
         location
     ));
     lines.push(format!("  remediation: {}", issue.remediation));
+    if remediate::supports(issue) {
+        lines.push(format!(
+            "  history cleanup: devguard remediate {}",
+            issue.fingerprint()
+        ));
+    }
     if let Some(description) = &issue.description {
         lines.push(format!("  details: {}", description));
     }
+    if issue.duplicates > 0 {
+        lines.push(format!("  duplicates merged: {}", issue.duplicates));
+    }
+    if let (Some(by), Some(at)) = (&issue.introduced_by, &issue.introduced_at) {
+        lines.push(format!("  introduced: {} by {}", at, by));
+    }
+    if let Some(snippet) = &issue.snippet {
+        lines.push("  context:".to_string());
+        lines.extend(snippet.iter().map(|line| format!("    {}", line)));
+    }
+    if let Some(url) = issue.url {
+        lines.push(format!("  docs: {}", url));
+    }
     lines.join("\n")
 }
 
@@ -154,3 +379,97 @@ fn render_severity(severity: Severity, color: bool) -> String {
         severity.label().to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{ProviderRun, ProviderStatus, record_suppressed, sample_report};
+
+    #[test]
+    fn suppressed_findings_are_listed_and_can_cost_points() {
+        let mut report = sample_report();
+        let score = report.score;
+        let mut finding = SuppressedFinding::new(
+            &report.issues[0],
+            Mechanism::Suppression,
+            "rotated, history rewrite pending",
+        );
+        finding.owner = Some("security@example.com".to_string());
+        let policy = crate::config::PolicyConfig {
+            suppressed_penalty: Some(3),
+            ..Default::default()
+        };
+        record_suppressed(&mut report, vec![finding], &policy);
+
+        assert_eq!(report.scoring.suppressed_penalty, 3);
+        assert_eq!(report.score, score.saturating_sub(3));
+        let rendered = render(&report, RenderOptions::default());
+        assert!(rendered.contains("Suppressed: 1 (suppression 1), -3 score"));
+        assert!(rendered.contains("SUPPRESSED (1)"));
+        assert!(
+            rendered.contains(
+                "via suppression by security@example.com: rotated, history rewrite pending"
+            )
+        );
+    }
+
+    #[test]
+    fn max_issues_truncates_with_remaining_count() {
+        let report = sample_report();
+        let rendered = render(
+            &report,
+            RenderOptions {
+                max_issues: Some(1),
+                group_by: GroupBy::File,
+                ..RenderOptions::default()
+            },
+        );
+        assert_eq!(rendered.matches("\n- [").count(), 1);
+        let listed = report
+            .issues
+            .iter()
+            .filter(|issue| issue.severity != Severity::Pass)
+            .count();
+        assert!(rendered.contains(&format!("... and {} more issue(s)", listed - 1)));
+    }
+
+    #[test]
+    fn summary_mode_omits_issue_listing() {
+        let rendered = render(
+            &sample_report(),
+            RenderOptions {
+                summary: true,
+                ..RenderOptions::default()
+            },
+        );
+        assert!(rendered.contains("Counts:"));
+        assert!(!rendered.contains("remediation:"));
+    }
+
+    #[test]
+    fn provider_outcomes_are_listed_in_the_header() {
+        let mut report = sample_report();
+        report.providers = vec![
+            ProviderRun {
+                name: "supabase",
+                enabled: true,
+                detected: true,
+                status: ProviderStatus::Ran,
+                issues: 2,
+                duration_ms: Some(4),
+            },
+            ProviderRun {
+                name: "stripe",
+                enabled: true,
+                detected: false,
+                status: ProviderStatus::NotDetected,
+                issues: 0,
+                duration_ms: None,
+            },
+        ];
+        let rendered = render(&report, RenderOptions::default());
+        assert!(
+            rendered.contains("Providers: supabase ran (2 issue(s), 4ms) | stripe not detected")
+        );
+    }
+}
@@ -1,8 +1,36 @@
-use crate::report::FinalReport;
+use crate::core::Severity;
+use crate::report::{ExitStatus, FinalReport, RenderOptions};
 use anyhow::Result;
+use serde::Serialize;
 
-pub fn render(report: &FinalReport) -> Result<String> {
-    Ok(format!("{}\n", serde_json::to_string_pretty(report)?))
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    #[serde(flatten)]
+    report: &'a FinalReport,
+    exit: ExitStatus,
+}
+
+pub fn render(report: &FinalReport, options: RenderOptions) -> Result<String> {
+    let exit = report.exit_status();
+    if options.shows_passes(true) {
+        return Ok(format!(
+            "{}\n",
+            serde_json::to_string_pretty(&JsonReport { report, exit })?
+        ));
+    }
+
+    // counts and scoring still reflect the passes; only the issue list is trimmed
+    let mut trimmed = report.clone();
+    trimmed
+        .issues
+        .retain(|issue| issue.severity != Severity::Pass);
+    Ok(format!(
+        "{}\n",
+        serde_json::to_string_pretty(&JsonReport {
+            report: &trimmed,
+            exit,
+        })?
+    ))
 }
 
 #[cfg(test)]
@@ -13,7 +41,8 @@ mod tests {
 
     #[test]
     fn json_report_has_stable_top_level_shape() {
-        let rendered = render(&sample_report()).expect("json render succeeds");
+        let rendered =
+            render(&sample_report(), RenderOptions::default()).expect("json render succeeds");
         let parsed: Value = serde_json::from_str(&rendered).expect("json parses");
 
         assert_eq!(parsed["schema_version"], "1");
@@ -28,5 +57,55 @@ mod tests {
         assert_eq!(parsed["issues"][0]["severity"], "error");
         assert_eq!(parsed["issues"][0]["category"], "secrets");
         assert!(parsed["issues"][0]["remediation"].is_string());
+        assert_eq!(parsed["exit"]["ok"], false);
+        assert_eq!(parsed["exit"]["code"], 1);
+        assert_eq!(parsed["exit"]["reasons"], parsed["exit_reasons"]);
+    }
+
+    #[test]
+    fn exit_status_matches_the_process_exit_code() {
+        let mut report = sample_report();
+        report.partial = true;
+        let parsed: Value = serde_json::from_str(
+            &render(&report, RenderOptions::default()).expect("json render succeeds"),
+        )
+        .expect("json parses");
+        assert_eq!(parsed["exit"]["code"], 3);
+        assert_eq!(
+            parsed["exit"]["reasons"][0],
+            "a time or size budget ran out, so results are partial"
+        );
+
+        report.partial = false;
+        report.passed = true;
+        report.exit_reasons.clear();
+        assert_eq!(
+            report.exit_status(),
+            ExitStatus {
+                ok: true,
+                reasons: Vec::new(),
+                code: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn no_passes_trims_pass_issues_but_keeps_counts() {
+        let report = sample_report();
+        let options = RenderOptions {
+            passes: Some(false),
+            ..RenderOptions::default()
+        };
+        let parsed: Value =
+            serde_json::from_str(&render(&report, options).expect("renders")).expect("parses");
+
+        assert!(
+            parsed["issues"]
+                .as_array()
+                .expect("issues array")
+                .iter()
+                .all(|issue| issue["severity"] != "pass")
+        );
+        assert_eq!(parsed["counts"]["pass"], report.counts.pass);
     }
 }
@@ -1,5 +1,6 @@
-use crate::core::Category;
+use crate::core::{Category, Severity};
 use crate::report::{FinalReport, RenderOptions, issue_location};
+use std::collections::BTreeSet;
 
 pub fn render(report: &FinalReport, options: RenderOptions) -> String {
     let mut lines = Vec::new();
@@ -16,6 +17,29 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         "| Repository | `{}` |",
         escape_cell(&report.repository_path)
     ));
+    if let Some(run) = &report.run {
+        if let Some(commit) = &run.git_commit {
+            lines.push(format!(
+                "| Commit | `{}`{} |",
+                &commit[..commit.len().min(12)],
+                run.git_branch
+                    .as_ref()
+                    .map(|branch| format!(" on `{}`", escape_cell(branch)))
+                    .unwrap_or_default()
+            ));
+        }
+        if let Some(started_at) = &run.started_at {
+            lines.push(format!("| Scanned at | {} |", started_at));
+        }
+    }
+    if !report.providers.is_empty() {
+        let providers = report
+            .providers
+            .iter()
+            .map(|provider| escape_cell(&provider.summary()))
+            .collect::<Vec<_>>();
+        lines.push(format!("| Providers | {} |", providers.join("<br>")));
+    }
     lines.push(format!(
         "| Score | **{}/{} ({})** |",
         report.score, report.max_score, report.label
@@ -53,6 +77,13 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         report.scoring.deductions.len(),
         report.scoring.total_deductions
     ));
+    if !report.suppressed.is_empty() {
+        lines.push(format!(
+            "| suppressed | {} | {} |",
+            report.suppressed.len(),
+            report.scoring.suppressed_penalty
+        ));
+    }
 
     if !report.exit_reasons.is_empty() {
         lines.push(String::new());
@@ -72,8 +103,15 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         report.scoring.total_deductions
     ));
     lines.push(format!(
-        "- Default weights: info `{}`, warning `{}`, error `{}`.",
-        report.scoring.weights.info, report.scoring.weights.warning, report.scoring.weights.error
+        "- Passing checks recovered `{}`.",
+        report.scoring.total_credits
+    ));
+    lines.push(format!(
+        "- Default weights: info `{}`, warning `{}`, error `{}`, pass credit `{}`.",
+        report.scoring.weights.info,
+        report.scoring.weights.warning,
+        report.scoring.weights.error,
+        report.scoring.weights.pass_credit
     ));
     if options.github_step_summary {
         lines.push("- This summary is optimized for `$GITHUB_STEP_SUMMARY`.".to_string());
@@ -112,18 +150,32 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
 
     lines.push(String::new());
     lines.push("### Issues by Category".to_string());
-    for category in Category::ALL {
+    // built-in categories in their usual order, then custom pattern labels
+    let custom = report
+        .issues
+        .iter()
+        .filter_map(|issue| issue.custom_category.as_deref())
+        .collect::<BTreeSet<_>>();
+    let groups = Category::ALL
+        .iter()
+        .map(|category| (category.label(), Some(*category)))
+        .chain(custom.into_iter().map(|label| (label, None)));
+    for (label, category) in groups {
         let issues = report
             .issues
             .iter()
-            .filter(|issue| issue.category == category)
+            .filter(|issue| match category {
+                Some(category) => issue.category == category && issue.custom_category.is_none(),
+                None => issue.custom_category.as_deref() == Some(label),
+            })
+            .filter(|issue| options.shows_passes(true) || issue.severity != Severity::Pass)
             .collect::<Vec<_>>();
         if issues.is_empty() {
             continue;
         }
 
         lines.push(String::new());
-        lines.push(format!("#### {}", category.label()));
+        lines.push(format!("#### {}", label));
 
         let issue_count = issues.len();
         let visible_issues = if options.summary_only {
@@ -147,6 +199,9 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
                 if let Some(description) = &issue.description {
                     lines.push(format!("  Details: {}", escape_cell(description)));
                 }
+                if let (Some(by), Some(at)) = (&issue.introduced_by, &issue.introduced_at) {
+                    lines.push(format!("  Introduced: {} by {}", at, escape_cell(by)));
+                }
                 lines.push(format!(
                     "  Remediation: {}",
                     escape_cell(&issue.remediation)
@@ -162,6 +217,29 @@ pub fn render(report: &FinalReport, options: RenderOptions) -> String {
         }
     }
 
+    if !report.suppressed.is_empty() && !options.summary_only {
+        lines.push(String::new());
+        lines.push("### Suppressed Findings".to_string());
+        lines.push(String::new());
+        lines.push("| Rule | Finding | Location | Via | Owner | Reason | Expires |".to_string());
+        lines.push("| --- | --- | --- | --- | --- | --- | --- |".to_string());
+        for finding in &report.suppressed {
+            lines.push(format!(
+                "| `{}` | {} | {} | {} | {} | {} | {} |",
+                finding.code,
+                escape_cell(&finding.title),
+                finding
+                    .location()
+                    .map(|location| format!("`{}`", escape_cell(&location)))
+                    .unwrap_or_default(),
+                finding.mechanism.label(),
+                escape_cell(finding.owner.as_deref().unwrap_or("")),
+                escape_cell(finding.reason.as_deref().unwrap_or("")),
+                finding.expires.as_deref().unwrap_or("")
+            ));
+        }
+    }
+
     lines.join("\n") + "\n"
 }
 
@@ -176,17 +254,10 @@ mod tests {
 
     #[test]
     fn markdown_report_includes_key_sections() {
-        let rendered = render(
-            &sample_report(),
-            RenderOptions {
-                summary_only: false,
-                color: false,
-                github_step_summary: false,
-            },
-        );
+        let rendered = render(&sample_report(), RenderOptions::default());
 
         assert!(rendered.contains("## DevGuard Summary"));
-        assert!(rendered.contains("| Score | **70/100 (Fair)** |"));
+        assert!(rendered.contains("| Score | **71/100 (Fair)** |"));
         assert!(rendered.contains("### Counts"));
         assert!(rendered.contains("### Issues by Category"));
         assert!(rendered.contains("#### Secrets"));
@@ -0,0 +1,199 @@
+use crate::core::{Issue, catalog};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_LANG: &str = "en";
+const BUILTIN_CATALOGS: [(&str, &str); 1] = [("es", include_str!("messages/es.toml"))];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Message {
+    title: Option<String>,
+    remediation: Option<String>,
+}
+
+// translated issue text keyed by rule id; rules without an entry keep their English text
+#[derive(Debug, Default)]
+pub struct Catalog {
+    messages: BTreeMap<String, Message>,
+}
+
+impl Catalog {
+    // the built-in messages for `lang`, with `<messages_dir>/<lang>.toml` layered over them
+    pub fn load(lang: &str, messages_dir: Option<&Path>) -> Result<Self> {
+        let lang = lang.trim().to_ascii_lowercase();
+        let mut catalog = Catalog::default();
+
+        let builtin = BUILTIN_CATALOGS.iter().find(|(code, _)| *code == lang);
+        if let Some((code, text)) = builtin {
+            catalog.merge(parse(text).with_context(|| format!("built-in {} messages", code))?);
+        }
+        let user = messages_dir
+            .map(|dir| dir.join(format!("{}.toml", lang)))
+            .filter(|path| path.is_file());
+        if let Some(path) = &user {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("failed reading {}", path.display()))?;
+            catalog.merge(
+                parse(&text)
+                    .with_context(|| format!("invalid message catalog {}", path.display()))?,
+            );
+        }
+
+        if builtin.is_none() && user.is_none() && lang != DEFAULT_LANG {
+            bail!(
+                "no messages for language `{}` (available: {})",
+                lang,
+                available(messages_dir).join(", ")
+            );
+        }
+        Ok(catalog)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn localize(&self, issues: &mut [Issue]) {
+        for issue in issues {
+            let Some(message) = self.messages.get(issue.code) else {
+                continue;
+            };
+            if let Some(title) = message
+                .title
+                .as_deref()
+                .and_then(|template| fill(template, issue))
+            {
+                // the English title often names the variable or file at fault, so it is
+                // kept as a detail
+                if !issue.title.eq_ignore_ascii_case(issue.rule_title) {
+                    issue.description = Some(match issue.description.take() {
+                        Some(description) => format!("{}; {}", issue.title, description),
+                        None => issue.title.clone(),
+                    });
+                }
+                issue.original_title = Some(std::mem::replace(&mut issue.title, title));
+            }
+            if let Some(remediation) = &message.remediation {
+                issue.remediation = remediation.clone();
+            }
+        }
+    }
+
+    fn merge(&mut self, messages: BTreeMap<String, Message>) {
+        for (code, message) in messages {
+            let entry = self.messages.entry(code).or_default();
+            if message.title.is_some() {
+                entry.title = message.title;
+            }
+            if message.remediation.is_some() {
+                entry.remediation = message.remediation;
+            }
+        }
+    }
+}
+
+fn parse(text: &str) -> Result<BTreeMap<String, Message>> {
+    let messages = toml::from_str::<BTreeMap<String, Message>>(text)?
+        .into_iter()
+        .map(|(code, message)| (code.to_ascii_uppercase(), message))
+        .collect::<BTreeMap<_, _>>();
+    if let Some(code) = messages.keys().find(|code| catalog::find(code).is_none()) {
+        bail!("unknown rule id {}", code);
+    }
+    Ok(messages)
+}
+
+// a title placeholder the issue has no value for leaves the English title in place
+fn fill(template: &str, issue: &Issue) -> Option<String> {
+    let mut title = template.to_string();
+    for (placeholder, value) in [
+        ("{file}", issue.file.clone()),
+        ("{line}", issue.line.map(|line| line.to_string())),
+    ] {
+        if title.contains(placeholder) {
+            title = title.replace(placeholder, &value?);
+        }
+    }
+    Some(title)
+}
+
+fn available(messages_dir: Option<&Path>) -> Vec<String> {
+    let mut langs = std::iter::once(DEFAULT_LANG.to_string())
+        .chain(BUILTIN_CATALOGS.iter().map(|(code, _)| code.to_string()))
+        .collect::<Vec<_>>();
+    if let Some(entries) = messages_dir.and_then(|dir| fs::read_dir(dir).ok()) {
+        langs.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "toml").then(|| path.file_stem()?.to_str().map(str::to_string))?
+        }));
+    }
+    langs.sort();
+    langs.dedup();
+    langs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Severity, rules};
+
+    #[test]
+    fn builtin_and_user_catalogs_translate_issues() {
+        let dir = std::env::temp_dir().join(format!("devguard-messages-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("dir created");
+        fs::write(
+            dir.join("es.toml"),
+            "[DG_ENV_001]\nremediation = \"define la variable en el entorno\"\n",
+        )
+        .expect("catalog written");
+
+        let catalog = Catalog::load("es", Some(&dir)).expect("loads");
+        let mut issues = vec![
+            Issue::from_rule(
+                rules::SECRET_AWS_ACCESS_KEY,
+                Severity::Error,
+                "AWS access key pattern detected",
+                "rotate it",
+            )
+            .with_file("deploy.sh"),
+            Issue::from_rule(
+                rules::ENV_REQUIRED_VAR_MISSING,
+                Severity::Warning,
+                "missing required env var DATABASE_URL",
+                "add it",
+            ),
+            Issue::from_rule(
+                rules::SECRET_AWS_ACCESS_KEY,
+                Severity::Error,
+                "AWS access key pattern detected",
+                "rotate it",
+            ),
+        ];
+        let fingerprints = issues.iter().map(Issue::fingerprint).collect::<Vec<_>>();
+        catalog.localize(&mut issues);
+
+        assert_eq!(issues[0].title, "Clave de acceso de AWS en deploy.sh");
+        assert_eq!(
+            issues[0].description.as_deref(),
+            Some("AWS access key pattern detected")
+        );
+        assert_eq!(issues[1].title, "Falta una variable de entorno obligatoria");
+        assert_eq!(issues[1].remediation, "define la variable en el entorno");
+        // no file to fill in, so the title stays English while the hint is translated
+        assert_eq!(issues[2].title, "AWS access key pattern detected");
+        assert!(issues[2].remediation.starts_with("desactiva"));
+        assert_eq!(
+            issues.iter().map(Issue::fingerprint).collect::<Vec<_>>(),
+            fingerprints
+        );
+
+        let error = Catalog::load("fr", Some(&dir)).expect_err("no french catalog");
+        assert!(error.to_string().contains("available: en, es"));
+        assert!(Catalog::load("en", None).expect("english").is_empty());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,14 +1,28 @@
+pub mod compact;
+pub mod cyclonedx;
+pub mod fleet;
+pub mod github;
 pub mod human;
 pub mod json;
 pub mod markdown;
+pub mod messages;
+pub mod rules;
 pub mod sarif;
+pub mod stored;
+pub mod telemetry;
+pub mod template;
 
-use crate::config::FailOn;
+use crate::config::{FailOn, PolicyConfig};
+use crate::core::suppress::SuppressedFinding;
 use crate::core::{Issue, Severity};
 use crate::score::{self, PenaltyProfile, ScoreBreakdown};
+use crate::utils::ci::CiEnvironment;
+use crate::utils::fs as fs_utils;
+use crate::utils::sandbox;
 use anyhow::{Context, Result};
 use clap::ValueEnum;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::Path;
@@ -23,15 +37,46 @@ pub enum ReportFormat {
     Json,
     Markdown,
     Sarif,
+    Compact,
+    Cyclonedx,
+    Github,
+}
+
+impl ReportFormat {
+    // what other tools read back (`devguard score --input`, code scanning, SBOM
+    // consumers) keeps every finding; the per-rule cap only shortens what people read
+    pub fn lists_every_finding(self) -> bool {
+        matches!(self, Self::Json | Self::Sarif | Self::Cyclonedx)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    #[default]
+    Severity,
+    Category,
+    File,
+    Dir,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct RenderOptions {
     pub summary_only: bool,
+    pub summary: bool,
+    pub max_issues: Option<usize>,
+    pub group_by: GroupBy,
+    // None keeps each format's default: human hides passes, json and markdown keep them
+    pub passes: Option<bool>,
     pub color: bool,
     pub github_step_summary: bool,
 }
 
+impl RenderOptions {
+    pub fn shows_passes(self, format_default: bool) -> bool {
+        self.passes.unwrap_or(format_default)
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ToolInfo {
     pub name: &'static str,
@@ -60,16 +105,20 @@ impl Counts {
     pub fn from_issues(issues: &[Issue]) -> Self {
         let mut counts = Self::default();
         for issue in issues {
-            match issue.severity {
-                Severity::Error => counts.error += 1,
-                Severity::Warning => counts.warning += 1,
-                Severity::Info => counts.info += 1,
-                Severity::Pass => counts.pass += 1,
-            }
+            counts.add(issue.severity);
         }
-        counts.total = issues.len();
         counts
     }
+
+    fn add(&mut self, severity: Severity) {
+        match severity {
+            Severity::Error => self.error += 1,
+            Severity::Warning => self.warning += 1,
+            Severity::Info => self.info += 1,
+            Severity::Pass => self.pass += 1,
+        }
+        self.total += 1;
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -82,11 +131,153 @@ pub struct FinalReport {
     pub label: String,
     pub min_score: u8,
     pub passed: bool,
+    pub partial: bool,
+    // no file content was read, so the score says nothing about the repository
+    pub empty: bool,
     pub fail_on: FailOn,
     pub exit_reasons: Vec<String>,
     pub counts: Counts,
     pub scoring: ScoreBreakdown,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunMetadata>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<ProviderRun>,
     pub issues: Vec<Issue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<SuppressedFinding>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<OwnerSummary>,
+}
+
+// per-owner counts; findings no `[owners]` pattern covers are grouped under `owner: null`
+#[derive(Debug, Clone, Serialize)]
+pub struct OwnerSummary {
+    pub owner: Option<String>,
+    pub counts: Counts,
+}
+
+pub const FAILED_EXIT_CODE: i32 = 1;
+pub const PARTIAL_EXIT_CODE: i32 = 3;
+pub const EMPTY_EXIT_CODE: i32 = 4;
+
+// the process exit code a report maps to and every reason it is not zero, so CI wrappers
+// can gate on the JSON output without re-deriving the policy from counts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExitStatus {
+    pub ok: bool,
+    pub reasons: Vec<String>,
+    pub code: i32,
+}
+
+impl FinalReport {
+    pub fn exit_status(&self) -> ExitStatus {
+        let code = if self.empty {
+            EMPTY_EXIT_CODE
+        } else if self.partial {
+            PARTIAL_EXIT_CODE
+        } else if self.passed {
+            0
+        } else {
+            FAILED_EXIT_CODE
+        };
+        let mut reasons = Vec::new();
+        if self.empty {
+            reasons.push("the secret scan read no file content".to_string());
+        }
+        if self.partial {
+            reasons.push("a time or size budget ran out, so results are partial".to_string());
+        }
+        reasons.extend(self.exit_reasons.iter().cloned());
+        ExitStatus {
+            ok: code == 0,
+            reasons,
+            code,
+        }
+    }
+
+    // everything else is a function of the tree and config, so two runs over the same
+    // checkout render the same bytes once timings are gone
+    pub fn strip_timings(&mut self) {
+        if let Some(run) = &mut self.run {
+            run.started_at = None;
+            run.duration_ms = None;
+        }
+        for provider in &mut self.providers {
+            provider.duration_ms = None;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetadata {
+    // wall-clock values; `--deterministic` leaves them out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    pub scanned_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    pub files_scanned: usize,
+    pub files_skipped: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<CiEnvironment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderRun {
+    pub name: &'static str,
+    pub enabled: bool,
+    pub detected: bool,
+    pub status: ProviderStatus,
+    pub issues: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderStatus {
+    Ran,
+    Forced,
+    Disabled,
+    NotDetected,
+}
+
+impl ProviderStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProviderStatus::Ran => "ran",
+            ProviderStatus::Forced => "ran (forced)",
+            ProviderStatus::Disabled => "disabled",
+            ProviderStatus::NotDetected => "not detected",
+        }
+    }
+}
+
+impl ProviderRun {
+    pub fn summary(&self) -> String {
+        match self.status {
+            ProviderStatus::Ran | ProviderStatus::Forced => match self.duration_ms {
+                Some(duration_ms) => format!(
+                    "{} {} ({} issue(s), {}ms)",
+                    self.name,
+                    self.status.label(),
+                    self.issues,
+                    duration_ms
+                ),
+                None => format!(
+                    "{} {} ({} issue(s))",
+                    self.name,
+                    self.status.label(),
+                    self.issues
+                ),
+            },
+            _ => format!("{} {}", self.name, self.status.label()),
+        }
+    }
 }
 
 pub fn build_report(
@@ -94,25 +285,166 @@ pub fn build_report(
     issues: Vec<Issue>,
     min_score: u8,
     fail_on: FailOn,
+    budgets: &PolicyConfig,
 ) -> FinalReport {
     let scoring = score::calculate_breakdown(&issues, PenaltyProfile::default());
-    let policy = score::evaluate_policy(scoring.final_score, &issues, min_score, fail_on);
+    let policy = score::evaluate_policy(scoring.final_score, &issues, min_score, fail_on, budgets);
 
     FinalReport {
         schema_version: REPORT_SCHEMA_VERSION,
         tool: ToolInfo::default(),
-        repository_path: normalize_path(repository_path),
+        repository_path: fs_utils::normalize_path(repository_path),
         score: scoring.final_score,
         max_score: score::MAX_SCORE,
         label: score::label_for_score(scoring.final_score).to_string(),
         min_score,
         passed: policy.passed,
+        partial: false,
+        empty: false,
         fail_on,
         exit_reasons: policy.reasons,
         counts: Counts::from_issues(&issues),
         scoring,
+        run: None,
+        providers: Vec::new(),
         issues,
+        suppressed: Vec::new(),
+        owners: Vec::new(),
+    }
+}
+
+// a generated or vendored directory can match one rule thousands of times. past `limit`
+// the rest of that rule's findings are listed as one summary after the ones shown; counts,
+// the score, and policy come from the full list and are left alone
+pub fn cap_findings_per_rule(issues: &mut Vec<Issue>, limit: usize) {
+    if limit == 0 {
+        return;
+    }
+    let mut kept = Vec::with_capacity(issues.len());
+    let mut seen = HashMap::<&'static str, usize>::new();
+    let mut overflow = BTreeMap::<&'static str, Vec<Issue>>::new();
+    for issue in issues.drain(..) {
+        let count = seen.entry(issue.code).or_default();
+        *count += 1;
+        if *count > limit && issue.severity != Severity::Pass {
+            overflow.entry(issue.code).or_default().push(issue);
+        } else {
+            kept.push(issue);
+        }
+    }
+    for (code, rest) in overflow {
+        let at = kept
+            .iter()
+            .rposition(|issue| issue.code == code)
+            .map_or(kept.len(), |idx| idx + 1);
+        kept.insert(at, overflow_summary(rest, limit));
     }
+    *issues = kept;
+}
+
+// the title leaves out the count so the summary keeps one fingerprint from run to run
+fn overflow_summary(rest: Vec<Issue>, limit: usize) -> Issue {
+    let mut by_directory = BTreeMap::<&str, usize>::new();
+    for issue in &rest {
+        *by_directory
+            .entry(issue.directory().unwrap_or("(no file)"))
+            .or_default() += 1;
+    }
+    let mut busiest = by_directory.into_iter().collect::<Vec<_>>();
+    busiest.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let places = busiest
+        .iter()
+        .take(3)
+        .map(|(directory, count)| match *directory {
+            "." => format!("the repository root ({})", count),
+            directory => format!("{} ({})", directory, count),
+        })
+        .collect::<Vec<_>>();
+    let severity = Severity::ALL
+        .into_iter()
+        .find(|severity| rest.iter().any(|issue| issue.severity == *severity))
+        .unwrap_or(Severity::Info);
+
+    let first = &rest[0];
+    Issue {
+        title: format!("{} matched in more locations than listed", first.code),
+        description: Some(format!(
+            "{} more locations past the first {}, mostly in {}. set general.max_findings_per_rule = 0 or run devguard audit to list every one",
+            rest.len(),
+            limit,
+            places.join(", ")
+        )),
+        severity,
+        file: None,
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+        snippet: None,
+        introduced_by: None,
+        introduced_at: None,
+        introduced_in: None,
+        owner: None,
+        actions: Vec::new(),
+        duplicates: 0,
+        original_title: None,
+        ..first.clone()
+    }
+}
+
+// empty unless at least one finding has an owner; owned teams first, by name
+pub fn owner_summaries(issues: &[Issue]) -> Vec<OwnerSummary> {
+    if issues.iter().all(|issue| issue.owner.is_none()) {
+        return Vec::new();
+    }
+    let mut by_owner: BTreeMap<Option<&str>, Counts> = BTreeMap::new();
+    for issue in issues {
+        by_owner
+            .entry(issue.owner.as_deref())
+            .or_default()
+            .add(issue.severity);
+    }
+    let mut summaries = by_owner
+        .into_iter()
+        .map(|(owner, counts)| OwnerSummary {
+            owner: owner.map(str::to_string),
+            counts,
+        })
+        .collect::<Vec<_>>();
+    summaries.sort_by_key(|summary| summary.owner.is_none());
+    summaries
+}
+
+// findings hidden by suppressions, `off` rule overrides, or the allowlist; with
+// `policy.suppressed_penalty` each one still costs that many points and the policy is
+// evaluated again on the lower score
+pub fn record_suppressed(
+    report: &mut FinalReport,
+    suppressed: Vec<SuppressedFinding>,
+    policy: &PolicyConfig,
+) {
+    let count = u16::try_from(suppressed.len()).unwrap_or(u16::MAX);
+    let penalty = u16::from(policy.suppressed_penalty.unwrap_or(0)).saturating_mul(count);
+    report.suppressed = suppressed;
+    if penalty == 0 {
+        return;
+    }
+
+    let scoring = &mut report.scoring;
+    scoring.suppressed_penalty = penalty;
+    scoring.total_deductions = scoring.total_deductions.saturating_add(penalty);
+    scoring.final_score = (i32::from(scoring.final_score) - i32::from(penalty)).max(0) as u8;
+    report.score = scoring.final_score;
+    report.label = score::label_for_score(report.score).to_string();
+    let evaluation = score::evaluate_policy(
+        report.score,
+        &report.issues,
+        report.min_score,
+        report.fail_on,
+        policy,
+    );
+    report.passed = evaluation.passed;
+    report.exit_reasons = evaluation.reasons;
 }
 
 pub fn render(
@@ -122,18 +454,23 @@ pub fn render(
 ) -> Result<String> {
     match format {
         ReportFormat::Human => Ok(human::render(report, options)),
-        ReportFormat::Json => json::render(report),
+        ReportFormat::Json => json::render(report, options),
         ReportFormat::Markdown => Ok(markdown::render(report, options)),
         ReportFormat::Sarif => sarif::render(report),
+        ReportFormat::Compact => Ok(compact::render(report, options)),
+        ReportFormat::Cyclonedx => cyclonedx::render(report),
+        ReportFormat::Github => Ok(github::render(report, options)),
     }
 }
 
 pub fn write_output(path: &Path, content: &str) -> Result<()> {
+    sandbox::ensure_allowed(&format!("writing {}", path.display()))?;
     fs::write(path, content).with_context(|| format!("failed writing {}", path.display()))?;
     Ok(())
 }
 
 pub fn write_github_step_summary(report: &FinalReport) -> Result<()> {
+    sandbox::ensure_allowed("writing the GitHub step summary")?;
     let Ok(path) = std::env::var("GITHUB_STEP_SUMMARY") else {
         eprintln!("warning: GITHUB_STEP_SUMMARY is not set; skipping step summary output");
         return Ok(());
@@ -143,8 +480,8 @@ pub fn write_github_step_summary(report: &FinalReport) -> Result<()> {
         report,
         RenderOptions {
             summary_only: true,
-            color: false,
             github_step_summary: true,
+            ..RenderOptions::default()
         },
     );
 
@@ -164,11 +501,6 @@ pub fn issue_location(issue: &Issue) -> Option<String> {
     issue.location()
 }
 
-fn normalize_path(path: &Path) -> String {
-    let raw = path.to_string_lossy().replace('\\', "/");
-    raw.strip_prefix("//?/").unwrap_or(&raw).to_string()
-}
-
 #[cfg(test)]
 pub(crate) fn sample_report() -> FinalReport {
     use crate::core::{Issue, Severity, rules};
@@ -207,5 +539,60 @@ pub(crate) fn sample_report() -> FinalReport {
         ],
         80,
         FailOn::Warning,
+        &PolicyConfig::default(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules;
+
+    #[test]
+    fn findings_past_the_rule_cap_fold_into_a_summary() {
+        let finding = |severity, file: &str, line| {
+            Issue::from_rule(
+                rules::SECRET_AWS_ACCESS_KEY,
+                severity,
+                "AWS access key pattern detected",
+                "rotate it",
+            )
+            .with_file(file)
+            .with_line(line)
+        };
+        let issues = std::iter::once(finding(Severity::Error, "src/deploy.sh", 3))
+            .chain((1..=4).map(|line| finding(Severity::Error, "fixtures/gen/a.env", line)))
+            .chain([finding(Severity::Warning, "docs/setup.md", 1)])
+            .collect::<Vec<_>>();
+        let budgets = PolicyConfig {
+            max_errors: Some(4),
+            ..PolicyConfig::default()
+        };
+        let report = build_report(Path::new("/tmp/repo"), issues, 0, FailOn::None, &budgets);
+
+        let mut listed = report.issues.clone();
+        cap_findings_per_rule(&mut listed, 0);
+        assert_eq!(listed.len(), 6);
+        cap_findings_per_rule(&mut listed, 2);
+        assert_eq!(listed.len(), 3);
+        let summary = &listed[2];
+        assert_eq!(summary.code, "DG_SEC_004");
+        assert_eq!(summary.file, None);
+        assert_eq!(summary.severity, Severity::Error);
+        assert_eq!(
+            summary.description.as_deref(),
+            Some(
+                "4 more locations past the first 2, mostly in fixtures/gen (3), docs (1). set general.max_findings_per_rule = 0 or run devguard audit to list every one"
+            )
+        );
+
+        // the run is judged on every finding, however many are listed
+        assert_eq!(report.counts.error, 5);
+        assert!(!report.passed);
+
+        // and saved reports keep them all, so re-scoring one gives the same counts
+        assert!(ReportFormat::Json.lists_every_finding());
+        assert!(ReportFormat::Sarif.lists_every_finding());
+        assert!(!ReportFormat::Human.lists_every_finding());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::config::Config;
+use crate::core::catalog::{RULE_DOCS, RuleDoc};
+use crate::core::rulepack::FixtureResult;
+use anyhow::Result;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct RuleListEntry<'a> {
+    #[serde(flatten)]
+    doc: &'a RuleDoc,
+    enabled: bool,
+}
+
+pub fn render_list(cfg: &Config, json: bool) -> Result<String> {
+    if json {
+        let entries = RULE_DOCS
+            .iter()
+            .map(|doc| RuleListEntry {
+                doc,
+                enabled: doc.is_enabled(cfg),
+            })
+            .collect::<Vec<_>>();
+        return Ok(format!("{}\n", serde_json::to_string_pretty(&entries)?));
+    }
+
+    let headers = ["RULE", "CATEGORY", "SEVERITY", "ENABLED", "DESCRIPTION"];
+    let rows = RULE_DOCS
+        .iter()
+        .map(|doc| {
+            [
+                doc.code.to_string(),
+                doc.category.slug().to_string(),
+                doc.severity.slug().to_string(),
+                if doc.is_enabled(cfg) { "yes" } else { "no" }.to_string(),
+                doc.title.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut lines = vec![format_row(&headers.map(str::to_string), &widths)];
+    lines.extend(rows.iter().map(|row| format_row(row, &widths)));
+    Ok(lines.join("\n") + "\n")
+}
+
+pub fn render_explain(doc: &RuleDoc, cfg: &Config) -> String {
+    let mut lines = vec![
+        format!("{} - {}", doc.code, doc.title),
+        format!("category: {}", doc.category.slug()),
+        format!("default severity: {}", doc.severity.slug()),
+        format!(
+            "enabled: {}",
+            if doc.is_enabled(cfg) { "yes" } else { "no" }
+        ),
+        String::new(),
+        format!("why it matters: {}", doc.rationale),
+        format!("example match: {}", doc.example),
+        format!("remediation: {}", doc.remediation),
+        match doc.config_key {
+            Some(key) => format!("controlled by: {} (or [rules] {} = \"off\")", key, doc.code),
+            None => format!("controlled by: [rules] {} = \"off\"", doc.code),
+        },
+    ];
+    if let Some(url) = doc.url {
+        lines.push(format!("docs: {}", url));
+    }
+    lines.join("\n") + "\n"
+}
+
+pub fn render_test(results: &[FixtureResult], json: bool) -> Result<String> {
+    if json {
+        return Ok(format!("{}\n", serde_json::to_string_pretty(results)?));
+    }
+
+    let mut lines = Vec::new();
+    for result in results {
+        if result.passed() {
+            lines.push(format!(
+                "PASS {} ({} expectation(s))",
+                result.path, result.expectations
+            ));
+            continue;
+        }
+        lines.push(format!("FAIL {}", result.path));
+        lines.extend(result.failures.iter().map(|failure| {
+            format!(
+                "  line {}: {}: {}",
+                failure.line, failure.pattern, failure.reason
+            )
+        }));
+    }
+    let failed = results.iter().filter(|result| !result.passed()).count();
+    lines.push(format!(
+        "{} fixture(s), {} passed, {} failed",
+        results.len(),
+        results.len() - failed,
+        failed
+    ));
+    Ok(lines.join("\n") + "\n")
+}
+
+fn format_row(cells: &[String; 5], widths: &[usize; 5]) -> String {
+    let mut row = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    row.truncate(row.trim_end().len());
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Issue, Severity, catalog, rules};
+
+    #[test]
+    fn rule_list_includes_every_rule() {
+        let rendered = render_list(&Config::default(), false).expect("list renders");
+        assert!(rendered.starts_with("RULE"));
+        assert!(RULE_DOCS.iter().all(|doc| rendered.contains(doc.code)));
+    }
+
+    #[test]
+    fn explain_includes_rationale_and_remediation() {
+        let doc = catalog::find("DG_ENV_004").expect("rule exists");
+        let rendered = render_explain(doc, &Config::default());
+        assert!(rendered.contains("why it matters:"));
+        assert!(rendered.contains("remediation:"));
+        assert!(rendered.contains("controlled by: env.forbid_commit"));
+    }
+
+    #[test]
+    fn issues_name_the_option_that_controls_them() {
+        let issue = Issue::from_rule(rules::STRIPE_LIVE_KEY_IN_DOTENV, Severity::Error, "t", "r");
+        assert_eq!(issue.config_key, "providers.stripe.warn_live_keys");
+        let issue = Issue::from_rule(rules::GIT_DIRTY_TREE, Severity::Warning, "t", "r");
+        assert_eq!(issue.config_key, "rules.DG_GIT_002");
+        let json = serde_json::to_value(&issue).expect("serializes");
+        assert_eq!(json["config_key"], "rules.DG_GIT_002");
+    }
+}
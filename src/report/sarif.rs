@@ -1,5 +1,6 @@
 use crate::core::{Category, Issue};
 use crate::report::FinalReport;
+use crate::utils::fs::normalize_path;
 use anyhow::Result;
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -20,6 +21,8 @@ struct SarifLog {
 #[derive(Debug, Serialize)]
 struct SarifRun {
     tool: SarifTool,
+    #[serde(rename = "columnKind")]
+    column_kind: &'static str,
     results: Vec<SarifResult>,
 }
 
@@ -47,6 +50,8 @@ struct SarifRule {
     full_description: Option<SarifMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     help: Option<SarifMessage>,
+    #[serde(rename = "helpUri", skip_serializing_if = "Option::is_none")]
+    help_uri: Option<&'static str>,
     properties: SarifRuleProperties,
 }
 
@@ -91,6 +96,12 @@ struct SarifArtifactLocation {
 struct SarifRegion {
     #[serde(rename = "startLine")]
     start_line: usize,
+    #[serde(rename = "startColumn", skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
 }
 
 pub fn render(report: &FinalReport) -> Result<String> {
@@ -114,11 +125,9 @@ pub fn render(report: &FinalReport) -> Result<String> {
             help: Some(SarifMessage {
                 text: issue.remediation.clone(),
             }),
+            help_uri: issue.url,
             properties: SarifRuleProperties {
-                tags: vec![
-                    issue.category.slug().to_string(),
-                    issue.severity.slug().to_string(),
-                ],
+                tags: vec![issue.category_slug(), issue.severity.slug().to_string()],
             },
         });
 
@@ -144,6 +153,7 @@ pub fn render(report: &FinalReport) -> Result<String> {
                     rules: rules.into_values().collect(),
                 },
             },
+            column_kind: "unicodeCodePoints",
             results,
         }],
     };
@@ -159,16 +169,19 @@ fn sarif_location_for_issue(report: &FinalReport, issue: &Issue) -> SarifLocatio
             },
             region: SarifRegion {
                 start_line: issue.line.unwrap_or(1),
+                start_column: issue.column,
+                end_line: issue.end_line,
+                end_column: issue.end_column,
             },
         },
     }
 }
 
 fn artifact_uri_for_issue(report: &FinalReport, issue: &Issue) -> String {
-    if let Some(file) = issue.file.as_deref() {
-        if let Some(uri) = resolve_issue_file_uri(report, file) {
-            return uri;
-        }
+    if let Some(file) = issue.file.as_deref()
+        && let Some(uri) = resolve_issue_file_uri(report, file)
+    {
+        return uri;
     }
 
     fallback_artifact_uri(report, issue)
@@ -248,6 +261,9 @@ fn preferred_anchor_candidates(category: Category) -> &'static [&'static str] {
             "package.json",
             "README.md",
         ],
+        Category::Release => &[".env.production", "package.json", "README.md"],
+        Category::Supply => &["package.json", ".husky", "README.md"],
+        Category::Tooling => &["devguard.toml", "README.md", "Cargo.toml", "package.json"],
         Category::Stripe => &[
             ".env",
             ".env.local",
@@ -273,10 +289,6 @@ fn relative_uri(repo_root: &Path, path: &Path) -> Option<String> {
     path.strip_prefix(repo_root).ok().map(normalize_path)
 }
 
-fn normalize_path(path: &Path) -> String {
-    path.to_string_lossy().replace('\\', "/")
-}
-
 fn normalize_uri(uri: &str) -> String {
     uri.replace('\\', "/")
 }
@@ -284,7 +296,7 @@ fn normalize_uri(uri: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::FailOn;
+    use crate::config::{FailOn, PolicyConfig};
     use crate::core::{Issue, Severity, rules};
     use crate::report::build_report;
     use crate::report::sample_report;
@@ -301,6 +313,15 @@ mod tests {
         assert_eq!(parsed["runs"][0]["results"][0]["ruleId"], "DG_SEC_004");
         assert_eq!(parsed["runs"][0]["results"][0]["level"], "error");
         assert!(parsed["runs"][0]["tool"]["driver"]["rules"].is_array());
+        let aws_rule = parsed["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .and_then(|rules| rules.iter().find(|rule| rule["id"] == "DG_SEC_004"))
+            .expect("aws rule is serialized");
+        assert!(
+            aws_rule["helpUri"]
+                .as_str()
+                .is_some_and(|uri| uri.starts_with("https://"))
+        );
     }
 
     #[test]
@@ -346,6 +367,7 @@ mod tests {
             ],
             80,
             FailOn::Warning,
+            &PolicyConfig::default(),
         );
 
         let rendered = render(&report).expect("sarif render succeeds");
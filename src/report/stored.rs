@@ -0,0 +1,180 @@
+use crate::core::issue::RuleSpec;
+use crate::core::suppress::SuppressedFinding;
+use crate::core::{Category, Issue, RemediationAction, Severity, catalog};
+use crate::report::{REPORT_SCHEMA_VERSION, RunMetadata};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+// the parts of a saved json report needed to score it again; scoring, counts, and exit
+// reasons are recomputed rather than trusted
+#[derive(Debug, Deserialize)]
+struct SavedReport {
+    schema_version: String,
+    repository_path: String,
+    #[serde(default)]
+    partial: bool,
+    #[serde(default)]
+    empty: bool,
+    counts: SavedCounts,
+    run: Option<RunMetadata>,
+    issues: Vec<SavedIssue>,
+    #[serde(default)]
+    suppressed: Vec<SuppressedFinding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedCounts {
+    pass: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedIssue {
+    code: String,
+    title: String,
+    description: Option<String>,
+    severity: Severity,
+    category: Category,
+    custom_category: Option<String>,
+    file: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    snippet: Option<Vec<String>>,
+    introduced_by: Option<String>,
+    introduced_at: Option<String>,
+    introduced_in: Option<String>,
+    owner: Option<String>,
+    remediation: String,
+    #[serde(default)]
+    actions: Vec<RemediationAction>,
+    #[serde(default)]
+    duplicates: usize,
+}
+
+#[derive(Debug)]
+pub struct StoredReport {
+    pub repository_path: String,
+    pub partial: bool,
+    pub empty: bool,
+    pub run: Option<RunMetadata>,
+    pub issues: Vec<Issue>,
+    pub suppressed: Vec<SuppressedFinding>,
+    // pass findings counted by the original run but left out of its issue list
+    pub missing_passes: usize,
+}
+
+pub fn load(path: &Path) -> Result<StoredReport> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed reading {}", path.display()))?;
+    parse(&text).with_context(|| format!("{} is not a devguard json report", path.display()))
+}
+
+fn parse(text: &str) -> Result<StoredReport> {
+    let saved = serde_json::from_str::<SavedReport>(text)?;
+    if saved.schema_version != REPORT_SCHEMA_VERSION {
+        bail!("unsupported report schema {}", saved.schema_version);
+    }
+    let issues = saved
+        .issues
+        .into_iter()
+        .map(into_issue)
+        .collect::<Result<Vec<_>>>()?;
+    let passes = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Pass)
+        .count();
+    Ok(StoredReport {
+        repository_path: saved.repository_path,
+        partial: saved.partial,
+        empty: saved.empty,
+        run: saved.run,
+        missing_passes: saved.counts.pass.saturating_sub(passes),
+        issues,
+        suppressed: saved.suppressed,
+    })
+}
+
+fn into_issue(saved: SavedIssue) -> Result<Issue> {
+    let Some(doc) = catalog::find(&saved.code) else {
+        bail!(
+            "unknown rule {} (was the report written by a newer devguard?)",
+            saved.code
+        );
+    };
+    let rule = RuleSpec {
+        code: doc.code,
+        rule_title: doc.title,
+        category: doc.category,
+        url: doc.url,
+        config_key: doc.config_key,
+    };
+    let mut issue = Issue::from_rule(rule, saved.severity, saved.title, saved.remediation);
+    // custom patterns choose their own category
+    issue.category = saved.category;
+    issue.custom_category = saved.custom_category;
+    issue.description = saved.description;
+    issue.file = saved.file;
+    issue.line = saved.line;
+    issue.column = saved.column;
+    issue.end_line = saved.end_line;
+    issue.end_column = saved.end_column;
+    issue.snippet = saved.snippet;
+    issue.introduced_by = saved.introduced_by;
+    issue.introduced_at = saved.introduced_at;
+    issue.introduced_in = saved.introduced_in;
+    issue.owner = saved.owner;
+    issue.actions = saved.actions;
+    issue.duplicates = saved.duplicates;
+    Ok(issue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FailOn, PolicyConfig};
+    use crate::report::{RenderOptions, build_report, json, sample_report};
+
+    #[test]
+    fn saved_reports_score_the_same_and_can_be_regated() {
+        let original = sample_report();
+        let rendered = json::render(&original, RenderOptions::default()).expect("renders");
+        let stored = parse(&rendered).expect("parses");
+        assert_eq!(stored.missing_passes, 0);
+        assert_eq!(stored.issues.len(), original.issues.len());
+
+        let rescored = build_report(
+            Path::new(&stored.repository_path),
+            stored.issues.clone(),
+            original.min_score,
+            original.fail_on,
+            &PolicyConfig::default(),
+        );
+        assert_eq!(rescored.score, original.score);
+        assert_eq!(rescored.passed, original.passed);
+
+        let lenient = build_report(
+            Path::new(&stored.repository_path),
+            stored.issues,
+            0,
+            FailOn::None,
+            &PolicyConfig::default(),
+        );
+        assert!(lenient.passed);
+
+        let trimmed = json::render(
+            &original,
+            RenderOptions {
+                passes: Some(false),
+                ..RenderOptions::default()
+            },
+        )
+        .expect("renders");
+        assert_eq!(
+            parse(&trimmed).expect("parses").missing_passes,
+            original.counts.pass
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use crate::config::{NetConfig, TelemetryConfig};
+use crate::core::{Category, Severity};
+use crate::report::FinalReport;
+use crate::utils::{net, sandbox};
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use std::fs;
+use std::net::UdpSocket;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Metric {
+    name: &'static str,
+    help: &'static str,
+    value: f64,
+    label: Option<(&'static str, &'static str)>,
+}
+
+impl Metric {
+    fn new(name: &'static str, help: &'static str, value: impl Into<f64>) -> Self {
+        Self {
+            name,
+            help,
+            value: value.into(),
+            label: None,
+        }
+    }
+
+    fn labelled(mut self, key: &'static str, value: &'static str) -> Self {
+        self.label = Some((key, value));
+        self
+    }
+}
+
+// every sink that is configured gets the same metrics; one failing sink does not stop
+// the others, and failures are returned as warnings rather than failing the run
+pub fn emit(report: &FinalReport, cfg: &TelemetryConfig, net_cfg: &NetConfig) -> Vec<String> {
+    if !cfg.enabled {
+        return Vec::new();
+    }
+    let metrics = collect(report);
+    let mut warnings = Vec::new();
+
+    if let Some(path) = &cfg.prometheus_textfile
+        && let Err(err) = write_textfile(path, &render_prometheus(&metrics, &cfg.prefix, report))
+    {
+        warnings.push(format!("telemetry: {:#}", err));
+    }
+    if let Some(addr) = &cfg.statsd
+        && let Err(err) = send_statsd(addr, &render_statsd(&metrics, &cfg.prefix))
+    {
+        warnings.push(format!("telemetry: {:#}", err));
+    }
+    if let Some(endpoint) = &cfg.otlp_endpoint
+        && let Err(err) = send_otlp(
+            endpoint,
+            &render_otlp(&metrics, &cfg.prefix, report),
+            net_cfg,
+        )
+    {
+        warnings.push(format!("telemetry: {:#}", err));
+    }
+    warnings
+}
+
+fn collect(report: &FinalReport) -> Vec<Metric> {
+    let mut metrics = vec![
+        Metric::new("score", "Repository score out of max_score.", report.score),
+        Metric::new(
+            "passed",
+            "1 when the policy passed, 0 otherwise.",
+            u8::from(report.passed),
+        ),
+        Metric::new(
+            "partial",
+            "1 when the scan stopped early and results are partial.",
+            u8::from(report.partial),
+        ),
+    ];
+    for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+        let count = report
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count();
+        metrics.push(
+            Metric::new(
+                "issues",
+                "Issues by severity, passes excluded.",
+                count as f64,
+            )
+            .labelled("severity", severity.slug()),
+        );
+    }
+    for category in Category::ALL {
+        let count = report
+            .issues
+            .iter()
+            .filter(|issue| issue.category == category && issue.severity != Severity::Pass)
+            .count();
+        metrics.push(
+            Metric::new(
+                "category_issues",
+                "Issues by category, passes excluded.",
+                count as f64,
+            )
+            .labelled("category", category.slug()),
+        );
+    }
+    if let Some(run) = &report.run {
+        if let Some(duration_ms) = run.duration_ms {
+            metrics.push(Metric::new(
+                "duration_seconds",
+                "Wall-clock duration of the run.",
+                duration_ms as f64 / 1000.0,
+            ));
+        }
+        metrics.push(Metric::new(
+            "files_scanned",
+            "Files whose content was scanned.",
+            run.files_scanned as f64,
+        ));
+        metrics.push(Metric::new(
+            "files_skipped",
+            "Files skipped as binary or oversized.",
+            run.files_skipped as f64,
+        ));
+    }
+    metrics
+}
+
+fn render_prometheus(metrics: &[Metric], prefix: &str, report: &FinalReport) -> String {
+    let repository = escape_label(&report.repository_path);
+    let mut out = String::new();
+    let mut previous = None;
+    for metric in metrics {
+        let name = format!("{}_{}", prefix, metric.name);
+        if previous != Some(metric.name) {
+            out.push_str(&format!(
+                "# HELP {} {}\n# TYPE {} gauge\n",
+                name, metric.help, name
+            ));
+            previous = Some(metric.name);
+        }
+        let extra = metric
+            .label
+            .map(|(key, value)| format!(",{}=\"{}\"", key, value))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{}{{repository=\"{}\"{}}} {}\n",
+            name, repository, extra, metric.value
+        ));
+    }
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// node_exporter may read the file at any moment, so it is replaced atomically
+fn write_textfile(path: &Path, content: &str) -> Result<()> {
+    sandbox::ensure_allowed(&format!("writing {}", path.display()))?;
+    let tmp = path.with_extension("prom.tmp");
+    fs::write(&tmp, content).with_context(|| format!("failed writing {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("failed replacing {}", path.display()))
+}
+
+fn render_statsd(metrics: &[Metric], prefix: &str) -> Vec<String> {
+    metrics
+        .iter()
+        .map(|metric| {
+            let name = match metric.label {
+                Some((_, value)) => format!("{}.{}.{}", prefix, metric.name, value),
+                None => format!("{}.{}", prefix, metric.name),
+            };
+            format!("{}:{}|g", name, metric.value)
+        })
+        .collect()
+}
+
+fn send_statsd(addr: &str, lines: &[String]) -> Result<()> {
+    if net::is_offline() {
+        bail!(
+            "not sending statsd metrics to {}: devguard is running with --offline",
+            addr
+        );
+    }
+    let socket = UdpSocket::bind("0.0.0.0:0").context("failed to open a UDP socket")?;
+    // one datagram per metric stays well under any MTU
+    for line in lines {
+        socket
+            .send_to(line.as_bytes(), addr)
+            .with_context(|| format!("failed sending statsd metrics to {}", addr))?;
+    }
+    Ok(())
+}
+
+// OTLP/HTTP with the JSON encoding; 64-bit integers are strings per the proto3 JSON mapping
+fn render_otlp(metrics: &[Metric], prefix: &str, report: &FinalReport) -> Value {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or(0)
+        .to_string();
+    let data = metrics
+        .iter()
+        .map(|metric| {
+            let attributes = metric
+                .label
+                .map(|(key, value)| vec![string_attribute(key, value)])
+                .unwrap_or_default();
+            json!({
+                "name": format!("{}.{}", prefix, metric.name),
+                "description": metric.help,
+                "gauge": {
+                    "dataPoints": [{
+                        "asDouble": metric.value,
+                        "timeUnixNano": now,
+                        "attributes": attributes,
+                    }],
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [
+                    string_attribute("service.name", "devguard"),
+                    string_attribute("devguard.repository", &report.repository_path),
+                ],
+            },
+            "scopeMetrics": [{
+                "scope": { "name": report.tool.name, "version": report.tool.version },
+                "metrics": data,
+            }],
+        }],
+    })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn send_otlp(endpoint: &str, body: &Value, net_cfg: &NetConfig) -> Result<()> {
+    let response = net::Client::new(net_cfg)?.request(
+        "POST",
+        endpoint,
+        &[("Content-Type", "application/json")],
+        Some(body),
+    )?;
+    if !response.is_success() {
+        bail!("OTLP endpoint {} answered {}", endpoint, response.status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::sample_report;
+
+    #[test]
+    fn prometheus_textfile_has_one_family_per_metric() {
+        let report = sample_report();
+        let rendered = render_prometheus(&collect(&report), "devguard", &report);
+        assert!(rendered.contains("# TYPE devguard_score gauge\n"));
+        assert!(rendered.contains(&format!(
+            "devguard_score{{repository=\"/tmp/devguard-example\"}} {}\n",
+            report.score
+        )));
+        assert!(rendered.contains(
+            "devguard_issues{repository=\"/tmp/devguard-example\",severity=\"error\"} 1\n"
+        ));
+        assert!(rendered.contains(
+            "devguard_category_issues{repository=\"/tmp/devguard-example\",category=\"secrets\"} 1\n"
+        ));
+        assert_eq!(rendered.matches("# TYPE devguard_issues ").count(), 1);
+    }
+
+    #[test]
+    fn statsd_and_otlp_carry_the_same_values() {
+        let report = sample_report();
+        let metrics = collect(&report);
+        let lines = render_statsd(&metrics, "ci.devguard");
+        assert!(lines.contains(&"ci.devguard.issues.warning:1|g".to_string()));
+        assert!(lines.contains(&"ci.devguard.category_issues.git:1|g".to_string()));
+
+        let otlp = render_otlp(&metrics, "devguard", &report);
+        let sent = &otlp["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(sent[0]["name"], "devguard.score");
+        assert_eq!(
+            sent[0]["gauge"]["dataPoints"][0]["asDouble"],
+            f64::from(report.score)
+        );
+    }
+
+    #[test]
+    fn disabled_telemetry_sends_nothing() {
+        let cfg = TelemetryConfig {
+            statsd: Some("127.0.0.1:9".to_string()),
+            ..TelemetryConfig::default()
+        };
+        assert!(emit(&sample_report(), &cfg, &NetConfig::default()).is_empty());
+    }
+}
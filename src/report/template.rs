@@ -0,0 +1,56 @@
+use crate::core::Severity;
+use crate::report::{FinalReport, RenderOptions};
+use anyhow::{Context, Result};
+use handlebars::Handlebars;
+use std::fs;
+use std::path::Path;
+
+// `--template <file>`: a handlebars template rendered against the same data as
+// `--format json`. passes are left out unless `--show-passes` asks for them
+pub fn render_file(report: &FinalReport, path: &Path, options: RenderOptions) -> Result<String> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("failed reading template {}", path.display()))?;
+    render(report, &source, options)
+        .with_context(|| format!("failed rendering template {}", path.display()))
+}
+
+fn render(report: &FinalReport, source: &str, options: RenderOptions) -> Result<String> {
+    let mut registry = Handlebars::new();
+    // the output is a terminal, markdown, or a PR comment, never HTML
+    registry.register_escape_fn(handlebars::no_escape);
+    registry.register_template_string("report", source)?;
+
+    let mut data = report.clone();
+    if !options.shows_passes(false) {
+        data.issues.retain(|issue| issue.severity != Severity::Pass);
+    }
+    Ok(registry.render("report", &data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::sample_report;
+
+    #[test]
+    fn templates_see_the_json_report_fields() {
+        let template = "{{label}} {{score}}/{{max_score}}{{#unless passed}} FAILED{{/unless}}\n\
+            {{#each issues}}- [{{severity}}] {{code}} {{title}}{{#if file}} ({{file}}){{/if}}\n{{/each}}\
+            errors: {{counts.error}}\n";
+        let report = sample_report();
+        let rendered = render(&report, template, RenderOptions::default()).expect("renders");
+
+        let first = &report.issues[0];
+        assert!(rendered.starts_with(&format!("{} {}/100", report.label, report.score)));
+        assert!(rendered.contains(&format!(
+            "- [error] {} {} ({})",
+            first.code,
+            first.title,
+            first.file.as_deref().expect("sample issue has a file")
+        )));
+        assert!(!rendered.contains("[pass]"));
+        assert!(rendered.ends_with(&format!("errors: {}\n", report.counts.error)));
+
+        assert!(render(&report, "{{#each issues}}", RenderOptions::default()).is_err());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::config::FailOn;
+use crate::config::{FailOn, PolicyConfig};
 use crate::core::{Category, Issue, Severity};
+use crate::policy::{self, PolicyContext};
 use serde::Serialize;
+use std::collections::BTreeMap;
 
 pub const MAX_SCORE: u8 = 100;
 
@@ -9,6 +11,7 @@ pub struct SeverityWeights {
     pub info: u8,
     pub warning: u8,
     pub error: u8,
+    pub pass_credit: u8,
 }
 
 impl Default for SeverityWeights {
@@ -17,6 +20,7 @@ impl Default for SeverityWeights {
             info: 2,
             warning: 8,
             error: 20,
+            pass_credit: 1,
         }
     }
 }
@@ -40,6 +44,9 @@ pub struct CategoryAdjustments {
     pub supabase: u8,
     pub vercel: u8,
     pub stripe: u8,
+    pub release: u8,
+    pub supply: u8,
+    pub tooling: u8,
 }
 
 impl CategoryAdjustments {
@@ -51,6 +58,9 @@ impl CategoryAdjustments {
             Category::Supabase => self.supabase,
             Category::Vercel => self.vercel,
             Category::Stripe => self.stripe,
+            Category::Release => self.release,
+            Category::Supply => self.supply,
+            Category::Tooling => self.tooling,
         }
     }
 }
@@ -85,6 +95,49 @@ pub struct CategoryPenalty {
     pub category: Category,
     pub count: usize,
     pub penalty: u16,
+    pub passed: usize,
+    pub credit: u16,
+    pub checked: bool,
+    pub completeness: Option<u8>,
+}
+
+impl CategoryPenalty {
+    fn new(category: Category) -> Self {
+        Self {
+            category,
+            count: 0,
+            penalty: 0,
+            passed: 0,
+            credit: 0,
+            checked: false,
+            completeness: None,
+        }
+    }
+
+    // pass credit only offsets penalties from the same category, so a clean
+    // category stays at zero and the overall score never exceeds the maximum.
+    fn finalize(&mut self, pass_credit: u8) {
+        self.checked = self.count > 0;
+        self.credit = (self.passed as u16 * u16::from(pass_credit)).min(self.penalty);
+        self.completeness = if self.checked {
+            Some(((self.passed * 100) / self.count) as u8)
+        } else {
+            None
+        };
+    }
+}
+
+// findings without a file are pooled under this name
+pub const REPOSITORY_DIRECTORY: &str = "(repository)";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryPenalty {
+    pub directory: String,
+    pub count: usize,
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+    pub penalty: u16,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -103,11 +156,21 @@ pub struct ScoreBreakdown {
     pub max_score: u8,
     pub final_score: u8,
     pub total_deductions: u16,
+    pub total_credits: u16,
     pub weights: SeverityWeights,
     pub category_adjustments: CategoryAdjustments,
     pub by_severity: WeightedSeverityBreakdown,
     pub by_category: Vec<CategoryPenalty>,
+    // worst first, passes left out
+    pub by_directory: Vec<DirectoryPenalty>,
     pub deductions: Vec<ScoreDeduction>,
+    // charged for suppressed findings under `policy.suppressed_penalty`
+    #[serde(skip_serializing_if = "is_zero")]
+    pub suppressed_penalty: u16,
+}
+
+fn is_zero(value: &u16) -> bool {
+    *value == 0
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Default)]
@@ -154,17 +217,34 @@ pub fn calculate_breakdown(issues: &[Issue], profile: PenaltyProfile) -> ScoreBr
     let mut by_severity = WeightedSeverityBreakdown::default();
     let mut by_category = Category::ALL
         .into_iter()
-        .map(|category| CategoryPenalty {
-            category,
-            count: 0,
-            penalty: 0,
-        })
+        .map(CategoryPenalty::new)
         .collect::<Vec<_>>();
+    let mut by_directory = BTreeMap::<&str, DirectoryPenalty>::new();
     let mut total_deductions = 0_u16;
     let mut deductions = Vec::new();
 
     for issue in issues {
         let (penalty, reason) = profile.penalty_for(issue);
+        if issue.severity != Severity::Pass {
+            let directory = issue.directory().unwrap_or(REPOSITORY_DIRECTORY);
+            let bucket = by_directory
+                .entry(directory)
+                .or_insert_with(|| DirectoryPenalty {
+                    directory: directory.to_string(),
+                    count: 0,
+                    error: 0,
+                    warning: 0,
+                    info: 0,
+                    penalty: 0,
+                });
+            bucket.count += 1;
+            bucket.penalty += u16::from(penalty);
+            match issue.severity {
+                Severity::Error => bucket.error += 1,
+                Severity::Warning => bucket.warning += 1,
+                _ => bucket.info += 1,
+            }
+        }
         let severity_bucket = by_severity.bucket_mut(issue.severity);
         severity_bucket.count += 1;
         severity_bucket.penalty += u16::from(penalty);
@@ -175,6 +255,9 @@ pub fn calculate_breakdown(issues: &[Issue], profile: PenaltyProfile) -> ScoreBr
         {
             category_bucket.count += 1;
             category_bucket.penalty += u16::from(penalty);
+            if issue.severity == Severity::Pass {
+                category_bucket.passed += 1;
+            }
         }
 
         total_deductions += u16::from(penalty);
@@ -190,19 +273,30 @@ pub fn calculate_breakdown(issues: &[Issue], profile: PenaltyProfile) -> ScoreBr
         }
     }
 
-    let final_score =
-        (i32::from(MAX_SCORE) - i32::from(total_deductions)).clamp(0, i32::from(MAX_SCORE)) as u8;
+    for category_bucket in &mut by_category {
+        category_bucket.finalize(profile.weights.pass_credit);
+    }
+    let total_credits = by_category.iter().map(|bucket| bucket.credit).sum::<u16>();
+    let mut by_directory = by_directory.into_values().collect::<Vec<_>>();
+    by_directory.sort_by(|a, b| b.penalty.cmp(&a.penalty).then(b.count.cmp(&a.count)));
+
+    let final_score = (i32::from(MAX_SCORE) - i32::from(total_deductions)
+        + i32::from(total_credits))
+    .clamp(0, i32::from(MAX_SCORE)) as u8;
 
     ScoreBreakdown {
         starting_score: MAX_SCORE,
         max_score: MAX_SCORE,
         final_score,
         total_deductions,
+        total_credits,
         weights: profile.weights,
         category_adjustments: profile.category_adjustments,
         by_severity,
         by_category,
+        by_directory,
         deductions,
+        suppressed_penalty: 0,
     }
 }
 
@@ -211,6 +305,7 @@ pub fn evaluate_policy(
     issues: &[Issue],
     min_score: u8,
     fail_on: FailOn,
+    budgets: &PolicyConfig,
 ) -> PolicyEvaluation {
     let mut reasons = Vec::new();
 
@@ -231,12 +326,87 @@ pub fn evaluate_policy(
         ));
     }
 
+    reasons.extend(evaluate_budgets(issues, budgets));
+    reasons.extend(evaluate_policy_rules(score, min_score, issues, budgets));
+
     PolicyEvaluation {
         passed: reasons.is_empty(),
         reasons,
     }
 }
 
+fn evaluate_policy_rules(
+    score: u8,
+    min_score: u8,
+    issues: &[Issue],
+    budgets: &PolicyConfig,
+) -> Vec<String> {
+    let ctx = PolicyContext {
+        score,
+        min_score,
+        issues,
+    };
+    budgets
+        .rules
+        .iter()
+        .filter_map(|(name, expression)| {
+            match policy::parse(expression).and_then(|expr| expr.evaluate(&ctx)) {
+                Ok(true) => None,
+                Ok(false) => Some(format!("policy rule {} failed: {}", name, expression)),
+                Err(err) => Some(format!(
+                    "policy rule {} could not be evaluated: {}",
+                    name, err
+                )),
+            }
+        })
+        .collect()
+}
+
+// budgets count errors and warnings; info and pass findings never consume budget.
+fn evaluate_budgets(issues: &[Issue], budgets: &PolicyConfig) -> Vec<String> {
+    let mut reasons = Vec::new();
+    let count_where =
+        |predicate: &dyn Fn(&Issue) -> bool| issues.iter().filter(|issue| predicate(issue)).count();
+
+    if let Some(max_errors) = budgets.max_errors {
+        let errors = count_where(&|issue| issue.severity == Severity::Error);
+        if errors > max_errors {
+            reasons.push(format!(
+                "error budget exceeded: {} errors > policy.max_errors {}",
+                errors, max_errors
+            ));
+        }
+    }
+
+    if let Some(max_warnings) = budgets.max_warnings {
+        let warnings = count_where(&|issue| issue.severity == Severity::Warning);
+        if warnings > max_warnings {
+            reasons.push(format!(
+                "warning budget exceeded: {} warnings > policy.max_warnings {}",
+                warnings, max_warnings
+            ));
+        }
+    }
+
+    for (category, max_issues) in &budgets.max_per_category {
+        let found = count_where(&|issue| {
+            issue.category == *category
+                && matches!(issue.severity, Severity::Error | Severity::Warning)
+        });
+        if found > *max_issues {
+            reasons.push(format!(
+                "{} budget exceeded: {} issues > policy.max_per_category.{} {}",
+                category.slug(),
+                found,
+                category.slug(),
+                max_issues
+            ));
+        }
+    }
+
+    reasons
+}
+
 pub fn label_for_score(score: u8) -> &'static str {
     match score {
         90..=100 => "Excellent",
@@ -288,14 +458,109 @@ mod tests {
         ];
 
         let breakdown = calculate_breakdown(&issues, PenaltyProfile::default());
-        assert_eq!(breakdown.final_score, 70);
+        assert_eq!(breakdown.final_score, 71);
         assert_eq!(breakdown.total_deductions, 30);
+        assert_eq!(breakdown.total_credits, 1);
         assert_eq!(breakdown.by_severity.error.penalty, 20);
         assert_eq!(breakdown.by_severity.warning.penalty, 8);
         assert_eq!(breakdown.by_severity.info.penalty, 2);
         assert_eq!(breakdown.by_severity.pass.penalty, 0);
     }
 
+    #[test]
+    fn passing_checks_offset_penalties_within_their_category() {
+        let unchecked = vec![Issue::from_rule(
+            rules::GIT_STATUS_UNAVAILABLE,
+            Severity::Info,
+            "unable to read git status",
+            "run git status",
+        )];
+        let checked = vec![
+            Issue::from_rule(
+                rules::GIT_DIRTY_TREE,
+                Severity::Info,
+                "working tree has changes",
+                "clean it up",
+            ),
+            Issue::from_rule(
+                rules::GIT_BRANCH_IDENTIFIED,
+                Severity::Pass,
+                "current branch: main",
+                "no action needed",
+            ),
+        ];
+
+        let unchecked = calculate_breakdown(&unchecked, PenaltyProfile::default());
+        let checked = calculate_breakdown(&checked, PenaltyProfile::default());
+        assert!(checked.final_score > unchecked.final_score);
+
+        let git = checked
+            .by_category
+            .iter()
+            .find(|bucket| bucket.category == Category::Git)
+            .expect("git bucket exists");
+        assert_eq!(git.credit, 1);
+        assert_eq!(git.completeness, Some(50));
+
+        let secrets = checked
+            .by_category
+            .iter()
+            .find(|bucket| bucket.category == Category::Secrets)
+            .expect("secrets bucket exists");
+        assert!(!secrets.checked);
+        assert_eq!(secrets.completeness, None);
+    }
+
+    #[test]
+    fn directories_are_ranked_by_weighted_penalty() {
+        let finding = |severity, file: Option<&str>| {
+            let issue = Issue::from_rule(rules::SECRET_AWS_ACCESS_KEY, severity, "key", "rotate");
+            match file {
+                Some(file) => issue.with_file(file),
+                None => issue,
+            }
+        };
+        let issues = vec![
+            finding(Severity::Info, Some("apps/web/a.ts")),
+            finding(Severity::Info, Some("apps/web/b.ts")),
+            finding(Severity::Info, Some("apps/web/c.ts")),
+            finding(Severity::Error, Some("services/api/.env")),
+            finding(Severity::Warning, Some("Dockerfile")),
+            finding(Severity::Warning, None),
+            finding(Severity::Pass, Some("docs/README.md")),
+        ];
+        let breakdown = calculate_breakdown(&issues, PenaltyProfile::default());
+        let ranked = breakdown
+            .by_directory
+            .iter()
+            .map(|dir| (dir.directory.as_str(), dir.count, dir.penalty))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranked,
+            [
+                ("services/api", 1, 20),
+                (REPOSITORY_DIRECTORY, 1, 8),
+                (".", 1, 8),
+                ("apps/web", 3, 6),
+            ]
+        );
+        assert_eq!(breakdown.by_directory[3].info, 3);
+    }
+
+    #[test]
+    fn pass_credit_never_lifts_score_above_maximum() {
+        let issues = vec![Issue::from_rule(
+            rules::GIT_CLEAN_TREE,
+            Severity::Pass,
+            "working tree is clean",
+            "no action needed",
+        )];
+
+        let breakdown = calculate_breakdown(&issues, PenaltyProfile::default());
+        assert_eq!(breakdown.final_score, 100);
+        assert_eq!(breakdown.total_credits, 0);
+    }
+
     #[test]
     fn rule_weight_override_takes_precedence() {
         let mut issue = Issue::from_rule(
@@ -320,7 +585,8 @@ mod tests {
             "add DATABASE_URL",
         )];
 
-        let warning_eval = evaluate_policy(92, &issues, 80, FailOn::Warning);
+        let warning_eval =
+            evaluate_policy(92, &issues, 80, FailOn::Warning, &PolicyConfig::default());
         assert!(!warning_eval.passed);
         assert!(
             warning_eval
@@ -329,7 +595,7 @@ mod tests {
                 .any(|reason| reason.contains("fail_on warning"))
         );
 
-        let error_eval = evaluate_policy(92, &issues, 80, FailOn::Error);
+        let error_eval = evaluate_policy(92, &issues, 80, FailOn::Error, &PolicyConfig::default());
         assert!(error_eval.passed);
     }
 
@@ -343,10 +609,72 @@ mod tests {
         )];
 
         let breakdown = calculate_breakdown(&issues, PenaltyProfile::default());
-        let evaluation = evaluate_policy(breakdown.final_score, &issues, 95, FailOn::None);
+        let evaluation = evaluate_policy(
+            breakdown.final_score,
+            &issues,
+            95,
+            FailOn::None,
+            &PolicyConfig::default(),
+        );
 
         assert_eq!(breakdown.final_score, 92);
         assert!(!evaluation.passed);
         assert_eq!(evaluation.reasons, vec!["score 92 is below min_score 95"]);
     }
+
+    #[test]
+    fn budgets_name_the_violated_limit() {
+        let issues = vec![
+            Issue::from_rule(
+                rules::SECRET_STRIPE_TEST_PATTERN,
+                Severity::Warning,
+                "Stripe test key pattern detected",
+                "move it",
+            ),
+            Issue::from_rule(
+                rules::ENV_REQUIRED_VAR_MISSING,
+                Severity::Warning,
+                "missing required env var DATABASE_URL",
+                "add DATABASE_URL",
+            ),
+        ];
+        let mut budgets = PolicyConfig {
+            max_warnings: Some(2),
+            ..PolicyConfig::default()
+        };
+
+        let within = evaluate_policy(100, &issues, 0, FailOn::None, &budgets);
+        assert!(within.passed);
+
+        budgets.max_per_category.insert(Category::Secrets, 0);
+        let exceeded = evaluate_policy(100, &issues, 0, FailOn::None, &budgets);
+        assert!(!exceeded.passed);
+        assert_eq!(
+            exceeded.reasons,
+            vec!["secrets budget exceeded: 1 issues > policy.max_per_category.secrets 0"]
+        );
+    }
+
+    #[test]
+    fn policy_rules_add_named_exit_reasons() {
+        let issues = vec![Issue::from_rule(
+            rules::SECRET_STRIPE_TEST_PATTERN,
+            Severity::Warning,
+            "Stripe test key pattern detected",
+            "move it",
+        )];
+        let mut budgets = PolicyConfig::default();
+        budgets.rules.insert(
+            "release_gate".to_string(),
+            r#"counts.critical == 0 && !issues[category == "Secrets"]"#.to_string(),
+        );
+
+        let evaluation = evaluate_policy(100, &issues, 0, FailOn::None, &budgets);
+        assert_eq!(
+            evaluation.reasons,
+            vec![
+                r#"policy rule release_gate failed: counts.critical == 0 && !issues[category == "Secrets"]"#
+            ]
+        );
+    }
 }
@@ -0,0 +1,122 @@
+use crate::utils::env;
+use serde::{Deserialize, Serialize};
+
+// the CI system a run happens in and where to find the job, recorded in the report so a
+// finding can be traced back to the pipeline that produced it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CiEnvironment {
+    pub provider: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_number: Option<u64>,
+}
+
+impl CiEnvironment {
+    pub fn is_github_actions(&self) -> bool {
+        self.provider == "github-actions"
+    }
+}
+
+pub fn detect() -> Option<CiEnvironment> {
+    from_vars(|name| env::var(name).filter(|value| !value.is_empty()))
+}
+
+pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<CiEnvironment> {
+    let set = |name: &str| var(name).is_some_and(|value| !matches!(value.as_str(), "0" | "false"));
+
+    if set("GITHUB_ACTIONS") {
+        let job_url = match (
+            var("GITHUB_SERVER_URL"),
+            var("GITHUB_REPOSITORY"),
+            var("GITHUB_RUN_ID"),
+        ) {
+            (Some(server), Some(repo), Some(run)) => {
+                Some(format!("{}/{}/actions/runs/{}", server, repo, run))
+            }
+            _ => None,
+        };
+        // pull_request runs check out `refs/pull/<n>/merge`; GITHUB_HEAD_REF is the PR branch
+        let pr_number = var("GITHUB_REF").and_then(|git_ref| {
+            git_ref
+                .strip_prefix("refs/pull/")?
+                .split('/')
+                .next()?
+                .parse()
+                .ok()
+        });
+        return Some(CiEnvironment {
+            provider: "github-actions".to_string(),
+            job_url,
+            branch: var("GITHUB_HEAD_REF").or_else(|| var("GITHUB_REF_NAME")),
+            pr_number,
+        });
+    }
+    if set("GITLAB_CI") {
+        return Some(CiEnvironment {
+            provider: "gitlab".to_string(),
+            job_url: var("CI_JOB_URL"),
+            branch: var("CI_MERGE_REQUEST_SOURCE_BRANCH_NAME")
+                .or_else(|| var("CI_COMMIT_REF_NAME")),
+            pr_number: var("CI_MERGE_REQUEST_IID").and_then(|iid| iid.parse().ok()),
+        });
+    }
+    set("CI").then(|| CiEnvironment {
+        provider: "ci".to_string(),
+        job_url: None,
+        branch: None,
+        pr_number: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(pairs: &[(&str, &str)]) -> Option<CiEnvironment> {
+        let vars = pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn reads_job_branch_and_pull_request_from_each_provider() {
+        let github = env(&[
+            ("CI", "true"),
+            ("GITHUB_ACTIONS", "true"),
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_REPOSITORY", "acme/shop"),
+            ("GITHUB_RUN_ID", "42"),
+            ("GITHUB_REF", "refs/pull/17/merge"),
+            ("GITHUB_REF_NAME", "17/merge"),
+            ("GITHUB_HEAD_REF", "fix-webhooks"),
+        ])
+        .expect("github actions");
+        assert!(github.is_github_actions());
+        assert_eq!(
+            github.job_url.as_deref(),
+            Some("https://github.com/acme/shop/actions/runs/42")
+        );
+        assert_eq!(github.branch.as_deref(), Some("fix-webhooks"));
+        assert_eq!(github.pr_number, Some(17));
+
+        let gitlab = env(&[
+            ("GITLAB_CI", "true"),
+            ("CI_JOB_URL", "https://gitlab.com/acme/shop/-/jobs/9"),
+            ("CI_COMMIT_REF_NAME", "main"),
+        ])
+        .expect("gitlab");
+        assert_eq!(gitlab.provider, "gitlab");
+        assert_eq!(gitlab.branch.as_deref(), Some("main"));
+        assert_eq!(gitlab.pr_number, None);
+
+        assert_eq!(env(&[("CI", "true")]).expect("generic").provider, "ci");
+        assert_eq!(env(&[("CI", "false")]), None);
+        assert_eq!(env(&[]), None);
+    }
+}
@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+// the environment a scan is judged against. a one-shot run reads its own; `devguard
+// daemon` stands the forwarding client's in for each request, so required keys, CI
+// detection, and NO_COLOR come out the same as they would have in-process
+thread_local! {
+    static CLIENT: RefCell<Option<BTreeMap<String, String>>> = const { RefCell::new(None) };
+}
+
+pub fn var(name: &str) -> Option<String> {
+    CLIENT.with_borrow(|client| match client {
+        Some(vars) => vars.get(name).cloned(),
+        None => std::env::var_os(name).map(|value| value.to_string_lossy().into_owned()),
+    })
+}
+
+// what a client sends along with a forwarded request
+pub fn snapshot() -> BTreeMap<String, String> {
+    std::env::vars_os()
+        .map(|(name, value)| {
+            (
+                name.to_string_lossy().into_owned(),
+                value.to_string_lossy().into_owned(),
+            )
+        })
+        .collect()
+}
+
+// runs `f` with `vars` in place of this process's environment
+pub fn with_vars<T>(vars: BTreeMap<String, String>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<BTreeMap<String, String>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CLIENT.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(CLIENT.replace(Some(vars)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_vars_stand_in_for_the_process_environment() {
+        let path = var("PATH");
+        let vars = BTreeMap::from([("DATABASE_URL".to_string(), "postgres://hook".to_string())]);
+        with_vars(vars, || {
+            assert_eq!(var("DATABASE_URL").as_deref(), Some("postgres://hook"));
+            assert_eq!(var("PATH"), None);
+        });
+        assert_eq!(var("PATH"), path);
+    }
+}
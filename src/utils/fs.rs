@@ -1,64 +1,201 @@
-use std::path::Path;
+use crate::config::ScanConfig;
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use walkdir::{DirEntry, WalkDir};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DotenvEntry {
-    pub key: String,
-    pub value: String,
-    pub line: usize,
-}
+// the filesystem-free helpers live in `utils::text` so the scanner core builds without std::fs
+pub use super::text::{
+    DotenvEntry, decode_text, is_likely_binary, is_utf16, looks_like_dotenv, parse_dotenv,
+};
 
 pub fn relative_path(repo_root: &Path, path: &Path) -> String {
-    path.strip_prefix(repo_root)
-        .unwrap_or(path)
+    let root = strip_verbatim_prefix(repo_root);
+    let path = strip_verbatim_prefix(path);
+    if let Ok(relative) = path.strip_prefix(&root) {
+        return normalize_path(relative);
+    }
+
+    let root = normalize_path(&root);
+    let path = normalize_path(&path);
+    strip_prefix_ignore_case(&path, &root).unwrap_or(path)
+}
+
+// `canonicalize` on windows yields `\\?\C:\...` or `\\?\UNC\server\share\...`;
+// strip those so report paths match what users type.
+pub fn strip_verbatim_prefix(path: &Path) -> PathBuf {
+    let raw = path.to_string_lossy();
+    if let Some(rest) = raw.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{}", rest))
+    } else if let Some(rest) = raw.strip_prefix(r"\\?\") {
+        PathBuf::from(rest)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+pub fn normalize_path(path: &Path) -> String {
+    strip_verbatim_prefix(path)
         .to_string_lossy()
         .replace('\\', "/")
 }
 
-pub fn is_likely_binary(bytes: &[u8]) -> bool {
-    let sample_len = bytes.len().min(8192);
-    bytes[..sample_len].contains(&0)
+// windows paths compare case-insensitively, so `C:/Repo` must still prefix `c:/repo/src`.
+fn strip_prefix_ignore_case(path: &str, root: &str) -> Option<String> {
+    if !cfg!(windows) {
+        return None;
+    }
+
+    let root = root.trim_end_matches('/');
+    let head = path.get(..root.len())?;
+    let rest = path.get(root.len()..)?;
+    if head.eq_ignore_ascii_case(root) && rest.starts_with('/') {
+        Some(rest.trim_start_matches('/').to_string())
+    } else {
+        None
+    }
 }
 
-pub fn parse_dotenv(content: &str) -> Vec<DotenvEntry> {
-    let mut entries = Vec::new();
+// walks regular files under `start`, honoring scan excludes and the symlink policy.
+// walkdir reports symlink loops as errors, which are dropped here; followed links
+// whose target resolves outside `repo_root` are never descended into.
+pub fn walk_files<'a>(
+    start: &Path,
+    repo_root: &'a Path,
+    scan: &'a ScanConfig,
+) -> impl Iterator<Item = DirEntry> + 'a {
+    WalkDir::new(start)
+        .follow_links(scan.follow_symlinks)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(move |entry| {
+            should_visit(entry, &scan.exclude)
+                && (!entry.path_is_symlink() || symlink_stays_inside(entry.path(), repo_root))
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+}
 
-    for (idx, raw_line) in content.lines().enumerate() {
-        let line_no = idx + 1;
-        let line = raw_line.trim();
+pub fn find_escaping_symlinks(repo_root: &Path, scan: &ScanConfig) -> Vec<(PathBuf, PathBuf)> {
+    WalkDir::new(repo_root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| should_visit(entry, &scan.exclude))
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path_is_symlink())
+        .filter_map(|entry| {
+            let target = entry.path().canonicalize().ok()?;
+            if target.starts_with(repo_root) {
+                None
+            } else {
+                Some((entry.into_path(), target))
+            }
+        })
+        .collect()
+}
 
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-        if !line.contains('=') {
-            continue;
+fn symlink_stays_inside(path: &Path, repo_root: &Path) -> bool {
+    path.canonicalize()
+        .is_ok_and(|target| target.starts_with(repo_root))
+}
+
+fn should_visit(entry: &DirEntry, excludes: &[String]) -> bool {
+    if !entry.file_type().is_dir() {
+        return true;
+    }
+
+    let dir_name = entry.file_name().to_string_lossy();
+    !excludes
+        .iter()
+        .any(|excluded| excluded.eq_ignore_ascii_case(&dir_name))
+}
+
+// file contents held on the heap right now, summed over every read still alive
+static BUFFERED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+pub enum FileContents {
+    Buffered(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl FileContents {
+    fn buffered(bytes: Vec<u8>) -> Self {
+        BUFFERED_BYTES.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        FileContents::Buffered(bytes)
+    }
+}
+
+impl Drop for FileContents {
+    fn drop(&mut self) {
+        if let FileContents::Buffered(bytes) = self {
+            BUFFERED_BYTES.fetch_sub(bytes.len() as u64, Ordering::Relaxed);
         }
+    }
+}
+
+impl Deref for FileContents {
+    type Target = [u8];
 
-        let mut parts = line.splitn(2, '=');
-        let key = parts.next().unwrap_or("").trim();
-        let value_raw = parts.next().unwrap_or("").trim();
-        if key.is_empty() {
-            continue;
+    fn deref(&self) -> &[u8] {
+        match self {
+            FileContents::Buffered(bytes) => bytes,
+            FileContents::Mapped(map) => map,
         }
+    }
+}
 
-        entries.push(DotenvEntry {
-            key: key.to_string(),
-            value: strip_quotes(value_raw),
-            line: line_no,
-        });
+// reads a file for content scanning. files above `max_file_size_kb`, unreadable
+// files and binaries yield None. anything above `max_buffered_kb`, or that would take
+// the heap held by all live reads past `max_buffered_total_mb`, is memory-mapped so
+// the pages stay file-backed instead of growing the heap.
+pub fn read_scannable(path: &Path, scan: &ScanConfig) -> Option<FileContents> {
+    if has_binary_extension(path, scan) {
+        return None;
     }
 
-    entries
-}
+    let file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    if len > scan.max_file_size_kb * 1024 {
+        return None;
+    }
 
-fn strip_quotes(value: &str) -> String {
-    if value.len() >= 2
-        && ((value.starts_with('"') && value.ends_with('"'))
-            || (value.starts_with('\'') && value.ends_with('\'')))
+    let heap_budget = scan.max_buffered_total_mb * 1024 * 1024;
+    let contents = if len > scan.max_buffered_kb * 1024
+        || BUFFERED_BYTES.load(Ordering::Relaxed) + len > heap_budget
     {
-        value[1..value.len() - 1].to_string()
+        // SAFETY: the map is read-only and dropped once the file is scanned. a file
+        // truncated underneath us mid-scan is the same race a plain read would have.
+        match unsafe { Mmap::map(&file) } {
+            Ok(map) => FileContents::Mapped(map),
+            Err(_) => FileContents::buffered(std::fs::read(path).ok()?),
+        }
     } else {
-        value.to_string()
+        FileContents::buffered(std::fs::read(path).ok()?)
+    };
+
+    if is_likely_binary(&contents) {
+        return None;
     }
+    Some(contents)
+}
+
+pub fn has_binary_extension(path: &Path, scan: &ScanConfig) -> bool {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+    scan.binary_extensions.iter().any(|binary| {
+        binary
+            .trim_start_matches('.')
+            .eq_ignore_ascii_case(extension)
+    })
+}
+
+// like fs::read_to_string, but BOM-aware and tolerant of UTF-16 files
+pub fn read_text(path: &Path) -> std::io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(decode_text(&bytes).into_owned())
 }
 
 #[cfg(test)]
@@ -66,31 +203,155 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parses_dotenv_lines() {
-        let input = r#"
-# comment
-DATABASE_URL=postgres://localhost/dev
-STRIPE_SECRET_KEY="sk_test_abc123abc123abc1"
-EMPTY=
-"#;
-        let parsed = parse_dotenv(input);
-        assert_eq!(parsed.len(), 3);
-        assert_eq!(parsed[0].key, "DATABASE_URL");
-        assert_eq!(parsed[1].value, "sk_test_abc123abc123abc1");
-        assert_eq!(parsed[2].value, "");
+    fn strips_verbatim_and_unc_prefixes() {
+        assert_eq!(
+            normalize_path(Path::new(r"\\?\C:\work\repo")),
+            "C:/work/repo"
+        );
+        assert_eq!(
+            normalize_path(Path::new(r"\\?\UNC\server\share\repo")),
+            "//server/share/repo"
+        );
+        assert_eq!(normalize_path(Path::new("src/main.rs")), "src/main.rs");
     }
 
+    #[cfg(windows)]
     #[test]
-    fn ignores_invalid_or_comment_lines() {
-        let input = r#"
-# ignored
-NOT_VALID
-=ALSO_INVALID
-VALID_KEY=value
-"#;
-        let parsed = parse_dotenv(input);
-        assert_eq!(parsed.len(), 1);
-        assert_eq!(parsed[0].key, "VALID_KEY");
-        assert_eq!(parsed[0].value, "value");
+    fn relative_path_handles_mixed_verbatim_and_case() {
+        assert_eq!(
+            relative_path(
+                Path::new(r"\\?\C:\Work\Repo"),
+                Path::new(r"c:\work\repo\src\.env")
+            ),
+            "src/.env"
+        );
+        assert_eq!(
+            relative_path(
+                Path::new(r"\\?\UNC\server\share\repo"),
+                Path::new(r"\\server\share\repo\config\app.env")
+            ),
+            "config/app.env"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walker_skips_symlink_loops_and_reports_escapes() {
+        use std::os::unix::fs::symlink;
+
+        let base = std::env::temp_dir().join(format!("devguard-walk-{}", std::process::id()));
+        let repo = base.join("repo");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(repo.join("src")).expect("create repo");
+        std::fs::create_dir_all(&outside).expect("create outside dir");
+        std::fs::write(repo.join("src/app.js"), "ok").expect("write file");
+        std::fs::write(outside.join("secret.env"), "KEY=1").expect("write outside file");
+        symlink(&repo, repo.join("src/loop")).expect("create loop link");
+        symlink(&outside, repo.join("escape")).expect("create escape link");
+        let repo = repo.canonicalize().expect("canonical repo");
+
+        let scan = ScanConfig {
+            follow_symlinks: true,
+            ..ScanConfig::default()
+        };
+        let files = walk_files(&repo, &repo, &scan)
+            .map(|entry| relative_path(&repo, entry.path()))
+            .collect::<Vec<_>>();
+        assert!(files.contains(&"src/app.js".to_string()));
+        assert!(!files.iter().any(|file| file.contains("secret.env")));
+
+        let escapes = find_escaping_symlinks(&repo, &scan);
+        assert_eq!(escapes.len(), 1);
+        assert!(escapes[0].0.ends_with("escape"));
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn large_files_are_mapped_and_binaries_skipped() {
+        let dir = std::env::temp_dir().join(format!("devguard-read-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let small = dir.join("small.txt");
+        let large = dir.join("large.txt");
+        let binary = dir.join("blob.bin");
+        std::fs::write(&small, "KEY=1").expect("write small");
+        std::fs::write(&large, "a".repeat(4096)).expect("write large");
+        std::fs::write(&binary, [0u8, 1, 2, 3]).expect("write binary");
+
+        let scan = ScanConfig {
+            max_file_size_kb: 8,
+            max_buffered_kb: 1,
+            ..ScanConfig::default()
+        };
+        assert!(matches!(
+            read_scannable(&small, &scan),
+            Some(FileContents::Buffered(_))
+        ));
+        let mapped = read_scannable(&large, &scan).expect("large file is readable");
+        assert!(matches!(mapped, FileContents::Mapped(_)));
+        assert_eq!(mapped.len(), 4096);
+        assert!(read_scannable(&binary, &scan).is_none());
+
+        // with no heap left across the scan, even a small file is mapped
+        let no_heap = ScanConfig {
+            max_buffered_total_mb: 0,
+            ..scan.clone()
+        };
+        assert!(matches!(
+            read_scannable(&small, &no_heap),
+            Some(FileContents::Mapped(_))
+        ));
+
+        let tight = ScanConfig {
+            max_file_size_kb: 1,
+            ..scan
+        };
+        assert!(read_scannable(&large, &tight).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn utf16_text_is_decoded_instead_of_skipped() {
+        let mut le = vec![0xFF, 0xFE];
+        le.extend("KEY=sk_live_abc".encode_utf16().flat_map(u16::to_le_bytes));
+        assert!(!is_likely_binary(&le));
+        assert_eq!(decode_text(&le), "KEY=sk_live_abc");
+
+        let be_without_bom = "token = v1.abc"
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect::<Vec<_>>();
+        assert!(!is_likely_binary(&be_without_bom));
+        assert_eq!(decode_text(&be_without_bom), "token = v1.abc");
+
+        assert!(is_likely_binary(&[
+            0x7F, 0x45, 0x4C, 0x46, 0x02, 0x01, 0x01, 0x03
+        ]));
+        assert!(has_binary_extension(
+            Path::new("assets/logo.PNG"),
+            &ScanConfig::default()
+        ));
+    }
+
+    #[test]
+    fn bom_prefixed_dotenv_files_parse_like_plain_utf8() {
+        let dir = std::env::temp_dir().join(format!("devguard-bom-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let utf8 = dir.join(".env.utf8");
+        let utf16 = dir.join(".env.utf16");
+        std::fs::write(&utf8, b"\xEF\xBB\xBFAPI_KEY=one\n").expect("write utf8");
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend("API_KEY=two\r\n".encode_utf16().flat_map(u16::to_le_bytes));
+        std::fs::write(&utf16, bytes).expect("write utf16");
+
+        for (path, value) in [(&utf8, "one"), (&utf16, "two")] {
+            let parsed = parse_dotenv(&read_text(path).expect("readable"));
+            assert_eq!(parsed.len(), 1);
+            assert_eq!(parsed[0].key, "API_KEY");
+            assert_eq!(parsed[0].value, value);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -0,0 +1,213 @@
+use super::LineOrigin;
+use anyhow::{Context, Result};
+use gix::bstr::ByteSlice;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub use gix::Repository;
+
+pub fn discover_repo(repo_root: &Path) -> Option<Repository> {
+    gix::discover(repo_root).ok()
+}
+
+pub fn workdir(repo: &Repository) -> Option<&Path> {
+    repo.work_dir()
+}
+
+// gix's status only compares the index with the worktree and untracked files, so HEAD
+// and the index are compared first to catch changes that are staged but not committed
+pub fn is_working_tree_dirty(repo: &Repository) -> Result<bool> {
+    if index_differs_from_head(repo)? {
+        return Ok(true);
+    }
+    let mut changes = repo
+        .status(gix::progress::Discard)
+        .context("failed to read git status")?
+        .into_index_worktree_iter(Vec::new())
+        .context("failed to read git status")?;
+    match changes.next() {
+        Some(change) => change.map(|_| true).context("failed to read git status"),
+        None => Ok(false),
+    }
+}
+
+fn index_differs_from_head(repo: &Repository) -> Result<bool> {
+    let mut committed = match repo.head_commit() {
+        Ok(commit) => commit
+            .tree()
+            .context("failed to read the HEAD tree")?
+            .traverse()
+            .breadthfirst
+            .files()
+            .context("failed to read the HEAD tree")?
+            .into_iter()
+            .filter(|entry| !entry.mode.is_tree())
+            .map(|entry| (entry.filepath, (entry.oid, entry.mode)))
+            .collect::<HashMap<_, _>>(),
+        // nothing is committed on an unborn branch, so every index entry is staged
+        Err(_) if repo.head().is_ok_and(|head| head.is_unborn()) => HashMap::new(),
+        Err(err) => return Err(err).context("failed to read HEAD"),
+    };
+    let index = repo.index_or_empty().context("failed to open git index")?;
+    for entry in index.entries() {
+        // a stage other than 0 is an unresolved conflict
+        if entry.stage_raw() != 0 {
+            return Ok(true);
+        }
+        match committed.remove(entry.path(&index)) {
+            Some((id, mode)) if id == entry.id && entry.mode.to_tree_entry_mode() == Some(mode) => {
+            }
+            _ => return Ok(true),
+        }
+    }
+    Ok(!committed.is_empty())
+}
+
+pub fn index_path(repo: &Repository) -> PathBuf {
+    repo.index_path()
+}
+
+pub fn tracked_paths(repo: &Repository) -> Result<Vec<String>> {
+    let idx = repo.index_or_empty().context("failed to open git index")?;
+    Ok(idx
+        .entries()
+        .iter()
+        .filter_map(|entry| entry.path(&idx).to_str().ok())
+        .map(|path| path.replace('\\', "/"))
+        .collect())
+}
+
+// Ok(None) means HEAD is detached
+pub fn head_branch(repo: &Repository) -> Result<Option<String>> {
+    let head = repo.head()?;
+    Ok(head
+        .referent_name()
+        .map(|name| name.shorten().to_str_lossy().into_owned()))
+}
+
+pub fn origin_url(repo: &Repository) -> Option<String> {
+    let remote = repo.find_remote("origin").ok()?;
+    remote
+        .url(gix::remote::Direction::Fetch)
+        .map(|url| url.to_bstring().to_string())
+}
+
+pub fn head_commit_and_branch(repo: &Repository) -> (Option<String>, Option<String>) {
+    let commit = repo.head_id().ok().map(|id| id.to_string());
+    let branch = head_branch(repo).ok().flatten();
+    (commit, branch)
+}
+
+// first-parent history from HEAD, as the libgit2 backend walks it
+pub fn last_changed(
+    repo: &Repository,
+    paths: &[String],
+    max_commits: usize,
+) -> Result<Vec<Option<u64>>> {
+    let mut changed = vec![None; paths.len()];
+    let Ok(mut commit) = repo.head_commit() else {
+        return Ok(changed);
+    };
+    let mut buf = Vec::new();
+    let mut entry_id = |tree: &gix::Tree<'_>, path: &str| {
+        tree.lookup_entry_by_path(path, &mut buf)
+            .ok()
+            .flatten()
+            .map(|entry| entry.object_id())
+    };
+
+    for _ in 0..max_commits {
+        let tree = commit.tree().context("failed to read commit tree")?;
+        let parent = commit
+            .parent_ids()
+            .next()
+            .map(|id| -> Result<gix::Commit<'_>> { Ok(id.object()?.try_into_commit()?) })
+            .transpose()
+            .context("failed to read parent commit")?;
+        let parent_tree = parent
+            .as_ref()
+            .map(|parent| parent.tree())
+            .transpose()
+            .context("failed to read commit tree")?;
+        let time = u64::try_from(commit.time().context("failed to read commit time")?.seconds)
+            .unwrap_or(0);
+        for (slot, path) in changed.iter_mut().zip(paths) {
+            if slot.is_some() {
+                continue;
+            }
+            let current = entry_id(&tree, path);
+            let before = parent_tree.as_ref().and_then(|tree| entry_id(tree, path));
+            if current.is_some() && current != before {
+                *slot = Some(time);
+            }
+        }
+        match parent {
+            Some(parent) if changed.iter().any(Option::is_none) => commit = parent,
+            _ => break,
+        }
+    }
+    Ok(changed)
+}
+
+// gix has no blame support in the version we build against, so findings from a
+// gitoxide build carry no attribution and the run reports DG_SCAN_004 instead
+pub const BLAME_SUPPORTED: bool = false;
+
+pub fn blame_lines(
+    _repo: &Repository,
+    _rel_path: &str,
+    _content: &[u8],
+    lines: &[usize],
+) -> Result<Vec<Option<LineOrigin>>> {
+    Ok(lines.iter().map(|_| None).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::process;
+    use std::fs;
+    use std::time::Duration;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let root = dir.to_string_lossy();
+        let identity = ["-c", "user.name=Ada", "-c", "user.email=ada@example.com"];
+        let output = process::run_with_timeout(
+            "git",
+            &[&["-C", root.as_ref()], &identity[..], args].concat(),
+            Duration::from_secs(30),
+        )
+        .expect("git runs");
+        assert!(output.success, "{}", output.first_line());
+    }
+
+    #[test]
+    fn staged_changes_count_as_dirty_and_history_dates_paths() {
+        let dir = std::env::temp_dir().join(format!("devguard-gix-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("dir created");
+        git(&dir, &["init", "--quiet"]);
+        fs::write(dir.join("config.js"), "const a = 1;\n").expect("file written");
+        git(&dir, &["add", "config.js"]);
+        let unborn = discover_repo(&dir).expect("repo found");
+        let staged_on_unborn = is_working_tree_dirty(&unborn).expect("status read");
+
+        git(&dir, &["commit", "--quiet", "-m", "add config"]);
+        let repo = discover_repo(&dir).expect("repo found");
+        let clean = is_working_tree_dirty(&repo).expect("status read");
+        let changed = last_changed(&repo, &["config.js".to_string(), "gone.js".to_string()], 10)
+            .expect("history read");
+
+        fs::write(dir.join("config.js"), "const a = 2;\n").expect("file written");
+        git(&dir, &["add", "config.js"]);
+        let staged =
+            is_working_tree_dirty(&discover_repo(&dir).expect("repo found")).expect("status read");
+        fs::remove_dir_all(&dir).expect("cleanup");
+
+        assert!(staged_on_unborn);
+        assert!(!clean);
+        assert!(staged);
+        assert!(changed[0].is_some_and(|time| time > 1_700_000_000));
+        assert_eq!(changed[1], None);
+    }
+}
@@ -0,0 +1,218 @@
+use super::LineOrigin;
+use anyhow::{Context, Result};
+use git2::StatusOptions;
+use std::path::{Path, PathBuf};
+
+pub use git2::Repository;
+
+pub fn discover_repo(repo_root: &Path) -> Option<Repository> {
+    Repository::discover(repo_root).ok()
+}
+
+pub fn workdir(repo: &Repository) -> Option<&Path> {
+    repo.workdir()
+}
+
+pub fn is_working_tree_dirty(repo: &Repository) -> Result<bool> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true);
+
+    let statuses = repo
+        .statuses(Some(&mut opts))
+        .context("failed to read git status")?;
+
+    Ok(!statuses.is_empty())
+}
+
+pub fn index_path(repo: &Repository) -> PathBuf {
+    repo.path().join("index")
+}
+
+pub fn tracked_paths(repo: &Repository) -> Result<Vec<String>> {
+    let idx = repo.index().context("failed to open git index")?;
+    Ok(idx
+        .iter()
+        .filter_map(|entry| String::from_utf8(entry.path).ok())
+        .map(|path| path.replace('\\', "/"))
+        .collect())
+}
+
+// Ok(None) means HEAD is detached
+pub fn head_branch(repo: &Repository) -> Result<Option<String>> {
+    let head = repo.head()?;
+    Ok(head
+        .is_branch()
+        .then(|| head.shorthand().unwrap_or("unknown").to_string()))
+}
+
+pub fn origin_url(repo: &Repository) -> Option<String> {
+    repo.find_remote("origin").ok()?.url().map(str::to_string)
+}
+
+pub fn head_commit_and_branch(repo: &Repository) -> (Option<String>, Option<String>) {
+    let Ok(head) = repo.head() else {
+        return (None, None);
+    };
+    let commit = head
+        .peel_to_commit()
+        .ok()
+        .map(|commit| commit.id().to_string());
+    let branch = head
+        .is_branch()
+        .then(|| head.shorthand().map(str::to_string))
+        .flatten();
+    (commit, branch)
+}
+
+pub const BLAME_SUPPORTED: bool = true;
+
+// blames the working-tree content rather than HEAD so line numbers match the scan;
+// lines that are not committed yet come back as None
+pub fn blame_lines(
+    repo: &Repository,
+    rel_path: &str,
+    content: &[u8],
+    lines: &[usize],
+) -> Result<Vec<Option<LineOrigin>>> {
+    let committed = repo
+        .blame_file(Path::new(rel_path), None)
+        .with_context(|| format!("failed to blame {}", rel_path))?;
+    let blame = committed
+        .blame_buffer(content)
+        .with_context(|| format!("failed to blame working copy of {}", rel_path))?;
+    Ok(lines
+        .iter()
+        .map(|line| {
+            let commit_id = blame.get_line(*line)?.final_commit_id();
+            if commit_id.is_zero() {
+                return None;
+            }
+            // hunks split by a buffer blame lose their signature, so read it from the commit
+            let commit = repo.find_commit(commit_id).ok()?;
+            let signature = commit.author();
+            let name = signature.name().unwrap_or("unknown");
+            Some(LineOrigin {
+                commit: commit_id.to_string(),
+                author: match signature.email() {
+                    Some(email) => format!("{} <{}>", name, email),
+                    None => name.to_string(),
+                },
+                time: u64::try_from(signature.when().seconds()).unwrap_or(0),
+            })
+        })
+        .collect())
+}
+
+// commit time of the last first-parent commit that changed each path (a file or a
+// directory) at HEAD, looking back at most `max_commits` commits
+pub fn last_changed(
+    repo: &Repository,
+    paths: &[String],
+    max_commits: usize,
+) -> Result<Vec<Option<u64>>> {
+    let mut changed = vec![None; paths.len()];
+    let Ok(mut commit) = repo.head().and_then(|head| head.peel_to_commit()) else {
+        return Ok(changed);
+    };
+    let entry_id =
+        |tree: &git2::Tree, path: &str| tree.get_path(Path::new(path)).ok().map(|entry| entry.id());
+
+    for _ in 0..max_commits {
+        let tree = commit.tree().context("failed to read commit tree")?;
+        let parent = commit.parent(0).ok();
+        let parent_tree = parent.as_ref().map(|parent| parent.tree()).transpose()?;
+        let time = u64::try_from(commit.time().seconds()).unwrap_or(0);
+        for (slot, path) in changed.iter_mut().zip(paths) {
+            if slot.is_some() {
+                continue;
+            }
+            let current = entry_id(&tree, path);
+            let before = parent_tree.as_ref().and_then(|tree| entry_id(tree, path));
+            if current.is_some() && current != before {
+                *slot = Some(time);
+            }
+        }
+        match parent {
+            Some(parent) if changed.iter().any(Option::is_none) => commit = parent,
+            _ => break,
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+
+    #[test]
+    fn blame_attributes_committed_lines_only() {
+        let dir = std::env::temp_dir().join(format!("devguard-blame-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let repo = Repository::init(&dir).expect("repo initialized");
+        fs::write(dir.join("config.js"), "const a = 1;\nconst b = 2;\n").expect("file written");
+
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new("config.js")).expect("file staged");
+        let tree = repo
+            .find_tree(index.write_tree().expect("tree written"))
+            .expect("tree");
+        let author = Signature::new("Ada", "ada@example.com", &git2::Time::new(1_700_000_000, 0))
+            .expect("signature");
+        let commit = repo
+            .commit(Some("HEAD"), &author, &author, "add config", &tree, &[])
+            .expect("committed");
+
+        let content = b"const a = 1;\nconst key = 3;\nconst b = 2;\n";
+        let origins = blame_lines(&repo, "config.js", content, &[1, 2, 3]).expect("blamed");
+        fs::remove_dir_all(&dir).expect("cleanup");
+
+        let expected = LineOrigin {
+            commit: commit.to_string(),
+            author: "Ada <ada@example.com>".to_string(),
+            time: 1_700_000_000,
+        };
+        assert_eq!(origins, [Some(expected.clone()), None, Some(expected)]);
+    }
+
+    #[test]
+    fn last_changed_follows_files_and_directories() {
+        let dir = std::env::temp_dir().join(format!("devguard-history-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let repo = Repository::init(&dir).expect("repo initialized");
+        let mut parents = Vec::new();
+        for (time, file) in [
+            (1_700_000_000, "db/migrations/001.sql"),
+            (1_700_100_000, "src/models/user.ts"),
+            (1_700_200_000, "README.md"),
+        ] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().expect("parent")).expect("dir created");
+            fs::write(&path, file).expect("file written");
+            let mut index = repo.index().expect("index");
+            index.add_path(Path::new(file)).expect("file staged");
+            index.write().expect("index written");
+            let tree = repo
+                .find_tree(index.write_tree().expect("tree written"))
+                .expect("tree");
+            let author = Signature::new("Ada", "ada@example.com", &git2::Time::new(time, 0))
+                .expect("signature");
+            let parent_refs = parents.iter().collect::<Vec<_>>();
+            let id = repo
+                .commit(Some("HEAD"), &author, &author, file, &tree, &parent_refs)
+                .expect("committed");
+            parents = vec![repo.find_commit(id).expect("commit")];
+        }
+
+        let paths = ["db/migrations", "src/models/user.ts", "missing.txt"].map(str::to_string);
+        let changed = last_changed(&repo, &paths, 100).expect("history read");
+        drop(parents);
+        fs::remove_dir_all(&dir).expect("cleanup");
+        assert_eq!(changed, [Some(1_700_000_000), Some(1_700_100_000), None]);
+    }
+}
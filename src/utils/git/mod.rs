@@ -0,0 +1,112 @@
+use crate::utils::fs::strip_verbatim_prefix;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+// libgit2 is the default backend; building with `--features gitoxide` swaps in the
+// pure-Rust implementation behind the same functions
+#[cfg(not(any(feature = "libgit2", feature = "gitoxide")))]
+compile_error!("devguard needs a git backend: enable the `libgit2` or `gitoxide` feature");
+
+#[cfg(feature = "gitoxide")]
+mod gitoxide;
+#[cfg(all(feature = "libgit2", not(feature = "gitoxide")))]
+mod libgit2;
+
+#[cfg(feature = "gitoxide")]
+use gitoxide as backend;
+#[cfg(all(feature = "libgit2", not(feature = "gitoxide")))]
+use libgit2 as backend;
+
+// lives with `Issue`, which carries it, so the scanner builds without a git backend
+pub use crate::core::issue::LineOrigin;
+
+pub use backend::{
+    BLAME_SUPPORTED, Repository, blame_lines, discover_repo, head_branch, head_commit_and_branch,
+    index_path, is_working_tree_dirty, last_changed, origin_url, tracked_paths, workdir,
+};
+
+// the index read once into memory, for callers asking about many paths
+pub struct TrackedSet {
+    workdir: PathBuf,
+    paths: HashSet<String>,
+}
+
+impl TrackedSet {
+    pub fn load(repo: &Repository, repo_root: &Path) -> Result<Self> {
+        Ok(Self {
+            workdir: strip_verbatim_prefix(workdir(repo).unwrap_or(repo_root)),
+            paths: tracked_paths(repo)?.into_iter().collect(),
+        })
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.tracked_relative(path).is_some()
+    }
+
+    // the path as git knows it, if it is tracked
+    pub fn tracked_relative(&self, path: &Path) -> Option<String> {
+        relative_to_workdir(&self.workdir, path).filter(|rel| self.paths.contains(rel))
+    }
+
+    // like `tracked_relative`, but also accepts a directory holding tracked files
+    pub fn tracked_entry(&self, path: &Path) -> Option<String> {
+        relative_to_workdir(&self.workdir, path).filter(|rel| self.has_prefix(rel))
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.paths.iter().map(String::as_str)
+    }
+
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        let (p, p_slash) = normalize_prefix(prefix);
+        self.paths
+            .iter()
+            .any(|path| *path == p || path.starts_with(&p_slash))
+    }
+}
+
+fn relative_to_workdir(workdir: &Path, path: &Path) -> Option<String> {
+    let abs = if path.is_absolute() {
+        strip_verbatim_prefix(path)
+    } else {
+        workdir.join(path)
+    };
+    let rel = abs.strip_prefix(workdir).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+fn normalize_prefix(prefix: &str) -> (String, String) {
+    let mut p = prefix.replace('\\', "/");
+    while p.starts_with("./") {
+        p = p[2..].to_string();
+    }
+    let p_slash = if p.ends_with('/') {
+        p.clone()
+    } else {
+        format!("{}/", p)
+    };
+    (p, p_slash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracked_set_answers_paths_and_prefixes_from_memory() {
+        let set = TrackedSet {
+            workdir: PathBuf::from("/repo"),
+            paths: [".env", ".vercel/project.json", "src/main.rs"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        };
+        assert!(set.contains(Path::new("/repo/.env")));
+        assert!(set.contains(Path::new("src/main.rs")));
+        assert!(!set.contains(Path::new("/repo/.env.local")));
+        assert!(!set.contains(Path::new("/elsewhere/.env")));
+        assert!(set.has_prefix("./.vercel"));
+        assert!(!set.has_prefix(".verc"));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::config::NetConfig;
+use crate::utils::net;
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+pub struct GithubClient {
+    client: net::Client,
+    api_url: String,
+    token: String,
+}
+
+impl GithubClient {
+    // honours GITHUB_API_URL so GitHub Enterprise Server works the same way actions do
+    pub fn new(token: String, cfg: &NetConfig) -> Result<Self> {
+        let api_url = std::env::var("GITHUB_API_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        Ok(Self {
+            client: net::Client::new(cfg)?,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    // returns Ok(None) for 404s, which GitHub also uses for "exists but you may not see it".
+    pub fn get(&self, path: &str) -> Result<Option<Value>> {
+        self.send("GET", path, None)
+    }
+
+    pub fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Option<Value>> {
+        let url = format!("{}{}", self.api_url, path);
+        let authorization = format!("Bearer {}", self.token);
+        let response = self
+            .client
+            .request(
+                method,
+                &url,
+                &[
+                    ("Authorization", &authorization),
+                    ("Accept", "application/vnd.github+json"),
+                    ("X-GitHub-Api-Version", "2022-11-28"),
+                ],
+                body,
+            )
+            .with_context(|| format!("GitHub API {} {} failed", method, path))?;
+
+        match response.status {
+            204 => Ok(Some(Value::Null)),
+            404 => Ok(None),
+            _ if response.is_success() => {
+                Ok(Some(response.body.with_context(|| {
+                    format!("invalid JSON from {} {}", method, path)
+                })?))
+            }
+            code @ (401 | 403) => {
+                let message = response
+                    .body
+                    .as_ref()
+                    .and_then(|json| json.get("message"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                bail!(
+                    "GitHub API {} {} returned {}: {}",
+                    method,
+                    path,
+                    code,
+                    message
+                )
+            }
+            code => bail!("GitHub API {} {} returned {}", method, path, code),
+        }
+    }
+}
+
+pub fn token_from_env() -> Option<String> {
+    ["GITHUB_TOKEN", "GH_TOKEN"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|token| !token.trim().is_empty())
+}
+
+// owner/name from ssh or https remote urls
+pub fn parse_remote_slug(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let path = if let Some(rest) = url.strip_prefix("git@") {
+        rest.split_once(':')?.1
+    } else {
+        let without_scheme = url.split_once("://")?.1;
+        without_scheme.split_once('/')?.1
+    };
+    let path = path.trim_end_matches(".git");
+    let mut parts = path.split('/');
+    let owner = parts.next().filter(|part| !part.is_empty())?;
+    let name = parts.next().filter(|part| !part.is_empty())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(format!("{}/{}", owner, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ssh_and_https_remotes() {
+        assert_eq!(
+            parse_remote_slug("git@github.com:aryasalem09/devguard.git").as_deref(),
+            Some("aryasalem09/devguard")
+        );
+        assert_eq!(
+            parse_remote_slug("https://github.com/aryasalem09/devguard").as_deref(),
+            Some("aryasalem09/devguard")
+        );
+        assert_eq!(
+            parse_remote_slug("ssh://git@github.com/org/app.git/").as_deref(),
+            Some("org/app")
+        );
+        assert_eq!(parse_remote_slug("/srv/git/app.git"), None);
+    }
+}
@@ -0,0 +1,59 @@
+use regex::Regex;
+
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+// translates a slash-separated glob into an unanchored regex body. `*` and `?` stay
+// within one path segment and `**/` spans any number of directories.
+pub fn glob_body(pattern: &str) -> String {
+    let mut regex = String::new();
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let mut idx = 0;
+    while idx < chars.len() {
+        match chars[idx] {
+            '*' if chars.get(idx + 1) == Some(&'*') => {
+                if chars.get(idx + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    idx += 3;
+                } else {
+                    regex.push_str(".*");
+                    idx += 2;
+                }
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+        idx += 1;
+    }
+    regex
+}
+
+// matches whole repo-relative paths, e.g. `apps/*/.env*`
+pub fn path_glob(pattern: &str) -> Option<Regex> {
+    let body = pattern.trim_start_matches("./").trim_start_matches('/');
+    Regex::new(&format!("^{}$", glob_body(body))).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_globs_stay_within_segments_unless_double_star() {
+        let root_env = path_glob(".env.*").expect("valid glob");
+        assert!(root_env.is_match(".env.staging"));
+        assert!(!root_env.is_match("apps/web/.env.staging"));
+
+        let per_app = path_glob("apps/*/.env*").expect("valid glob");
+        assert!(per_app.is_match("apps/web/.env"));
+        assert!(per_app.is_match("apps/api/.env.local"));
+        assert!(!per_app.is_match("apps/web/nested/.env"));
+
+        let anywhere = path_glob("**/secrets/*.env").expect("valid glob");
+        assert!(anywhere.is_match("secrets/prod.env"));
+        assert!(anywhere.is_match("config/secrets/prod.env"));
+    }
+}
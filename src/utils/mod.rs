@@ -1,2 +1,21 @@
+#[cfg(not(feature = "wasm"))]
+pub mod ci;
+pub mod date;
+pub mod digest;
+#[cfg(not(feature = "wasm"))]
+pub mod env;
+#[cfg(not(feature = "wasm"))]
 pub mod fs;
+#[cfg(not(feature = "wasm"))]
 pub mod git;
+#[cfg(not(feature = "wasm"))]
+pub mod github;
+pub mod glob;
+#[cfg(not(feature = "wasm"))]
+pub mod net;
+#[cfg(not(feature = "wasm"))]
+pub mod process;
+pub mod sandbox;
+pub mod text;
+#[cfg(not(feature = "wasm"))]
+pub mod zip;
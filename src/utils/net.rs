@@ -0,0 +1,191 @@
+use crate::config::NetConfig;
+use crate::utils::sandbox;
+use anyhow::{Result, bail};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+// every outbound request goes through `Client::request`, so this is the single switch
+// that keeps air-gapped runs from ever opening a socket
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::SeqCst);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+        || sandbox::is_sandboxed()
+        || std::env::var("DEVGUARD_OFFLINE")
+            .is_ok_and(|value| !matches!(value.trim(), "" | "0" | "false"))
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Option<Value>,
+    pub text: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+pub struct Client {
+    #[cfg(feature = "net")]
+    agent: ureq::Agent,
+    retries: u32,
+    offline: bool,
+}
+
+impl Client {
+    pub fn new(cfg: &NetConfig) -> Result<Self> {
+        #[cfg(feature = "net")]
+        {
+            let mut builder = ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(cfg.timeout_secs.max(1)))
+                .user_agent(concat!("devguard/", env!("CARGO_PKG_VERSION")))
+                .try_proxy_from_env(true);
+            if let Some(proxy) = cfg
+                .proxy
+                .as_deref()
+                .filter(|proxy| !proxy.trim().is_empty())
+            {
+                builder = builder.proxy(
+                    ureq::Proxy::new(proxy)
+                        .map_err(|err| anyhow::anyhow!("invalid net.proxy {}: {}", proxy, err))?,
+                );
+            }
+            Ok(Self {
+                agent: builder.build(),
+                retries: cfg.retries,
+                offline: is_offline(),
+            })
+        }
+        #[cfg(not(feature = "net"))]
+        {
+            Ok(Self {
+                retries: cfg.retries,
+                offline: is_offline(),
+            })
+        }
+    }
+
+    // non-2xx statuses come back as responses so callers can map them; only transport
+    // failures and exhausted retries are errors
+    pub fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&Value>,
+    ) -> Result<Response> {
+        // asked again per request, in case --offline was switched on after the client was built
+        if self.offline || is_offline() {
+            bail!(
+                "refusing {} {}: devguard is running with --offline",
+                method,
+                url
+            );
+        }
+
+        let mut attempt = 0;
+        loop {
+            match self.send_once(method, url, headers, body) {
+                Ok(response) if attempt < self.retries && is_retryable(method, response.status) => {
+                }
+                Ok(response) => return Ok(response),
+                Err(err) if attempt >= self.retries || !is_idempotent(method) => {
+                    return Err(err.context(format!("{} {} failed", method, url)));
+                }
+                Err(_) => {}
+            }
+            std::thread::sleep(backoff(attempt));
+            attempt += 1;
+        }
+    }
+
+    #[cfg(feature = "net")]
+    fn send_once(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&Value>,
+    ) -> Result<Response> {
+        let mut request = self.agent.request(method, url);
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        let response = match body {
+            Some(body) => request.send_json(body.clone()),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(err) => return Err(err.into()),
+        };
+        let status = response.status();
+        let text = response.into_string().unwrap_or_default();
+        let body = serde_json::from_str::<Value>(&text).ok();
+        Ok(Response { status, body, text })
+    }
+
+    #[cfg(not(feature = "net"))]
+    fn send_once(
+        &self,
+        method: &str,
+        url: &str,
+        _headers: &[(&str, &str)],
+        _body: Option<&Value>,
+    ) -> Result<Response> {
+        bail!(
+            "cannot {} {}: devguard was built without the `net` feature (reinstall with `--features net`)",
+            method,
+            url
+        )
+    }
+}
+
+// a POST that failed with a 5xx or mid-transfer may still have been applied, and sending
+// it again could open a second tracker issue; a 429 means it was turned away unprocessed
+fn is_retryable(method: &str, status: u16) -> bool {
+    status == 429 || (is_idempotent(method) && matches!(status, 500 | 502 | 503 | 504))
+}
+
+fn is_idempotent(method: &str) -> bool {
+    !method.eq_ignore_ascii_case("POST")
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(500 * 2u64.pow(attempt.min(4)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_mode_refuses_before_connecting() {
+        let mut client = Client::new(&NetConfig::default()).expect("client builds");
+        client.offline = true;
+        let err = client
+            .request("GET", "http://127.0.0.1:9/", &[], None)
+            .expect_err("offline request fails");
+        assert!(err.to_string().contains("--offline"));
+    }
+
+    #[test]
+    fn only_transient_statuses_are_retried() {
+        assert!(is_retryable("GET", 503));
+        assert!(is_retryable("PATCH", 502));
+        assert!(is_retryable("GET", 429));
+        assert!(!is_retryable("GET", 404));
+        assert!(!is_retryable("GET", 401));
+        assert!(!is_retryable("POST", 503));
+        assert!(is_retryable("POST", 429));
+        assert_eq!(backoff(1), Duration::from_millis(1000));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::utils::sandbox;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    pub fn first_line(&self) -> &str {
+        self.stdout
+            .lines()
+            .chain(self.stderr.lines())
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("")
+    }
+}
+
+// the scanned repo's own config can name programs for git to run (an fsmonitor hook, an
+// external diff, a signature checker); `-c` outranks every config file, so these switch
+// them off whatever the repo says. textconv drivers need `--no-textconv` on the command
+const UNTRUSTED_REPO_CONFIG: [&str; 6] = [
+    "-c",
+    "core.fsmonitor=",
+    "-c",
+    "diff.external=",
+    "-c",
+    "log.showSignature=false",
+];
+
+// returns None when the program is missing, cannot be spawned, or exceeds the timeout.
+pub fn run_with_timeout(program: &str, args: &[&str], timeout: Duration) -> Option<CommandOutput> {
+    run_while(program, args, timeout, || true)
+}
+
+// `keep_going` is asked between polls; once it answers false the program is killed and,
+// as on a timeout, None is returned. with --sandbox, git is only run through `run_git`
+pub fn run_while(
+    program: &str,
+    args: &[&str],
+    timeout: Duration,
+    keep_going: impl FnMut() -> bool,
+) -> Option<CommandOutput> {
+    if refused(program, sandbox::is_sandboxed()) {
+        return None;
+    }
+    spawn(program, args, timeout, keep_going)
+}
+
+pub fn run_git(args: &[&str], timeout: Duration) -> Option<CommandOutput> {
+    run_git_while(args, timeout, || true)
+}
+
+pub fn run_git_while(
+    args: &[&str],
+    timeout: Duration,
+    keep_going: impl FnMut() -> bool,
+) -> Option<CommandOutput> {
+    let args = [&UNTRUSTED_REPO_CONFIG[..], args].concat();
+    spawn("git", &args, timeout, keep_going)
+}
+
+fn refused(program: &str, sandboxed: bool) -> bool {
+    sandboxed && program == "git"
+}
+
+fn spawn(
+    program: &str,
+    args: &[&str],
+    timeout: Duration,
+    mut keep_going: impl FnMut() -> bool,
+) -> Option<CommandOutput> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    let stdout_reader = child.stdout.take().map(spawn_reader);
+    let stderr_reader = child.stderr.take().map(spawn_reader);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline && keep_going() => {
+                thread::sleep(Duration::from_millis(25))
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    Some(CommandOutput {
+        success: status.success(),
+        stdout: join_reader(stdout_reader),
+        stderr: join_reader(stderr_reader),
+    })
+}
+
+fn spawn_reader(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = String::new();
+        let _ = pipe.read_to_string(&mut buffer);
+        buffer
+    })
+}
+
+fn join_reader(reader: Option<thread::JoinHandle<String>>) -> String {
+    reader
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sandboxed_runs_only_start_git_with_the_repo_config_overridden() {
+        assert!(refused("git", true));
+        assert!(!refused("git", false));
+        assert!(!refused("minisign", true));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_program_is_killed_once_the_caller_stops_waiting() {
+        let started = Instant::now();
+        let mut polls = 0;
+        let output = run_while("sleep", &["5"], Duration::from_secs(30), || {
+            polls += 1;
+            polls < 3
+        });
+        assert!(output.is_none());
+        assert_eq!(polls, 3);
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::{Result, bail};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SANDBOX: AtomicBool = AtomicBool::new(false);
+
+// `--sandbox` is for pointing devguard at untrusted code: nothing is written anywhere and,
+// because it implies `--offline`, nothing goes over the network. every write path calls
+// `ensure_allowed`, and the CLI checks the requested outputs before the scan starts
+pub fn set_sandbox(sandbox: bool) {
+    SANDBOX.store(sandbox, Ordering::SeqCst);
+}
+
+pub fn is_sandboxed() -> bool {
+    SANDBOX.load(Ordering::SeqCst)
+        || std::env::var("DEVGUARD_SANDBOX")
+            .is_ok_and(|value| !matches!(value.trim(), "" | "0" | "false"))
+}
+
+pub fn ensure_allowed(action: &str) -> Result<()> {
+    check(is_sandboxed(), action)
+}
+
+fn check(sandboxed: bool, action: &str) -> Result<()> {
+    if sandboxed {
+        bail!(
+            "{} is not allowed with --sandbox, which forbids writes and network access",
+            action
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sandbox_refuses_side_effects() {
+        check(false, "writing report.json").expect("allowed outside the sandbox");
+        let err = check(true, "writing report.json").expect_err("refused in the sandbox");
+        assert_eq!(
+            err.to_string(),
+            "writing report.json is not allowed with --sandbox, which forbids writes and network access"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use serde::Serialize;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DotenvEntry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Utf16Order {
+    Little,
+    Big,
+}
+
+// BOM first, then the NUL pattern of mostly-ASCII UTF-16 text: every other byte is zero.
+fn detect_utf16(bytes: &[u8]) -> Option<Utf16Order> {
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return Some(Utf16Order::Little);
+    }
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        return Some(Utf16Order::Big);
+    }
+
+    let sample = &bytes[..bytes.len().min(8192) & !1];
+    if sample.len() < 4 {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let even_nuls = sample.iter().step_by(2).filter(|byte| **byte == 0).count();
+    let odd_nuls = sample
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .filter(|byte| **byte == 0)
+        .count();
+    if odd_nuls * 10 >= pairs * 9 && even_nuls == 0 {
+        Some(Utf16Order::Little)
+    } else if even_nuls * 10 >= pairs * 9 && odd_nuls == 0 {
+        Some(Utf16Order::Big)
+    } else {
+        None
+    }
+}
+
+pub fn is_utf16(bytes: &[u8]) -> bool {
+    detect_utf16(bytes).is_some()
+}
+
+pub fn is_likely_binary(bytes: &[u8]) -> bool {
+    if detect_utf16(bytes).is_some() {
+        return false;
+    }
+
+    let sample = &bytes[..bytes.len().min(8192)];
+    if sample.contains(&0) {
+        return true;
+    }
+    // binaries without early NULs still tend to be dense with control bytes
+    let control = sample
+        .iter()
+        .filter(|byte| **byte < 0x20 && !matches!(**byte, b'\t' | b'\n' | b'\r' | 0x0C | 0x1B))
+        .count();
+    control * 10 > sample.len()
+}
+
+pub fn decode_text(bytes: &[u8]) -> Cow<'_, str> {
+    let Some(order) = detect_utf16(bytes) else {
+        let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+        return String::from_utf8_lossy(bytes);
+    };
+
+    let body = if bytes.starts_with(&[0xFF, 0xFE]) || bytes.starts_with(&[0xFE, 0xFF]) {
+        &bytes[2..]
+    } else {
+        bytes
+    };
+    let units = body.chunks_exact(2).map(|pair| match order {
+        Utf16Order::Little => u16::from_le_bytes([pair[0], pair[1]]),
+        Utf16Order::Big => u16::from_be_bytes([pair[0], pair[1]]),
+    });
+    Cow::Owned(
+        char::decode_utf16(units)
+            .map(|unit| unit.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect(),
+    )
+}
+
+pub fn parse_dotenv(content: &str) -> Vec<DotenvEntry> {
+    let mut entries = Vec::new();
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw_line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !line.contains('=') {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value_raw = parts.next().unwrap_or("").trim();
+        if key.is_empty() {
+            continue;
+        }
+
+        entries.push(DotenvEntry {
+            key: key.to_string(),
+            value: strip_quotes(value_raw),
+            line: line_no,
+        });
+    }
+
+    entries
+}
+
+// true when nearly every meaningful line is a `KEY=value` assignment with an env-style key
+pub fn looks_like_dotenv(content: &str) -> bool {
+    let meaningful = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .count();
+    let assignments = parse_dotenv(content)
+        .iter()
+        .filter(|entry| {
+            let key = entry
+                .key
+                .strip_prefix("export ")
+                .unwrap_or(&entry.key)
+                .trim();
+            key.chars()
+                .next()
+                .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+                && key
+                    .chars()
+                    .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_')
+        })
+        .count();
+    assignments > 0 && assignments * 10 >= meaningful * 8
+}
+
+fn strip_quotes(value: &str) -> String {
+    if value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+    {
+        value[1..value.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dotenv_lines() {
+        let input = r#"
+# comment
+DATABASE_URL=postgres://localhost/dev
+STRIPE_SECRET_KEY="sk_test_abc123abc123abc1"
+EMPTY=
+"#;
+        let parsed = parse_dotenv(input);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].key, "DATABASE_URL");
+        assert_eq!(parsed[1].value, "sk_test_abc123abc123abc1");
+        assert_eq!(parsed[2].value, "");
+    }
+
+    #[test]
+    fn recognizes_dotenv_shaped_content() {
+        assert!(looks_like_dotenv(
+            "# prod\nAPI_KEY=abc\nexport DB_URL=postgres://x\n"
+        ));
+        assert!(!looks_like_dotenv(
+            "fn main() {\n    let x = 1;\n}\nKEY=1\n"
+        ));
+        assert!(!looks_like_dotenv("[section]\nkey = value\n"));
+    }
+
+    #[test]
+    fn ignores_invalid_or_comment_lines() {
+        let input = r#"
+# ignored
+NOT_VALID
+=ALSO_INVALID
+VALID_KEY=value
+"#;
+        let parsed = parse_dotenv(input);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].key, "VALID_KEY");
+        assert_eq!(parsed[0].value, "value");
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result, bail};
+use flate2::read::DeflateDecoder;
+use std::io::Read;
+
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+const DIRECTORY_ENTRY: u32 = 0x0201_4b50;
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+// just enough of the zip format to read office documents: the central directory, stored
+// and deflated entries, no zip64, no encryption
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub name: String,
+    method: u16,
+    compressed_size: usize,
+    local_offset: usize,
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+pub fn entries(data: &[u8]) -> Result<Vec<Entry>> {
+    // the end record is 22 bytes plus a comment of up to 64 KiB
+    let search_from = data.len().saturating_sub(22 + u16::MAX as usize);
+    let Some(end) = (search_from..data.len().saturating_sub(21))
+        .rev()
+        .find(|offset| u32_at(data, *offset) == Some(END_OF_DIRECTORY))
+    else {
+        bail!("not a zip archive");
+    };
+    let count = u16_at(data, end + 10).context("truncated zip end record")? as usize;
+    let mut offset = u32_at(data, end + 16).context("truncated zip end record")? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(data, offset) != Some(DIRECTORY_ENTRY) {
+            bail!("corrupt zip central directory");
+        }
+        let field16 = |at| u16_at(data, offset + at).context("truncated zip directory entry");
+        let field32 = |at| u32_at(data, offset + at).context("truncated zip directory entry");
+        let method = field16(10)?;
+        let compressed_size = field32(20)?;
+        let size = field32(24)?;
+        let name_len = field16(28)? as usize;
+        let extra_len = field16(30)? as usize;
+        let comment_len = field16(32)? as usize;
+        let local_offset = field32(42)?;
+        if [compressed_size, size, local_offset].contains(&u32::MAX) {
+            bail!("zip64 archives are not supported");
+        }
+        let name = data
+            .get(offset + 46..offset + 46 + name_len)
+            .context("truncated zip entry name")?;
+        entries.push(Entry {
+            name: String::from_utf8_lossy(name).into_owned(),
+            method,
+            compressed_size: compressed_size as usize,
+            local_offset: local_offset as usize,
+        });
+        offset += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(entries)
+}
+
+// `limit` caps the inflated size, whatever the directory claims
+pub fn read_entry(data: &[u8], entry: &Entry, limit: usize) -> Result<Vec<u8>> {
+    let header = entry.local_offset;
+    if u32_at(data, header) != Some(LOCAL_HEADER) {
+        bail!("corrupt zip entry {}", entry.name);
+    }
+    let name_len = u16_at(data, header + 26).context("truncated zip entry")? as usize;
+    let extra_len = u16_at(data, header + 28).context("truncated zip entry")? as usize;
+    let start = header + 30 + name_len + extra_len;
+    let compressed = data
+        .get(start..start + entry.compressed_size)
+        .with_context(|| format!("truncated zip entry {}", entry.name))?;
+
+    let mut out = Vec::new();
+    match entry.method {
+        STORED => out.extend_from_slice(&compressed[..compressed.len().min(limit)]),
+        DEFLATED => {
+            DeflateDecoder::new(compressed)
+                .take(limit as u64)
+                .read_to_end(&mut out)
+                .with_context(|| format!("failed inflating zip entry {}", entry.name))?;
+        }
+        other => bail!(
+            "zip entry {} uses unsupported compression method {}",
+            entry.name,
+            other
+        ),
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::DeflateEncoder;
+    use std::io::Write;
+
+    // laid out the way zip writers do: local headers and data, then the central directory
+    fn archive(files: &[(&str, &[u8], bool)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut directory = Vec::new();
+        for (name, content, deflate) in files {
+            let body = if *deflate {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(content).expect("deflated");
+                encoder.finish().expect("deflated")
+            } else {
+                content.to_vec()
+            };
+            let method: u16 = if *deflate { DEFLATED } else { STORED };
+            let offset = data.len() as u32;
+            data.extend(LOCAL_HEADER.to_le_bytes());
+            data.extend([0u8; 4]);
+            data.extend(method.to_le_bytes());
+            data.extend([0u8; 16]);
+            data.extend((name.len() as u16).to_le_bytes());
+            data.extend(0u16.to_le_bytes());
+            data.extend(name.as_bytes());
+            data.extend(&body);
+
+            directory.extend(DIRECTORY_ENTRY.to_le_bytes());
+            directory.extend([0u8; 6]);
+            directory.extend(method.to_le_bytes());
+            directory.extend([0u8; 8]);
+            directory.extend((body.len() as u32).to_le_bytes());
+            directory.extend((content.len() as u32).to_le_bytes());
+            directory.extend((name.len() as u16).to_le_bytes());
+            directory.extend([0u8; 12]);
+            directory.extend(offset.to_le_bytes());
+            directory.extend(name.as_bytes());
+        }
+        let directory_offset = data.len() as u32;
+        let directory_len = directory.len() as u32;
+        data.extend(directory);
+        data.extend(END_OF_DIRECTORY.to_le_bytes());
+        data.extend([0u8; 4]);
+        data.extend((files.len() as u16).to_le_bytes());
+        data.extend((files.len() as u16).to_le_bytes());
+        data.extend(directory_len.to_le_bytes());
+        data.extend(directory_offset.to_le_bytes());
+        data.extend(0u16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn stored_and_deflated_entries_are_read_within_the_limit() {
+        let data = archive(&[
+            ("mimetype", b"application/zip", false),
+            ("word/document.xml", &b"<w:t>hello</w:t>".repeat(50), true),
+        ]);
+        let listed = entries(&data).expect("valid archive");
+        let names = listed
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["mimetype", "word/document.xml"]);
+        assert_eq!(
+            read_entry(&data, &listed[0], 1024).expect("stored"),
+            b"application/zip"
+        );
+        let inflated = read_entry(&data, &listed[1], 1024).expect("deflated");
+        assert_eq!(inflated.len(), 800);
+        assert_eq!(read_entry(&data, &listed[1], 16).expect("capped").len(), 16);
+        assert!(entries(b"not a zip").is_err());
+    }
+}